@@ -1,38 +1,451 @@
+use std::f64::consts::FRAC_PI_2;
+
 use eframe::epi;
-use egui::{TopBottomPanel, CentralPanel, Color32, Vec2};
-use egui::plot::Plot;
+use egui::{TopBottomPanel, CentralPanel, Color32, Vec2, ComboBox, Slider, DragValue, Grid, ScrollArea, Ui};
+use egui::plot::{Plot, Legend};
+
+use planetary_transfer::catalog::{self, Body};
+use planetary_transfer::delta_v_map::{self, DeltaV, LOW_ORBIT_RADII};
+use planetary_transfer::maneuvers::{self, Apsis, Maneuver};
+use planetary_transfer::propagator::{Verification, Verifying, PROPAGATION_STEPS};
+use planetary_transfer::geometry::Frame;
+use planetary_transfer::{Arrival, ArrivalEntry, BurnLocation, Calendar, Leg, ParentEntry, Recorder, Replay, ScenarioFile, SystemBody, SystemFile, Mass, Distance, DistanceUnit, Quantity, Velocity, Duration, Parent, Planet, Transfer, Orbit, Explained, FiniteBurn, LaunchVehicle, Optimum, Mission, ResonantOrbit, Sensitivity, Parameter, Perturbation, SurfaceEffect, SurfaceForces, RandomSystem, TransferSummary, round_to, significant, SIGNIFICANT_FIGURES};
+
+use transfer_widget::widgets::{SliderWithText, QuantitySlider, QuantityDragValue, Timeline, OrbitInput, InputMode, OrbitParameters, distance_units, mass_units, velocity_units};
+use crate::model::{AppState, ArrivalModel, Budget, BurnInputs, Burns, DepartureModel, DerivedModel, MissionState};
+use crate::palette::{Command, Palette};
+use crate::tour::{Facts, Step, Tour, HIGHLIGHT};
+use crate::worker::{Job, Worker};
+use transfer_widget::plotting::{Protractor, TransferPlot, VelocityPlot, ResonancePlot, MoonPlot, MissionPlot, RelativePlot, BodySizing, body_color, marker_shape, points_per_unit};
+
+const LAUNCH_WINDOWS: usize = 5;
+const MAX_REVOLUTIONS: u32 = planetary_transfer::SCENARIO_MAX_REVOLUTIONS;
+//Drift in degrees of the plane of the parking orbit by the launch window past which it is warned about
+const NODAL_DRIFT_WARNING: f64 = 5.0;
+//Largest headroom of the delta-v slider, outward transfers going well past escaping the parent
+const MAX_HEADROOM: f64 = 5.0;
+//Steps of the numerical check integrated in a slice of work of the worker, about a frame's worth on the web
+const VERIFICATION_SLICE: usize = 1_000;
+//Colors given in turn to the bodies added to the custom system
+const SYSTEM_COLORS: [[u8; 3]; 6] = [[40, 122, 184], [193, 68, 14], [120, 176, 60], [227, 187, 118], [91, 93, 223], [190, 190, 190]];
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+enum Tab {
+    Transfer,
+    Resonance,
+    Maneuvers,
+    DeltaVMap,
+}
 
-use planetary_transfer::{Mass, Distance, Velocity, Parent, Planet, Transfer};
+//Days and years the durations are given in
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+enum TimeUnits {
+    Earth,
+    Sols,
+    Kerbin,
+    //Those of the target picked from the catalog, Earth ones otherwise
+    TargetSidereal,
+    TargetLocal,
+    Custom,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+enum DeltaVColumn {
+    Origin,
+    Target,
+    DeltaV,
+}
 
-use crate::widgets::SliderWithText;
-use crate::plotting::{Protractor, TransferPlot};
+//Elements of the diagram listed in its legend, each hidden on its own
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Layers {
+    origin: bool,
+    target: bool,
+    transfer: bool,
+    markers: bool,
+    protractor: bool,
+}
 
+impl Default for Layers {
+    fn default() -> Self {
+        Self {origin: true, target: true, transfer: true, markers: true, protractor: true}
+    }
+}
+
+//Actions of the command palette
+#[derive(Clone, Copy)]
+enum Action {
+    Tab(Tab),
+    Parent(usize),
+    Origin(usize),
+    Target(usize),
+    SwapBodies,
+    Hohmann,
+    Optimize,
+    CopySummary,
+    CopyDerivation,
+    SaveScenario,
+    OpenScenario,
+    TimeMarkers,
+    LaunchWindows,
+    Advanced,
+    DarkMode,
+    Tour,
+    Surprise,
+}
+
+//Inputs everything shown is derived from, the results being only recomputed when one of them changes
+#[derive(Clone, PartialEq)]
+struct Scenario {
+    state: AppState,
+    mission: MissionState,
+}
+
+struct Computed {
+    scenario: Scenario,
+    //Sentences describing the transfer in the calendar they were formatted in, dropped along with the transfer
+    texts: Option<Texts>,
+    //Departure burns with the inputs they were integrated from, kept over changes of the scenario not affecting them
+    burns: Option<(BurnInputs, Burns)>,
+    //Inputs of the burns being integrated by the worker
+    burning: Option<BurnInputs>,
+    verification: Option<Verification>,
+    perturbation: Option<Perturbation>,
+    surface_effect: Option<SurfaceEffect>,
+}
+
+impl Computed {
+    //None until the worker is done integrating the burns of new inputs
+    fn burns(&mut self, inputs: BurnInputs, worker: &mut Worker<Burning>) -> Option<Burns> {
+        if let Some((_, burns)) = self.burns.filter(|(cached, _)| *cached == inputs) {
+            return Some(burns);
+        }
+        if self.burning != Some(inputs) {
+            worker.start(Burning { inputs, finite_burn: None });
+            self.burning = Some(inputs);
+        }
+        None
+    }
+}
+
+//Sentences of the transfer panel following from the transfer and the calendar alone
+struct Texts {
+    calendar: Calendar,
+    warnings: Vec<String>,
+    time_of_flight: String,
+    c3: String,
+    approach: String,
+    orbit: String,
+    //Only shown with the advanced options
+    sensitivities: Vec<String>,
+    miss: String,
+    tolerance: Option<String>,
+}
+
+impl Texts {
+    fn new(transfer: &Transfer, calendar: Calendar, phase_angle: String, advanced: bool) -> Self {
+        let sensitivities = if advanced {Sensitivity::all(transfer)} else {Vec::new()};
+        let miss = transfer.miss_sensitivity();
+        Self {
+            calendar,
+            warnings: transfer.warnings().iter().map(|warning| warning.to_string()).collect(),
+            time_of_flight: format!("The transfer will take {}, departing with a phase angle of {}.", transfer.time_of_flight().formatted_in(&calendar), phase_angle),
+            c3: format!(
+                "C3 is {} km²/s², with a hyperbolic excess speed of {} at departure and {} at arrival.",
+                significant(transfer.c3(), SIGNIFICANT_FIGURES),
+                transfer.departure_v_infinity().formatted(),
+                transfer.arrival_v_infinity().formatted(),
+            ),
+            //Side of the target the spacecraft comes from, setting the orientation of the capture orbit
+            approach: transfer.arrival_approach().description(),
+            orbit: match transfer.apoapsis() {
+                Some(apoapsis) => format!("The transfer orbit has a periapsis of {} and an apoapsis of {}.", transfer.periapsis().formatted(), apoapsis.formatted()),
+                None => format!("The transfer orbit is hyperbolic, with a periapsis of {}.", transfer.periapsis().formatted()),
+            },
+            sensitivities: sensitivities.iter().map(|sensitivity| {
+                let parameter = match sensitivity.parameter {
+                    Parameter::OriginSma => "semi-major axis of the origin",
+                    Parameter::TargetSma => "semi-major axis of the target",
+                    Parameter::Mass => "mass of the parent",
+                };
+                format!(
+                    "±{} and ±{} per 1% error on the {}.",
+                    Velocity::from_meters_per_second(sensitivity.delta_v.mps().abs()).formatted(),
+                    Duration::from_seconds(sensitivity.time_of_flight.s().abs()).formatted_in(&calendar),
+                    parameter,
+                )
+            }).collect(),
+            miss: format!(
+                "1 m/s error ⇒ {} miss, 1 s late ⇒ {} miss.",
+                miss.miss(Velocity::from_meters_per_second(1.0), Duration::from_seconds(0.0)).formatted(),
+                miss.miss(Velocity::from_meters_per_second(0.0), Duration::from_seconds(1.0)).formatted(),
+            ),
+            tolerance: transfer.target().sphere_of_influence().map(|sphere_of_influence| format!(
+                "Reaching the sphere of influence of the target takes a burn within {} and {} of the nominal one.",
+                miss.delta_v_tolerance(sphere_of_influence).formatted(),
+                miss.timing_tolerance(sphere_of_influence).formatted(),
+            )),
+        }
+    }
+}
+
+//Numerical check, perturbation by a third body and surface forces, each being a slice of work for the worker,
+//the numerical check being integrated a slice of steps at a time
+struct Analysis {
+    scenario: Scenario,
+    transfer: Transfer,
+    verifying: Option<Verifying>,
+    third_body: Option<(Planet, f64)>,
+    surface_forces: SurfaceForces,
+    verification: Option<Verification>,
+    surface_effect: Option<SurfaceEffect>,
+}
+
+struct Analyzed {
+    scenario: Scenario,
+    verification: Option<Verification>,
+    perturbation: Option<Perturbation>,
+    surface_effect: Option<SurfaceEffect>,
+}
+
+impl Job for Analysis {
+    type Output = Analyzed;
+
+    fn step(&mut self) -> Option<Analyzed> {
+        if let Some(verifying) = &mut self.verifying {
+            self.verification = verifying.advance(VERIFICATION_SLICE);
+            if self.verification.is_some() {
+                self.verifying = None;
+            }
+            return None;
+        }
+        if self.surface_forces.enabled() && self.surface_effect.is_none() {
+            self.surface_effect = self.transfer.surface_effect(&self.surface_forces);
+            return None;
+        }
+        Some(Analyzed {
+            scenario: self.scenario.clone(),
+            verification: self.verification.take(),
+            surface_effect: self.surface_effect.take(),
+            perturbation: self.third_body.and_then(|(third_body, longitude)| self.transfer.perturbation(&third_body, longitude)),
+        })
+    }
+}
+
+//Cheapest departure and arrival within the range of the slider for the scenario it was asked for, falling back to a
+//rendezvous when the spacecraft can't be captured, each search being a slice of work for the worker
+struct Optimization {
+    scenario: Scenario,
+    transfer: Transfer,
+    arrival: Arrival,
+    min: Velocity,
+    max: Velocity,
+}
+
+impl Job for Optimization {
+    type Output = (Scenario, Option<Optimum>);
+
+    fn step(&mut self) -> Option<(Scenario, Option<Optimum>)> {
+        let optimum = self.transfer.cheapest(self.arrival, self.min, self.max);
+        if optimum.is_none() && self.arrival != Arrival::Rendezvous {
+            self.arrival = Arrival::Rendezvous;
+            return None;
+        }
+        Some((self.scenario.clone(), optimum))
+    }
+}
+
+//Finite departure burn and maneuver node, each being a slice of work for the worker
+struct Burning {
+    inputs: BurnInputs,
+    finite_burn: Option<Option<FiniteBurn>>,
+}
+
+impl Job for Burning {
+    type Output = (BurnInputs, Burns);
+
+    fn step(&mut self) -> Option<(BurnInputs, Burns)> {
+        match self.finite_burn {
+            None => {
+                self.finite_burn = Some(Burns::finite_burn(&self.inputs));
+                None
+            }
+            Some(finite_burn) => Some((self.inputs, Burns {finite_burn, node: Burns::node(&self.inputs)})),
+        }
+    }
+}
+
+//Everything but the catalogs and the text of the inputs is restored on startup
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct Gui {
-    origin_sma: Distance,
-    target_sma: Distance,
-    mass: Mass,
-    velocity: Velocity,
-    hohmann: bool,
+    tab: Tab,
+    dark_mode: bool,
+    time_units: TimeUnits,
+    custom_day: Duration,
+    custom_year: Duration,
+    //Current date, as time since the epoch of the calendar
+    epoch: Duration,
+    //Inputs of the transfer, derived into what the panels show by DerivedModel
+    state: AppState,
+    #[serde(skip)]
+    bodies: Vec<Body>,
+    //Bodies of a custom system around the custom parent, any two of which can be picked as origin and target
+    system: Vec<SystemBody>,
+    origin_system: Option<usize>,
+    target_system: Option<usize>,
+    system_path: String,
+    #[serde(skip)]
+    system_status: Option<String>,
+    time_markers: bool,
+    hohmann_ghost: bool,
+    layers: Layers,
+    velocity_plot: bool,
+    launch_windows: bool,
+    phase_angle: f64,
+    launch_window: Option<usize>,
+    arrive_by: bool,
+    //Date the target must be reached by, as time since the epoch of the calendar
+    deadline: Duration,
+    return_leg: bool,
+    //None for the frame of the parent
+    frame: Option<Frame>,
+    body_sizing: BodySizing,
+    scenario_path: String,
+    #[serde(skip)]
+    scenario_status: Option<String>,
+    //Session being recorded or replayed, with the time it started at
+    #[serde(skip)]
+    recorder: Option<(f64, Recorder)>,
+    #[serde(skip)]
+    replay: Option<(f64, Replay)>,
+    #[serde(skip)]
+    launch_vehicles: Vec<LaunchVehicle>,
+    launch_vehicle: usize,
+    //Inputs of the departure and arrival, derived into what their panels show by DepartureModel and ArrivalModel
+    mission: MissionState,
+    moon: bool,
+    moon_sma: Distance,
+    resonance: u32,
+    resonance_revolutions: u32,
+    maneuver_periapsis: Distance,
+    maneuver_apoapsis: Distance,
+    maneuver_apsis: Distance,
+    maneuver_inclination: f64,
+    #[serde(skip)]
+    delta_v_map: Vec<DeltaV>,
+    delta_v_column: DeltaVColumn,
+    delta_v_descending: bool,
+    //Recomputing the transfer and redrawing every frame, rather than only when the inputs change
+    fixed_update: bool,
+    //Model of the last inputs it was derived from
+    #[serde(skip)]
+    derived: Option<(Scenario, DerivedModel)>,
+    #[serde(skip)]
+    computed: Option<Computed>,
+    #[serde(skip)]
+    worker: Worker<Analysis>,
+    #[serde(skip)]
+    optimizer: Worker<Optimization>,
+    #[serde(skip)]
+    burner: Worker<Burning>,
+    #[serde(skip)]
+    explained: Option<Explained>,
+    #[serde(skip)]
+    palette: Palette,
+    #[serde(skip)]
+    tour: Tour,
+    //Whether the tour was shown on a first run
+    tour_seen: bool,
+    //Seed of the last random system, kept so that the next ones follow from it
+    seed: u64,
+    #[serde(skip)]
+    seed_text: String,
 
+    #[serde(skip)]
     origin_sma_text: String,
+    #[serde(skip)]
     target_sma_text: String,
+    #[serde(skip)]
     mass_text: String,
+    #[serde(skip)]
     velocity_text: String,
+    #[serde(skip)]
+    origin_mass_text: String,
+    #[serde(skip)]
+    target_mass_text: String,
+    #[serde(skip)]
+    capture_radius_text: String,
 }
 
 impl Default for Gui {
     fn default() -> Self {
+        let bodies = catalog::bodies();
         Self {
-            origin_sma: Distance::from_astronomical_unit(1.0),
-            target_sma: Distance::from_astronomical_unit(1.52366),
-            mass: Mass::from_solar(1.0),
-            velocity: Velocity::from_kilometers_per_second(30.0),
-            hohmann: true,
+            tab: Tab::Transfer,
+            dark_mode: true,
+            time_units: TimeUnits::Earth,
+            custom_day: Duration::from_days(1.0),
+            custom_year: Duration::from_years(1.0),
+            epoch: Duration::from_seconds(0.0),
+            state: AppState::default(),
+            delta_v_map: delta_v_map::delta_v_map(&bodies),
+            bodies,
+            system: Vec::new(),
+            origin_system: None,
+            target_system: None,
+            system_path: format!("system.{}", planetary_transfer::SYSTEM_EXTENSION),
+            system_status: None,
+            time_markers: false,
+            hohmann_ghost: true,
+            layers: Layers::default(),
+            velocity_plot: false,
+            launch_windows: false,
+            phase_angle: 0.0,
+            launch_window: None,
+            arrive_by: false,
+            deadline: Duration::from_years(2.0),
+            return_leg: false,
+            frame: None,
+            body_sizing: BodySizing::default(),
+            scenario_path: format!("scenario.{}", planetary_transfer::SCENARIO_EXTENSION),
+            scenario_status: None,
+            recorder: None,
+            replay: None,
+            launch_vehicles: LaunchVehicle::catalog(),
+            launch_vehicle: 0,
+            mission: MissionState::default(),
+            moon: false,
+            moon_sma: Distance::from_kilometers(9_376.0),
+            resonance: 3,
+            resonance_revolutions: 4,
+            maneuver_periapsis: Distance::from_astronomical_unit(1.0),
+            maneuver_apoapsis: Distance::from_astronomical_unit(1.0),
+            maneuver_apsis: Distance::from_astronomical_unit(1.52366),
+            maneuver_inclination: 0.0,
+            delta_v_column: DeltaVColumn::DeltaV,
+            delta_v_descending: false,
+            fixed_update: false,
+            derived: None,
+            computed: None,
+            worker: Worker::default(),
+            optimizer: Worker::default(),
+            burner: Worker::default(),
+            explained: None,
+            palette: Palette::default(),
+            tour: Tour::default(),
+            tour_seen: false,
+            seed: 0,
+            seed_text: "".to_string(),
 
             origin_sma_text: "".to_string(),
             target_sma_text: "".to_string(),
             mass_text: "".to_string(),
             velocity_text: "".to_string(),
+            origin_mass_text: "".to_string(),
+            target_mass_text: "".to_string(),
+            capture_radius_text: "".to_string(),
         }
     }
 }
@@ -42,33 +455,176 @@ impl epi::App for Gui {
         Vec2::new(f32::MAX, f32::MAX)
     }
     
+    fn setup(&mut self, ctx: &egui::CtxRef, _frame: &eframe::epi::Frame, storage: Option<&dyn epi::Storage>) {
+        if let Some(storage) = storage {
+            *self = epi::get_value(storage, epi::APP_KEY).unwrap_or_default();
+            //Inputs saved by older versions among the other settings
+            if let Some(state) = AppState::migrate(storage) {self.state = state}
+            if let Some(mission) = MissionState::migrate(storage) {self.mission = mission}
+        }
+        //Indices of a previous version of the catalogs may no longer be valid
+        if matches!(self.state.parent_body, Some(i) if i >= self.bodies.len()) {self.state.parent_body = None}
+        if matches!(self.state.origin_body, Some(i) if i >= self.bodies.len()) {self.state.origin_body = None}
+        if matches!(self.state.target_body, Some(i) if i >= self.bodies.len()) {self.state.target_body = None}
+        if matches!(self.origin_system, Some(i) if i >= self.system.len()) {self.origin_system = None}
+        if matches!(self.target_system, Some(i) if i >= self.system.len()) {self.target_system = None}
+        if self.launch_vehicle >= self.launch_vehicles.len() {self.launch_vehicle = 0}
+        ctx.set_visuals(if self.dark_mode {egui::Visuals::dark()} else {egui::Visuals::light()});
+    }
+
+    fn save(&mut self, storage: &mut dyn epi::Storage) {
+        epi::set_value(storage, epi::APP_KEY, self);
+    }
+
     fn update(&mut self, ctx: &egui::CtxRef, _frame: &eframe::epi::Frame) {
 
+        //A replayed session sets the parameters as they were at the time of the recording
+        if let Some((start, replay)) = &mut self.replay {
+            let keyframe = replay.advance(Duration::from_seconds(ctx.input().time - *start)).cloned();
+            if replay.finished() {
+                self.replay = None;
+                self.scenario_status = Some("Finished replaying the recording.".to_string());
+            }
+            if let Some(keyframe) = keyframe {
+                self.load_leg(&keyframe.parent, &keyframe.leg);
+            }
+            ctx.request_repaint();
+        }
+
         let portrait = ctx.input().screen_rect.aspect_ratio() <= 0.6;
 
         let color_mode = if *&ctx.style().visuals.dark_mode {Color32::WHITE} else {Color32::BLACK};
 
-        //Create the parent
-        let parent = Parent::new(self.mass);
-            
-        //Create the two planet used for the transfer
-        let origin = Planet::new(self.origin_sma, parent);
-        let target = Planet::new(self.target_sma, parent);
+        //The optimum found by the worker is only applied to the scenario it was searched for
+        if let Some((scenario, optimum)) = self.optimizer.poll() {
+            if let Some(optimum) = optimum.filter(|_| scenario == self.scenario()) {
+                self.state.hohmann = false;
+                self.state.velocity = optimum.delta_v;
+            }
+        }
+        if self.optimizer.busy() {ctx.request_repaint()}
+
+        //Inputs brought back within what the transfer allows, everything shown following from them
+        //The model of the previous frame is kept as long as none of the inputs changed
+        let (scenario, model) = match self.derived.take() {
+            Some((scenario, model)) if !self.fixed_update && scenario == self.scenario() => (scenario, model),
+            _ => match DerivedModel::new(&mut self.state, &self.bodies) {
+                Some(model) => (self.scenario(), model),
+                None => {
+                    ctx.request_repaint();
+                    return;
+                }
+            },
+        };
+        if self.fixed_update {ctx.request_repaint()}
+        let (parent, target, transfer, min, max) = (model.parent, model.target, model.transfer, model.min, model.max);
+        let (sma_min, sma_max) = (model.sma_min, model.sma_max);
+        //Forget the bodies of the custom system picked as origin or target once their orbit or the parent is changed
+        for (body, sma) in [(&mut self.origin_system, self.state.origin_sma), (&mut self.target_system, self.state.target_sma)] {
+            if let Some(i) = *body {
+                if self.state.parent_body.is_some() || (self.system[i].sma - sma.m()).abs() > self.system[i].sma * 1E-9 {
+                    *body = None;
+                }
+            }
+        }
+        let calendar = self.calendar(&transfer);
+
+        //Changes of the parameters are recorded as the transfer they end up in
+        if self.recorder.is_some() {
+            let mut file = self.scenario_file(&transfer);
+            let time = ctx.input().time;
+            if let Some((start, recorder)) = &mut self.recorder {
+                recorder.record(Duration::from_seconds(time - *start), file.parent, file.legs.remove(0));
+            }
+        }
 
-        //Create a transfer with the two previously created planets
-        let mut transfer = Transfer::new(origin, target);
-        if self.hohmann {self.velocity = transfer.delta_v_hohmann()};
-        transfer.set_delta_v(self.velocity);
+        let commands = self.commands();
+        if let Some(action) = self.palette.show(ctx, &commands) {
+            self.perform(action, ctx, &model);
+            ctx.request_repaint();
+        }
+        if !self.tour_seen {
+            self.tour_seen = true;
+            self.perform(Action::Tour, ctx, &model);
+        }
 
-        let min = transfer.min_velocity();
-        let max = transfer.max_velocity();
+        let arrival = ArrivalModel::new(&self.state, &self.mission, &model, &self.bodies);
+        let moon_approach = if self.state.advanced && self.moon {transfer.moon_approach(self.moon_sma)} else {None};
         
+        //Reuse the results of the previous frame when nothing they depend on changed
+        //The costly ones are left to the worker, coming back in a later frame
+        let mut computed = match self.computed.take() {
+            Some(computed) if computed.scenario == scenario => computed,
+            previous => {
+                let numerical_check = self.state.advanced && self.mission.numerical_check;
+                //Perturbation of the transfer by a third body of the system
+                let third_body = (self.state.advanced && self.mission.third_body).then(|| (
+                    Planet::new(self.mission.third_body_sma, parent).with_mass(self.mission.third_body_mass),
+                    self.mission.third_body_longitude.to_radians(),
+                ));
+                //Only with the advanced options shown
+                let surface_forces = if self.state.advanced {self.mission.surface_forces} else {SurfaceForces::default()};
+                if numerical_check || third_body.is_some() || surface_forces.enabled() {
+                    let verifying = numerical_check.then(|| transfer.verifying(PROPAGATION_STEPS));
+                    self.worker.start(Analysis {scenario: scenario.clone(), transfer, verifying, third_body, surface_forces, verification: None, surface_effect: None});
+                } else {
+                    self.worker.cancel();
+                }
+                Computed {
+                    scenario: scenario.clone(),
+                    texts: None,
+                    burns: previous.as_ref().and_then(|previous| previous.burns),
+                    burning: previous.and_then(|previous| previous.burning),
+                    verification: None,
+                    perturbation: None,
+                    surface_effect: None,
+                }
+            }
+        };
+        if let Some(analyzed) = self.worker.poll().filter(|analyzed| analyzed.scenario == computed.scenario) {
+            computed.verification = analyzed.verification;
+            computed.perturbation = analyzed.perturbation;
+            computed.surface_effect = analyzed.surface_effect;
+        }
+        //Departure from the origin picked from the catalog, through a low parking orbit
+        let departure = self.state.origin_body.filter(|_| self.state.advanced).map(|i| {
+            let mut departure = DepartureModel::new(&self.mission, &self.bodies[i], &transfer, transfer.wait_time_until_window(self.phase_angle.to_radians()));
+            departure.burns = computed.burns(departure.burn_inputs, &mut self.burner);
+            (self.bodies[i].name, departure)
+        });
+        if let Some((inputs, burns)) = self.burner.poll() {
+            computed.burns = Some((inputs, burns));
+            computed.burning = None;
+        }
+        let analyzing = self.worker.busy();
+        if self.burner.busy() {ctx.request_repaint()}
+        if analyzing {ctx.request_repaint()}
+        let (verification, perturbation, surface_effect) = (&computed.verification, computed.perturbation, computed.surface_effect);
+
         //Orbits of the planets and their markers at departure and arrival and the transfer orbit
-        let mut transfer_plot = TransferPlot::new(&transfer, color_mode);
+        let mut transfer_plot = TransferPlot::new(&transfer, &model.geometry, color_mode);
+        if let Some(i) = self.state.origin_body {transfer_plot.set_body_origin(&self.bodies[i])}
+        if let Some(i) = self.state.target_body {transfer_plot.set_body_target(&self.bodies[i])}
+        if let Some(body) = self.origin_system.map(|i| &self.system[i]) {transfer_plot.set_named_origin(&body.name, system_color(body))}
+        if let Some(body) = self.target_system.map(|i| &self.system[i]) {transfer_plot.set_named_target(&body.name, system_color(body))}
+        if self.hohmann_ghost && !self.state.hohmann {transfer_plot.set_reference(&model.hohmann.0, &model.hohmann.1)}
+
+        //The diagram highlights what the tour explains
+        match self.tour.step() {
+            Some(Step::Origin) => transfer_plot.highlight_origin(),
+            Some(Step::Target) => transfer_plot.highlight_target(),
+            Some(Step::Hohmann | Step::Velocity) => transfer_plot.highlight_transfer(),
+            _ => (),
+        }
 
         //Angle measurer
-        let protractor = Protractor::new(transfer.target_true_anomaly_departure(), self.origin_sma.m.max(self.target_sma.m))
-            .color(Color32::GRAY);
+        let protractor_color = if self.tour.at(Step::PhaseAngle) || self.tour.at(Step::Protractor) {HIGHLIGHT} else {Color32::GRAY};
+        let protractor = Protractor::new(transfer.target_true_anomaly_departure(), transfer_plot.to_plot(self.state.origin_sma.max(self.state.target_sma)))
+            .color(protractor_color);
+        let texts = match computed.texts.take() {
+            Some(texts) if !self.fixed_update && texts.calendar == calendar => texts,
+            _ => Texts::new(&transfer, calendar, protractor.text_string(), self.state.advanced),
+        };
 
         if portrait {
             TopBottomPanel::bottom("bottom")
@@ -79,177 +635,729 @@ impl epi::App for Gui {
             
             ui.spacing_mut().slider_width = ui.available_width() - 110.0;
 
-            ui.add_enabled_ui(self.hohmann, |ui| {
+            let (distance_units, mass_units, velocity_units) = (distance_units(), mass_units(), velocity_units());
+
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, Tab::Transfer, "Transfer");
+                ui.selectable_value(&mut self.tab, Tab::Resonance, "Resonance");
+                ui.selectable_value(&mut self.tab, Tab::Maneuvers, "Maneuvers");
+                ui.selectable_value(&mut self.tab, Tab::DeltaVMap, "ΔV map");
+                egui::widgets::global_dark_light_mode_switch(ui);
+                self.dark_mode = ui.visuals().dark_mode;
+                ComboBox::from_id_source("time_units")
+                    .selected_text(time_units_name(self.time_units))
+                    .show_ui(ui, |ui| {
+                        for units in [TimeUnits::Earth, TimeUnits::Sols, TimeUnits::Kerbin, TimeUnits::TargetSidereal, TimeUnits::TargetLocal, TimeUnits::Custom] {
+                            ui.selectable_value(&mut self.time_units, units, time_units_name(units));
+                        }
+                    });
+                if self.time_units == TimeUnits::Custom {
+                    let (mut day, mut year) = (self.custom_day.h(), self.custom_year.s() / self.custom_day.s());
+                    ui.add(DragValue::new(&mut day).speed(0.1).clamp_range(0.1..=10000.0).prefix("Day: ").suffix(" h"));
+                    ui.add(DragValue::new(&mut year).speed(1.0).clamp_range(1.0..=100000.0).prefix("Year: ").suffix(" days"));
+                    self.custom_day = Duration::from_hours(day);
+                    self.custom_year = self.custom_day * year;
+                }
+                ui.checkbox(&mut self.fixed_update, "Fixed update")
+                    .on_hover_text("Recompute the transfer and redraw every frame rather than only when the inputs change");
+                ui.separator();
+                if ui.button("Surprise me").on_hover_text("Random system around a custom parent, drawn again from its seed").clicked() {
+                    self.random_system(RandomSystem::next_seed(self.seed ^ ui.input().time.to_bits()));
+                }
+                ui.add(egui::TextEdit::singleline(&mut self.seed_text).desired_width(150.0).hint_text("Seed"));
+                let seed = self.seed_text.trim().parse::<u64>();
+                if ui.add_enabled(seed.is_ok(), egui::Button::new("Load seed")).clicked() {
+                    self.random_system(seed.unwrap_or_default());
+                }
+            });
+
+            ui.add_enabled_ui(self.state.hohmann, |ui| {
 
                 ui.add_space(5.0);
 
-                ui.label("Semi-major axis of the origin body:");
-
-                let sma_min = Distance::from_kilometers(10.0);
-                let sma_max = Distance::from_astronomical_unit(50.0);
-
-                if self.origin_sma.km > 7_500_000.0 {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.origin_sma.au, &mut self.origin_sma_text, 
-                        sma_min.au..=sma_max.au
-                    )
-                        .suffix(" au")
-                    );
-                    self.origin_sma.au_updated();
-                    if slider.hovered() {transfer_plot.highlight_origin()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_origin(Color32::RED)}
-
-                } else if self.origin_sma.m > 100_000.0 {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.origin_sma.km, &mut self.origin_sma_text, 
-                        sma_min.km..=sma_max.km
-                    )
-                        .suffix(" km")
-                    );
-                    self.origin_sma.km_updated();
-                    if slider.hovered() {transfer_plot.highlight_origin()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_origin(Color32::RED)}
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} of the origin body:", self.state.origin_mode.name()));
+                    input_mode(ui, "origin_mode", &mut self.state.origin_mode, parent.radius().is_some());
+                    body_picker(ui, "origin_body", &self.bodies, self.state.parent_body, &mut self.state.origin_body, &mut self.state.origin_sma, &mut self.state.origin_mass);
+                    //Bodies picked bring their direction along, toggling it forgetting the pick
+                    if let Some(i) = self.state.origin_body {self.state.origin_retrograde = self.bodies[i].retrograde}
+                    ui.checkbox(&mut self.state.origin_retrograde, "Retrograde").on_hover_text("Orbits against the direction of the target");
+                });
+
+
+                let slider = if self.state.origin_mode != InputMode::Sma {
+                    ui.add(OrbitInput::new(self.state.origin_mode, &mut self.state.origin_sma, &mut self.origin_sma_text, parent, sma_min..=sma_max))
                 } else {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.origin_sma.m, &mut self.origin_sma_text, 
-                        sma_min.m..=sma_max.m
-                    )
-                        .suffix(" m")
-                    );
-                    self.origin_sma.m_updated();
-                    if slider.hovered() {transfer_plot.highlight_origin()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_origin(Color32::RED)}
-                }
+                    ui.add(QuantitySlider::new(&mut self.state.origin_sma, &mut self.origin_sma_text, sma_min..=sma_max, &distance_units))
+                };
+                if slider.hovered() {transfer_plot.highlight_origin()}
+                if slider.dragged() | slider.has_focus() {transfer_plot.set_color_origin(Color32::RED)}
+                self.tour.target(Step::Origin, slider.rect);
+
+                orbit_readout(ui, &transfer.origin(), &calendar);
+                self.state.origin_parameters.ui(ui, "origin_parameters");
 
                 ui.add_space(5.0);
 
-                ui.label("Semi-major axis of the target body:");
-
-                if self.target_sma.km > 7500000.0 {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.target_sma.au, &mut self.target_sma_text, 
-                        sma_min.au..=sma_max.au
-                    )
-                        .suffix(" au")
-                    );
-                    self.target_sma.au_updated();
-                    if slider.hovered() {transfer_plot.highlight_target()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_target(Color32::RED)}
-
-                } else if self.target_sma.m > 100_000.0 {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.target_sma.km, &mut self.target_sma_text, 
-                        sma_min.km..=sma_max.km
-                    )
-                        .suffix(" km")
-                    );
-                    self.target_sma.km_updated();
-                    if slider.hovered() {transfer_plot.highlight_target()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_target(Color32::RED)}
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} of the target body:", self.state.target_mode.name()));
+                    input_mode(ui, "target_mode", &mut self.state.target_mode, parent.radius().is_some());
+                    body_picker(ui, "target_body", &self.bodies, self.state.parent_body, &mut self.state.target_body, &mut self.state.target_sma, &mut self.state.target_mass);
+                    if let Some(i) = self.state.target_body {self.state.target_retrograde = self.bodies[i].retrograde}
+                    ui.checkbox(&mut self.state.target_retrograde, "Retrograde").on_hover_text("Orbits against the direction of the origin, as Triton around Neptune");
+                });
+
+                let slider = if self.state.target_mode != InputMode::Sma {
+                    ui.add(OrbitInput::new(self.state.target_mode, &mut self.state.target_sma, &mut self.target_sma_text, parent, sma_min..=sma_max))
                 } else {
-                    let slider = ui.add(SliderWithText::new(
-                        &mut self.target_sma.m, &mut self.target_sma_text, 
-                        sma_min.m..=sma_max.m
-                    )
-                        .suffix(" m")
-                    );
-                    self.target_sma.m_updated();
-                    if slider.hovered() {transfer_plot.highlight_target()}
-                    if slider.dragged() | slider.has_focus() {transfer_plot.set_color_target(Color32::RED)}
+                    ui.add(QuantitySlider::new(&mut self.state.target_sma, &mut self.target_sma_text, sma_min..=sma_max, &distance_units))
+                };
+                if slider.hovered() {transfer_plot.highlight_target()}
+                if slider.dragged() | slider.has_focus() {transfer_plot.set_color_target(Color32::RED)}
+                self.tour.target(Step::Target, slider.rect);
+
+                if self.state.degenerate {
+                    ui.colored_label(Color32::from_rgb(255, 115, 0), "The origin and target share an orbit: reaching the target only takes phasing along it, not a transfer.");
                 }
+                orbit_readout(ui, &transfer.target(), &calendar);
+                self.state.target_parameters.ui(ui, "target_parameters");
 
                 ui.add_space(5.0);
 
-                ui.label("Mass of the parent body:");
+                ui.horizontal(|ui| {
+                    ui.label("Mass of the parent body:");
+
+                    let bodies = &self.bodies;
+                    let selected = self.state.parent_body.map(|i| bodies[i].name).unwrap_or("Custom");
+                    ComboBox::from_id_source("parent_body")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.state.parent_body, None, "Custom");
+                            for (i, body) in bodies.iter().enumerate() {
+                                if ui.selectable_value(&mut self.state.parent_body, Some(i), body.name).clicked() {
+                                    self.state.mass = body.mass;
+                                }
+                            }
+                        });
+                });
 
                 let mass_min = Mass::from_lunar(0.05);
                 let mass_max = Mass::from_solar(100.0);
 
-                if self.mass.jovian > 97.0 {
-                    ui.add(SliderWithText::new(&mut self.mass.solar, &mut self.mass_text, mass_min.solar..=mass_max.solar)
-                        .suffix(" M☉")
-                    );
-                    self.mass.solar_updated();
-                    
-                } else if self.mass.earth > 35.0 {
-                    ui.add(SliderWithText::new(&mut self.mass.jovian, &mut self.mass_text, mass_min.jovian..=mass_max.jovian)
-                        .suffix(" Mj")
-                    );
-                    self.mass.jovian_updated();
-
-                } else if self.mass.lunar > 8.0 {
-                    ui.add(SliderWithText::new(&mut self.mass.earth, &mut self.mass_text, mass_min.earth..=mass_max.earth)
-                        .suffix(" Me")
-                    );
-                    self.mass.earth_updated();
-                } else {
-                    ui.add(SliderWithText::new(&mut self.mass.lunar, &mut self.mass_text, mass_min.lunar..=mass_max.lunar)
-                        .suffix(" Ml")
-                    );
-                    self.mass.lunar_updated(); 
+                let slider = ui.add(QuantitySlider::new(&mut self.state.mass, &mut self.mass_text, mass_min..=mass_max, &mass_units));
+                self.tour.target(Step::Parent, slider.rect);
+
+                ui.checkbox(&mut self.state.binary, "Binary parent");
+
+                egui::CollapsingHeader::new("System editor").show(ui, |ui| self.system_editor(ui));
+
+                if self.state.binary {
+                    ui.horizontal(|ui| {
+                        let mut mass = self.state.companion_mass.solar();
+                        let mut separation = self.state.companion_separation.au();
+                        ui.label("Mass of the companion:");
+                        ui.add(DragValue::new(&mut mass).speed(0.01).clamp_range(0.0001..=100.0).suffix(" M☉"));
+                        ui.label("Separation:");
+                        ui.add(DragValue::new(&mut separation).speed(0.01).clamp_range(0.001..=100.0).suffix(" AU"));
+                        ui.label("Eccentricity:");
+                        ui.add(DragValue::new(&mut self.state.companion_eccentricity).speed(0.01).clamp_range(0.0..=0.8));
+                        self.state.companion_mass = Mass::from_solar(mass);
+                        self.state.companion_separation = Distance::from_astronomical_unit(separation);
+                    });
                 }
             });
 
             ui.add_space(5.0);
 
-            ui.checkbox(&mut self.hohmann, "Hohmann");
+            ui.horizontal(|ui| {
+                let hohmann = ui.checkbox(&mut self.state.hohmann, "Hohmann");
+                self.tour.target(Step::Hohmann, hohmann.rect);
+                info(ui, &mut self.explained, Explained::HohmannDeltaV);
+                ui.add_enabled(!self.state.hohmann, egui::Checkbox::new(&mut self.hohmann_ghost, "Ghost"))
+                    .on_hover_text("Keep the Hohmann transfer drawn behind this one");
+                ui.add(DragValue::new(&mut self.state.headroom).speed(0.01).clamp_range(0.01..=MAX_HEADROOM).prefix("Headroom: "))
+                    .on_hover_text("Extra delta-v offered by the slider, 1 reaching escape outward and the slowest transfer inward");
+                if ui.add_enabled(!self.optimizer.busy(), egui::Button::new("Optimize")).on_hover_text("Cheapest departure and arrival within the range of the slider").clicked() {
+                    self.optimize(&transfer, min, max);
+                }
 
-            if self.velocity.mps.abs() >= 1000.0 {
-                let slider = ui.add(SliderWithText::new(
-                    &mut self.velocity.kps, &mut self.velocity_text,
-                    min.kps..=max.kps
-                )
-                    .suffix(" km/s")
-                    .max_decimals(14)
-                    .enabled_slider(!self.hohmann)
+                let solutions = transfer.revolution_solutions(MAX_REVOLUTIONS);
+                let format_solution = |(revolutions, time_of_flight): (u32, Duration)| format!(
+                    "{} revolutions, {}", revolutions, time_of_flight.formatted_in(&calendar)
                 );
-                self.velocity.kps_updated();
-                if slider.hovered() {transfer_plot.highlight_transfer()}
-                if slider.dragged() | slider.has_focus() {transfer_plot.highlight_transfer()}
-                
-            } else if self.velocity.mmps.abs() >= 1000.0 {
-                let slider = ui.add(SliderWithText::new(
-                    &mut self.velocity.mps, &mut self.velocity_text,
-                    min.mps..=max.mps
-                )
-                    .suffix(" m/s")
-                    .max_decimals(14)
-                    .enabled_slider(!self.hohmann)
+                //Revolutions past the solutions offered, as from an older save, are shown as they are
+                let selected = match solutions.iter().find(|(revolutions, _)| *revolutions == transfer.revolutions()) {
+                    Some(&solution) => format_solution(solution),
+                    None => format!("{} revolutions", transfer.revolutions()),
+                };
+                ComboBox::from_id_source("revolutions")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for solution in solutions {
+                            ui.selectable_value(&mut self.state.revolutions, solution.0, format_solution(solution));
+                        }
+                    });
+            });
+
+            let slider = ui.add(QuantitySlider::new(&mut self.state.velocity, &mut self.velocity_text, min..=max, &velocity_units)
+                .max_decimals(14)
+                .enabled_slider(!self.state.hohmann)
+            );
+            if slider.hovered() {transfer_plot.highlight_transfer()}
+            if slider.dragged() | slider.has_focus() {transfer_plot.highlight_transfer()}
+            self.tour.target(Step::Velocity, slider.rect);
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.time_markers, "Time markers");
+                ui.checkbox(&mut self.velocity_plot, "Velocity plot");
+                ui.checkbox(&mut self.launch_windows, "Launch windows");
+                ui.checkbox(&mut self.return_leg, "Return leg");
+                ui.checkbox(&mut self.state.advanced, "Advanced");
+                ComboBox::from_id_source("frame")
+                    .selected_text(frame_name(self.frame))
+                    .show_ui(ui, |ui| {
+                        for frame in [None, Some(Frame::Target), Some(Frame::Synodic)] {
+                            ui.selectable_value(&mut self.frame, frame, frame_name(frame));
+                        }
+                    });
+                if ui.button("Swap bodies").clicked() {
+                    self.swap_bodies(&transfer);
+                }
+                let palette = ui.button("Commands").on_hover_text("Ctrl+K");
+                if palette.clicked() {
+                    self.palette.open();
+                }
+                self.tour.target(Step::Commands, palette.rect);
+                if ui.button("Tour").clicked() {
+                    self.tab = Tab::Transfer;
+                    self.tour.start();
+                }
+            });
+            //Bodies are drawn to the scale of their orbits, only visible once enlarged
+            ui.add(Slider::new(&mut self.body_sizing.exaggeration, 1.0..=10000.0)
+                .logarithmic(true)
+                .prefix("×")
+                .text("Body size")
+            );
+
+            if self.launch_windows {
+                ui.add(Slider::new(&mut self.phase_angle, -180.0..=180.0)
+                    .suffix(" °")
+                    .text("Current phase angle")
                 );
-                self.velocity.mps_updated();
-                if slider.hovered() {transfer_plot.highlight_transfer()}
-                if slider.dragged() | slider.has_focus() {transfer_plot.highlight_transfer()}
+            }
 
-            } else {
-                let slider = ui.add(SliderWithText::new(
-                    &mut self.velocity.mmps, &mut self.velocity_text, 
-                    min.mmps..=max.mmps
+            if self.state.advanced {
+                let mass_min = Mass::from_lunar(0.0001);
+                let mass_max = Mass::from_jovian(80.0);
+
+                ui.label("Mass of the origin body:");
+                ui.add(QuantitySlider::new(&mut self.state.origin_mass, &mut self.origin_mass_text, mass_min..=mass_max, &mass_units));
+
+                ui.add_space(5.0);
+
+                ui.label("Mass of the target body:");
+                ui.add(QuantitySlider::new(&mut self.state.target_mass, &mut self.target_mass_text, mass_min..=mass_max, &mass_units));
+
+                ui.checkbox(&mut self.state.barycentric, "Barycentric motion of the bodies");
+
+                ui.add_space(5.0);
+
+                ui.label("Radius of the capture orbit:");
+
+                let radius_min = Distance::from_kilometers(100.0);
+                let radius_max = Distance::from_kilometers(10_000_000.0);
+
+                let mut capture_radius = self.mission.capture_radius.km();
+                ui.add(SliderWithText::new(
+                    &mut capture_radius, &mut self.capture_radius_text,
+                    radius_min.km()..=radius_max.km()
                 )
-                    .suffix(" mm/s")
-                    .max_decimals(14)
-                    .enabled_slider(!self.hohmann)
+                    .suffix(" km")
                 );
-                self.velocity.mmps_updated();
-                if slider.hovered() {transfer_plot.highlight_transfer()}
-                if slider.dragged() | slider.has_focus() {transfer_plot.highlight_transfer()}
+                self.mission.capture_radius.set_in_unit(capture_radius, DistanceUnit::Kilometers);
+                ui.horizontal(|ui| {
+                    ui.label("Time in the capture orbit:");
+                    ui.add(DragValue::new(&mut self.mission.station_keeping_years).speed(0.1).clamp_range(0.0..=100.0).suffix(" years"));
+                });
+
+                ui.add_space(5.0);
+
+                let launch_vehicles = &self.launch_vehicles;
+                ComboBox::from_label("Launch vehicle")
+                    .selected_text(&launch_vehicles[self.launch_vehicle].name)
+                    .show_ui(ui, |ui| {
+                        for (i, launch_vehicle) in launch_vehicles.iter().enumerate() {
+                            ui.selectable_value(&mut self.launch_vehicle, i, &launch_vehicle.name);
+                        }
+                    });
+
+                ui.checkbox(&mut self.mission.origin_rotation, "Rotation of the origin at launch");
+                ui.horizontal(|ui| {
+                    ui.label("Gravity losses:");
+                    ui.add(DragValue::new(&mut self.mission.gravity_losses).speed(0.1).clamp_range(0.0..=100.0).suffix(" %"));
+                    ui.label("Drag losses:");
+                    ui.add(DragValue::new(&mut self.mission.drag_losses).speed(0.1).clamp_range(0.0..=100.0).suffix(" %"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Engine acceleration:");
+                    ui.add(DragValue::new(&mut self.mission.engine_acceleration).speed(0.01).clamp_range(0.001..=100.0).suffix(" m/s²"));
+                    ui.checkbox(&mut self.mission.finite_burn, "Finite departure burn");
+                });
+                ui.checkbox(&mut self.mission.eccentric_parking, "Eccentric parking orbit");
+                if self.mission.eccentric_parking {
+                    ui.horizontal(|ui| {
+                        ui.label("Apoapsis altitude:");
+                        ui.add(DragValue::new(&mut self.mission.parking_apoapsis_altitude).speed(100.0).clamp_range(0.0..=10_000_000.0).suffix(" km"));
+                        ComboBox::from_label("Departure burn")
+                            .selected_text(burn_location_name(self.mission.departure_burn))
+                            .show_ui(ui, |ui| {
+                                for location in [BurnLocation::Periapsis, BurnLocation::Apoapsis, BurnLocation::Optimal] {
+                                    ui.selectable_value(&mut self.mission.departure_burn, location, burn_location_name(location));
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.mission.fixed_apsides, "Line of apsides fixed at");
+                        ui.add_enabled(self.mission.fixed_apsides, DragValue::new(&mut self.mission.asymptote_angle).speed(1.0).clamp_range(0.0..=360.0).suffix(" °"));
+                        ui.label("from the departure asymptote");
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Parking orbit inclination:");
+                    ui.add(DragValue::new(&mut self.mission.parking_inclination).speed(0.1).clamp_range(0.0..=180.0).suffix(" °"));
+                });
+                ui.checkbox(&mut self.mission.numerical_check, "Numerical check");
+                //Only stars and the bodies they light push the spacecraft with their light, a parent of a custom mass staying dark
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.mission.surface_forces.radiation_pressure, "Radiation pressure");
+                    ui.checkbox(&mut self.mission.surface_forces.drag, "Drag");
+                    ui.label("Area to mass:");
+                    ui.add(DragValue::new(&mut self.mission.surface_forces.area_to_mass).speed(0.001).clamp_range(0.0001..=100.0).suffix(" m²/kg"));
+                });
+                ui.checkbox(&mut self.mission.third_body, "Third body");
+
+                if self.mission.third_body {
+                    ui.horizontal(|ui| {
+                        let mut sma = self.mission.third_body_sma.au();
+                        let mut mass = self.mission.third_body_mass.jovian();
+                        ui.label("Semi-major axis:");
+                        ui.add(DragValue::new(&mut sma).speed(0.01).clamp_range(0.01..=100.0).suffix(" AU"));
+                        ui.label("Mass:");
+                        ui.add(DragValue::new(&mut mass).speed(0.01).clamp_range(0.0001..=80.0).suffix(" Mj"));
+                        ui.label("Longitude at departure:");
+                        ui.add(DragValue::new(&mut self.mission.third_body_longitude).speed(1.0).clamp_range(-180.0..=180.0).suffix(" °"));
+                        self.mission.third_body_sma = Distance::from_astronomical_unit(sma);
+                        self.mission.third_body_mass = Mass::from_jovian(mass);
+                    });
+                }
+
+                ui.checkbox(&mut self.moon, "Moon of the target");
+
+                if self.moon {
+                    ui.horizontal(|ui| {
+                        let mut sma = self.moon_sma.km();
+                        let speed = sma * 1E-3;
+                        ui.label("Semi-major axis:");
+                        ui.add(DragValue::new(&mut sma).speed(speed).clamp_range(1.0..=f64::MAX).suffix(" km"));
+                        self.moon_sma = Distance::from_kilometers(sma);
+                    });
+                }
             }
 
             ui.add_space(10.0);
         });
 
+        if self.velocity_plot {
+            TopBottomPanel::bottom("velocity").show(ctx, |ui| {
+                let velocity_plot = VelocityPlot::new(&transfer, color_mode);
+
+                ui.label("Speed of the spacecraft (km/s) against time since departure (days):");
+
+                Plot::new("velocity_plot")
+                .height(150.0)
+                .allow_zoom(false)
+                .allow_drag(false)
+                .legend(Legend::default())
+
+                .show(ui, |plot_ui| {
+                    plot_ui.line(velocity_plot.profile());
+                    for apsis in velocity_plot.apsides() {
+                        plot_ui.hline(apsis);
+                    }
+                });
+            });
+        }
+
         CentralPanel::default().show(ctx, |ui| {
 
-            let transfer_time = transfer.time_of_flight()
-                .round_to(2)
-                .smallest_duration_formatted();
-    
-            ui.label(format!("The transfer will take {}.", transfer_time));
-            ui.add_space(5.0);
+            if self.tab == Tab::Resonance {
+                self.resonance(ui, &target, &calendar, color_mode);
+                return;
+            }
+            if self.tab == Tab::Maneuvers {
+                self.maneuvers(ui, parent);
+                return;
+            }
+            if self.tab == Tab::DeltaVMap {
+                self.delta_v_map(ui);
+                return;
+            }
+
+            //Results are hidden when the two-body approximation breaks down
+            for warning in &texts.warnings {
+                ui.colored_label(Color32::from_rgb(255, 115, 0), warning);
+                ui.add_space(5.0);
+            }
+
+            if texts.warnings.is_empty() {
+                let phase_angle = ui.horizontal(|ui| {
+                    ui.label(&texts.time_of_flight);
+                    info(ui, &mut self.explained, Explained::TimeOfFlight);
+                    info(ui, &mut self.explained, Explained::PhaseAngle);
+                });
+                self.tour.target(Step::PhaseAngle, phase_angle.response.rect);
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(&texts.c3);
+                    info(ui, &mut self.explained, Explained::C3);
+                    info(ui, &mut self.explained, Explained::ArrivalVInfinity);
+                });
+                ui.add_space(5.0);
+                ui.label(&texts.approach);
+                ui.add_space(5.0);
+                //Missions that flew between the same bodies of the catalog
+                if let (Some(origin), Some(target)) = (self.state.origin_body, self.state.target_body) {
+                    let missions = Mission::between(self.bodies[origin].name, self.bodies[target].name);
+                    if !missions.is_empty() {
+                        missions_comparison(ui, &transfer, &missions, color_mode);
+                        ui.add_space(5.0);
+                    }
+                }
+                if transfer.retrograde() {
+                    ui.label("The departure burn reverses the motion of the spacecraft, which orbits retrograde.");
+                    ui.add_space(5.0);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(&texts.orbit);
+                    info(ui, &mut self.explained, Explained::TransferOrbit);
+                });
+                ui.add_space(5.0);
+                if self.return_leg {
+                    let return_leg = transfer.reversed();
+                    ui.label(format!(
+                        "The transfer takes {} of delta-v, the return leg {} over {}.",
+                        Velocity::from_meters_per_second((transfer.launch_velocity() - transfer.origin().orbital_velocity()).mps().abs()).formatted(),
+                        Velocity::from_meters_per_second((return_leg.launch_velocity() - return_leg.origin().orbital_velocity()).mps().abs()).formatted(),
+                        return_leg.time_of_flight().formatted_in(&calendar),
+                    ));
+                    ui.add_space(5.0);
+                }
+                if self.launch_windows {
+                    let windows = transfer.launch_windows(self.phase_angle.to_radians(), LAUNCH_WINDOWS);
+                    let synodic_period = transfer.synodic_period().formatted_in(&calendar);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Current date:");
+                        date_input(ui, &calendar, &mut self.epoch);
+                    });
+                    let wait_time = transfer.wait_time_until_window(self.phase_angle.to_radians());
+                    ui.label(format!("Next window in {}, on {}.", wait_time.formatted_in(&calendar), calendar.date(self.epoch + wait_time)));
+                    ui.add_space(5.0);
+                    ui.label(format!("Launch windows over the next {} synodic periods of {}:", LAUNCH_WINDOWS, synodic_period));
+                    let dates: Vec<String> = windows.iter().map(|&window| calendar.date(self.epoch + window).to_string()).collect();
+                    let timeline = ui.add(Timeline::new(&windows, &mut self.launch_window).dates(&dates));
+
+                    //Loading a window moves the epoch to it, the phase angle becoming the ideal one
+                    if timeline.changed() {
+                        if let Some(window) = self.launch_window {
+                            self.epoch = self.epoch + windows[window];
+                        }
+                        self.phase_angle = protractor.angle().to_degrees();
+                        self.launch_window = None;
+                    }
+                    ui.add_space(5.0);
+
+                    //Latest window still reaching the target by a date, with more delta-v when the current transfer can't make it
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.arrive_by, "Arrive by:");
+                        date_input(ui, &calendar, &mut self.deadline);
+                    });
+                    if self.arrive_by {
+                        match transfer.solve_for_arrival(self.phase_angle.to_radians(), self.deadline - self.epoch) {
+                            Some(solution) => {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!(
+                                        "Depart on {} at the latest with {} of delta-v, arriving on {}.",
+                                        calendar.date(self.epoch + solution.departure),
+                                        Velocity::from_meters_per_second(solution.delta_v.mps().abs()).formatted(),
+                                        calendar.date(self.epoch + solution.arrival),
+                                    ));
+                                    if ui.button("Use").on_hover_text("Set the delta-v of this transfer").clicked() {
+                                        self.state.hohmann = false;
+                                        self.state.velocity = solution.delta_v;
+                                    }
+                                });
+                            }
+                            None => {
+                                ui.colored_label(Color32::from_rgb(255, 115, 0), "No transfer within the range of delta-v arrives by then.");
+                            }
+                        }
+                    }
+                    ui.add_space(5.0);
+                }
+                if self.state.advanced {
+                    let launch_vehicle = &self.launch_vehicles[self.launch_vehicle];
+                    match launch_vehicle.payload(transfer.c3()) {
+                        Some(payload) => ui.label(format!("The {} can deliver up to {} kg on this transfer.", launch_vehicle.name, significant(payload.kg(), SIGNIFICANT_FIGURES))),
+                        None => ui.label(format!("The {} cannot fly this transfer.", launch_vehicle.name)),
+                    };
+                    ui.add_space(5.0);
+                    if let Some((name, departure)) = &departure {
+                        match departure.burns.map(|burns| burns.node) {
+                            None => {
+                                ui.label("Integrating the departure burn...");
+                            }
+                            Some(Some(node)) => {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!(
+                                        "The departure burn from a low orbit of {} lasts {}, starting {} before the node and losing {} to its finite duration.",
+                                        name,
+                                        node.burn_time.formatted(),
+                                        node.lead_time().formatted(),
+                                        node.finite_burn_loss.formatted(),
+                                    ));
+                                    if ui.button("Copy node").clicked() {
+                                        ui.output().copied_text = node.to_text();
+                                    }
+                                });
+                            }
+                            Some(None) => {
+                                ui.colored_label(Color32::RED, "The engine is too weak to depart in a single burn.");
+                            }
+                        }
+                        ui.add_space(5.0);
+                        if let Some(estimate) = departure.launch {
+                            ui.label(format!(
+                                "Launching from the equator of {} to a low orbit takes {}, including {} of gravity losses and {} of drag losses, {} being gained from its rotation.",
+                                name,
+                                estimate.total.formatted(),
+                                estimate.gravity_losses.formatted(),
+                                estimate.drag_losses.formatted(),
+                                estimate.rotation_credit.formatted(),
+                            ));
+                        }
+                        if let Some([periapsis, apoapsis, optimal]) = departure.escapes {
+                            ui.label(format!(
+                                "Departing from the eccentric parking orbit takes {} at periapsis, {} at apoapsis and {} at best, {}° past the periapsis.",
+                                periapsis.delta_v.formatted(),
+                                apoapsis.delta_v.formatted(),
+                                optimal.delta_v.formatted(),
+                                optimal.true_anomaly.to_degrees().round(),
+                            ));
+                        }
+                        if self.mission.finite_burn {
+                            match departure.burns.map(|burns| burns.finite_burn) {
+                                None => (),
+                                Some(Some(burn)) => {
+                                    ui.label(format!(
+                                        "Burning for {} instead of instantly takes {} to depart, a {} % penalty.",
+                                        burn.burn_time.formatted(),
+                                        burn.adjusted.formatted(),
+                                        significant(burn.penalty() * 100.0, SIGNIFICANT_FIGURES),
+                                    ));
+                                }
+                                Some(None) => {
+                                    ui.colored_label(Color32::RED, "The engine is too weak to depart in a single burn.");
+                                }
+                            }
+                        }
+                        if let Some(lifetime) = departure.lifetime {
+                            ui.label(format!("Drag brings the low orbit of {} down within {}.", name, lifetime.formatted_in(&calendar)));
+                        }
+                        if let Some((rate, drift)) = departure.nodal_drift {
+                            let text = format!(
+                                "The oblateness of {} turns the plane of the parking orbit by {}° a day, {}° by the next launch window in {}.",
+                                name,
+                                significant(rate.to_degrees(), SIGNIFICANT_FIGURES),
+                                significant(drift.to_degrees(), SIGNIFICANT_FIGURES),
+                                transfer.wait_time_until_window(self.phase_angle.to_radians()).formatted_in(&calendar),
+                            );
+                            if drift.to_degrees().abs() > NODAL_DRIFT_WARNING {
+                                ui.colored_label(Color32::from_rgb(255, 115, 0), format!("{} The departure must be lined up with the plane it drifts to.", text));
+                            } else {
+                                ui.label(text);
+                            }
+                        }
+                        if let Some(synchronous_orbit) = departure.synchronous_orbit {
+                            ui.label(format!("The synchronous orbit of {} is {} from its center.", name, synchronous_orbit.formatted()));
+                        }
+                    }
+                    //Budget from the pad to the surface of the target, as far as the bodies are known
+                    let budget = Budget::new(&self.mission, departure.as_ref().map(|(_, departure)| departure), &arrival);
+                    if budget.steps.len() > 1 {
+                        Grid::new("budget").striped(true).show(ui, |ui| {
+                            for (step, delta_v) in &budget.steps {
+                                ui.label(*step);
+                                ui.label(delta_v.formatted());
+                                ui.end_row();
+                            }
+                            ui.strong("Total");
+                            ui.strong(budget.total().formatted());
+                            ui.end_row();
+                        });
+                    }
+                    ui.add_space(5.0);
+                }
+                if self.state.advanced {
+                    for sensitivity in &texts.sensitivities {
+                        ui.label(sensitivity);
+                    }
+                    ui.label(&texts.miss);
+                    if let Some(tolerance) = &texts.tolerance {
+                        ui.label(tolerance);
+                    }
+                    ui.add_space(5.0);
+                }
+                let inclination = (self.state.target_parameters.inclination - self.state.origin_parameters.inclination).abs();
+                if inclination > 0.0 {
+                    let plane_change = maneuvers::plane_change(transfer.arrival_velocity(), inclination.to_radians());
+                    ui.label(format!("Matching the inclination of the target takes {} more at arrival.", plane_change.formatted()));
+                    //Worst case of a target met a quarter revolution from the nodes
+                    if let Some(broken_plane) = transfer.broken_plane(inclination.to_radians(), FRAC_PI_2) {
+                        ui.label(format!(
+                            "Broken-plane transfer: {} in total, tilting the departure by {:.2}° and the plane by {:.2}° in a mid-course burn of {}, {} after departure at {:.0}° from the origin.",
+                            broken_plane.total().formatted(),
+                            broken_plane.departure_plane_change.to_degrees(),
+                            broken_plane.mid_course_plane_change.to_degrees(),
+                            broken_plane.mid_course.formatted(),
+                            broken_plane.mid_course_time.formatted_in(&calendar),
+                            broken_plane.mid_course_angle.to_degrees(),
+                        ));
+                    }
+                    ui.add_space(5.0);
+                }
+                if analyzing {
+                    ui.label("Computing the numerical check and perturbations...");
+                    ui.add_space(5.0);
+                }
+                if let Some(verification) = verification {
+                    ui.label(format!("Numerical propagation arrives {} from the analytic arrival.", verification.error.formatted()));
+                    ui.add_space(5.0);
+                }
+                if let Some(surface_effect) = surface_effect {
+                    if surface_effect.significant {
+                        ui.colored_label(Color32::from_rgb(255, 115, 0), surface_effect.to_string());
+                    } else {
+                        ui.label(surface_effect.to_string());
+                    }
+                    ui.add_space(5.0);
+                }
+                if let Some(perturbation) = perturbation {
+                    if perturbation.significant {
+                        ui.colored_label(Color32::from_rgb(255, 115, 0), perturbation.to_string());
+                    } else {
+                        ui.label(perturbation.to_string());
+                    }
+                    ui.add_space(5.0);
+                }
+                if self.state.advanced && self.moon {
+                    match (moon_approach, target.hill_sphere()) {
+                        (Some(moon_approach), _) => ui.label(format!("Meeting the moon at periapsis takes {} more at arrival.", moon_approach.delta_v.formatted())),
+                        (None, Some(hill_sphere)) if self.moon_sma >= hill_sphere => ui.colored_label(
+                            Color32::RED,
+                            format!("The moon is beyond the {} Hill sphere of the target, it cannot orbit it.", hill_sphere.formatted()),
+                        ),
+                        (None, _) => ui.colored_label(Color32::from_rgb(255, 115, 0), "The moon is outside the sphere of influence of the target."),
+                    };
+                    ui.add_space(5.0);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Copy summary").clicked() {
+                        ui.output().copied_text = self.summary(&model).to_text();
+                    }
+                    //Calculation chain from the inputs to the results, for lab reports
+                    if ui.button("Copy derivation (Markdown)").clicked() {
+                        ui.output().copied_text = transfer.derivation().to_markdown();
+                    }
+                    if ui.button("Copy derivation (LaTeX)").clicked() {
+                        ui.output().copied_text = transfer.derivation().to_latex();
+                    }
+                });
+                ui.add_space(5.0);
+                //Scenarios shared as .transfer files
+                ui.horizontal(|ui| {
+                    ui.label("Scenario file:");
+                    ui.text_edit_singleline(&mut self.scenario_path);
+                    if ui.button("Save").clicked() {
+                        self.save_scenario(&transfer);
+                    }
+                    if ui.button("Open").clicked() {
+                        self.open_scenario();
+                    }
+                });
+                //Sessions recorded to the scenario file, as for tutorials or bug reports
+                ui.horizontal(|ui| {
+                    let time = ui.input().time;
+                    let label = if self.recorder.is_some() {"⏹ Stop recording"} else {"⏺ Record"};
+                    if ui.add_enabled(self.replay.is_none(), egui::Button::new(label)).clicked() {
+                        match self.recorder.take() {
+                            Some((_, recorder)) => self.save_recording(recorder),
+                            None => {
+                                self.recorder = Some((time, Recorder::new()));
+                                self.scenario_status = Some("Recording...".to_string());
+                            }
+                        }
+                    }
+                    let label = if self.replay.is_some() {"⏹ Stop replay"} else {"▶ Replay"};
+                    if ui.add_enabled(self.recorder.is_none(), egui::Button::new(label)).clicked() {
+                        if self.replay.take().is_some() {
+                            self.scenario_status = Some("Stopped replaying the recording.".to_string());
+                        } else {
+                            self.open_replay(time);
+                        }
+                    }
+                });
+                if let Some(status) = &self.scenario_status {
+                    ui.label(status);
+                }
+                ui.add_space(5.0);
+                if let Some(capture) = arrival.capture {
+                    let direct = capture.direct_insertion.formatted();
+                    if capture.plausible {
+                        let savings = capture.savings.formatted();
+                        ui.label(format!("Ballistic capture is plausible, saving {} over a {} direct insertion.", savings, direct));
+                    } else {
+                        ui.label(format!("Ballistic capture is unlikely, direct insertion costs {}.", direct));
+                    }
+                    ui.add_space(5.0);
+                }
+                if let Some(station_keeping) = arrival.station_keeping {
+                    if station_keeping.unstable {
+                        ui.colored_label(Color32::from_rgb(255, 115, 0), "The capture orbit is too far out for the target to hold on to the spacecraft for long.");
+                    } else {
+                        ui.label(format!(
+                            "Keeping the capture orbit takes about {} a year, {} against drag and {} against the pull of the parent.",
+                            station_keeping.annual().formatted(),
+                            station_keeping.drag.formatted(),
+                            station_keeping.tidal.formatted(),
+                        ));
+                    }
+                    ui.add_space(5.0);
+                }
+            }
             if portrait {
                 ui.label(format!("The phase angle is {} °.", protractor.text_string()));
             }
             
-            Plot::new("my_plot")
+            if self.frame.is_none() {
+                legend(ui, &mut self.layers);
+            }
+
+            let plot = Plot::new("my_plot")
+            .legend(Legend::default())
             .allow_zoom(false)
             .allow_drag(false)
             .show_background(false)
@@ -258,35 +1366,811 @@ impl epi::App for Gui {
             .data_aspect(1.0)
 
             .show(ui, |plot_ui| {
-                let transfer_orbits = transfer_plot.orbit_all();
-                let transfer_markers = transfer_plot.marker_all();
+                //Frames rotating with the target replace the diagram, whose angles have no meaning there
+                if let Some(frame) = self.frame {
+                    let relative_plot = RelativePlot::new(&transfer, frame, transfer_plot.scale().m(), color_mode);
+                    for path in relative_plot.paths() {
+                        plot_ui.line(path);
+                    }
+                    for marker in relative_plot.markers() {
+                        plot_ui.points(marker);
+                    }
+                    return;
+                }
+
+                let points_per_unit = points_per_unit(plot_ui);
+                transfer_plot.set_sizing(self.body_sizing, points_per_unit);
+
+                //The other bodies of the custom system, around the custom parent
+                if self.state.parent_body.is_none() {
+                    for (i, body) in self.system.iter().enumerate() {
+                        if Some(i) != self.origin_system && Some(i) != self.target_system {
+                            plot_ui.line(transfer_plot.orbit_other(&body.name, Distance::from_meters(body.sma), system_color(body)));
+                        }
+                    }
+                }
 
-                for orbits in transfer_orbits {
-                    plot_ui.line(orbits);
+                if let Some((line, label)) = transfer_plot.reference() {
+                    plot_ui.line(line);
+                    plot_ui.text(label);
                 }
-                for plots in protractor.plot() {
-                    plot_ui.line(plots);
+                if self.layers.origin {
+                    plot_ui.line(transfer_plot.orbit_origin());
                 }
-                for markers in transfer_markers {
-                    plot_ui.points(markers);
+                if self.layers.target {
+                    plot_ui.line(transfer_plot.orbit_target());
                 }
-                if !portrait {
+                if self.layers.transfer {
+                    plot_ui.line(transfer_plot.orbit_transfer());
+                }
+                if let Some(verification) = verification {
+                    plot_ui.line(transfer_plot.numerical_path(&verification.path));
+                }
+                if self.layers.protractor {
+                    for plots in protractor.plot() {
+                        plot_ui.line(plots);
+                    }
+                }
+                if self.layers.markers {
+                    for markers in transfer_plot.marker_all() {
+                        plot_ui.points(markers);
+                    }
+                    let (points, labels) = transfer_plot.apsides();
+                    plot_ui.points(points);
+                    for label in labels {
+                        plot_ui.text(label);
+                    }
+                }
+                if self.time_markers {
+                    let (points, labels) = transfer_plot.time_markers();
+                    plot_ui.points(points);
+                    for label in labels {
+                        plot_ui.text(label);
+                    }
+                }
+                if !portrait && self.layers.protractor {
                     plot_ui.text(protractor.text());
                 }
                 
-                plot_ui.points(
-                    egui::plot::Points::new(
-                        egui::plot::Values::from_values
-                        (vec![egui::plot::Value::new(0.0, 0.0)]))
-                            .radius(((self.origin_sma.m / self.target_sma.m).min(self.target_sma.m / self.origin_sma.m) as f32) * 20.0)
-                            .shape(egui::plot::MarkerShape::Diamond)
-                );
+                let parent_marker = egui::plot::Points::new(
+                    egui::plot::Values::from_values
+                    (vec![egui::plot::Value::new(0.0, 0.0)]))
+                        .radius(self.body_sizing.marker_radius(parent.radius(), transfer_plot.scale().m(), points_per_unit))
+                        .shape(egui::plot::MarkerShape::Diamond);
+                plot_ui.points(match self.state.parent_body.map(|i| &self.bodies[i]) {
+                    Some(body) => parent_marker.color(body_color(body)).shape(marker_shape(body.marker)).name(body.name),
+                    None => parent_marker,
+                });
             });
+            self.tour.target(Step::Diagram, plot.response.rect);
+            self.tour.target(Step::Protractor, plot.response.rect);
 
         });
+
+        //Transfers to a moon show its system as an inset, the main diagram keeping the interplanetary leg
+        if let Some(moon_approach) = moon_approach.filter(|_| self.tab == Tab::Transfer) {
+            egui::Window::new("Moon of the target")
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+                .resizable(false)
+                .show(ctx, |ui| {
+                    let moon_plot = MoonPlot::new(&moon_approach, color_mode);
+
+                    Plot::new("moon_plot")
+                    .width(200.0)
+                    .height(200.0)
+                    .allow_zoom(false)
+                    .allow_drag(false)
+                    .show_axes([false; 2])
+                    .show_x(false).show_y(false)
+                    .data_aspect(1.0)
+
+                    .show(ui, |plot_ui| {
+                        for orbit in moon_plot.orbit_all() {
+                            plot_ui.line(orbit);
+                        }
+                        for marker in moon_plot.markers() {
+                            plot_ui.points(marker);
+                        }
+                    });
+                });
+        }
+
+        //Formula behind a result, with the values of the transfer substituted in it
+        if let Some(explained) = self.explained {
+            let mut open = true;
+            egui::Window::new(explained.name())
+                .open(&mut open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    Grid::new("explanation").striped(true).show(ui, |ui| {
+                        for step in transfer.explain(explained) {
+                            ui.label(step.symbol).on_hover_text(step.name);
+                            if step.formula.is_empty() {
+                                ui.label("Input");
+                                ui.label("");
+                            } else {
+                                ui.label(format!("= {}", step.formula));
+                                ui.label(format!("= {}", step.substituted));
+                            }
+                            ui.label(format!("= {}", step.formatted_value()));
+                            ui.end_row();
+                        }
+                    });
+                });
+            if !open {self.explained = None}
+        }
+
+        let facts = Facts {
+            delta_v: transfer.delta_v_hohmann().formatted(),
+            time_of_flight: transfer.time_of_flight().formatted_in(&calendar),
+            phase_angle: format!("{} °", protractor.text_string()),
+            synodic_period: transfer.synodic_period().formatted_in(&calendar),
+        };
+        self.tour.show(ctx, &facts);
+
+        computed.texts = Some(texts);
+        self.derived = Some((scenario, model));
+        self.computed = Some(computed);
     }
 
     fn name(&self) -> &str {
         "Planetary transfer calculator"
     }
-}
\ No newline at end of file
+}
+
+//Bodies of the catalog orbiting the parent, picking one setting the orbit and mass of the planet
+fn time_units_name(units: TimeUnits) -> &'static str {
+    match units {
+        TimeUnits::Earth => "Earth days",
+        TimeUnits::Sols => "Martian sols",
+        TimeUnits::Kerbin => "Kerbin days (6 h)",
+        TimeUnits::TargetSidereal => "Sidereal days of the target",
+        TimeUnits::TargetLocal => "Local days of the target",
+        TimeUnits::Custom => "Custom days",
+    }
+}
+
+fn frame_name(frame: Option<Frame>) -> &'static str {
+    match frame {
+        None => "Inertial view",
+        Some(Frame::Target) => "Target-centered view",
+        Some(Frame::Synodic) => "Co-rotating view",
+    }
+}
+
+fn burn_location_name(location: BurnLocation) -> &'static str {
+    match location {
+        BurnLocation::Periapsis => "At periapsis",
+        BurnLocation::Apoapsis => "At apoapsis",
+        BurnLocation::Optimal => "Cheapest point",
+    }
+}
+
+//Period and speed of a planet on its orbit, following its slider
+fn legend(ui: &mut Ui, layers: &mut Layers) {
+    ui.horizontal(|ui| {
+        ui.label("Show:");
+        ui.checkbox(&mut layers.origin, "Origin orbit");
+        ui.checkbox(&mut layers.target, "Target orbit");
+        ui.checkbox(&mut layers.transfer, "Transfer");
+        ui.checkbox(&mut layers.markers, "Markers");
+        ui.checkbox(&mut layers.protractor, "Protractor");
+    });
+}
+
+fn orbit_readout(ui: &mut Ui, planet: &Planet, calendar: &Calendar) {
+    ui.label(format!(
+        "Orbital period of {}, orbital velocity of {}.",
+        Duration::from_seconds(planet.period()).formatted_in(calendar),
+        planet.orbital_velocity().formatted(),
+    ));
+}
+
+fn system_color(body: &SystemBody) -> Color32 {
+    let [r, g, b] = body.color;
+    Color32::from_rgb(r, g, b)
+}
+
+//Date in a calendar, days and years counting from one
+fn date_input(ui: &mut Ui, calendar: &Calendar, time: &mut Duration) {
+    let date = calendar.date(*time);
+    let (mut year, mut day) = (date.year.unwrap_or(1), date.day);
+    if date.year.is_some() {
+        ui.add(DragValue::new(&mut year).clamp_range(1..=100000).prefix("Year "));
+    }
+    ui.add(DragValue::new(&mut day).clamp_range(1..=100000).prefix("Day "));
+    if (Some(year), day) != (date.year.or(Some(1)), date.day) {
+        *time = calendar.time(year, day);
+    }
+}
+
+fn info(ui: &mut Ui, explained: &mut Option<Explained>, result: Explained) {
+    if ui.small_button("ℹ").on_hover_text("Explain this number").clicked() {
+        *explained = Some(result);
+    }
+}
+
+fn missions_comparison(ui: &mut Ui, transfer: &Transfer, missions: &[Mission], color_mode: Color32) {
+    egui::CollapsingHeader::new("Historical missions").show(ui, |ui| {
+        for mission in missions {
+            let comparison = mission.compare(transfer);
+            ui.label(format!(
+                "{} launched on {} with a C3 of {} km²/s², arriving {} later on {}. This transfer needs {} km²/s² {} and takes {} {}.",
+                mission.name,
+                mission.launch,
+                significant(mission.c3, SIGNIFICANT_FIGURES),
+                mission.time_of_flight.formatted(),
+                mission.arrival,
+                significant(comparison.c3.abs(), SIGNIFICANT_FIGURES),
+                if comparison.c3 > 0.0 {"more"} else {"less"},
+                Duration::from_seconds(comparison.time_of_flight.abs()).formatted(),
+                if comparison.time_of_flight > 0.0 {"longer"} else {"shorter"},
+            ));
+        }
+
+        let mission_plot = MissionPlot::new(transfer, missions, color_mode);
+
+        ui.label("Time of flight (days) against C3 (km²/s²):");
+
+        Plot::new("mission_plot")
+        .height(150.0)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .legend(Legend::default())
+
+        .show(ui, |plot_ui| {
+            plot_ui.line(mission_plot.transfers());
+            let (points, labels) = mission_plot.missions();
+            plot_ui.points(points);
+            for label in labels {
+                plot_ui.text(label);
+            }
+            plot_ui.points(mission_plot.transfer());
+        });
+    });
+}
+
+fn body_picker(ui: &mut Ui, id: &str, bodies: &[Body], parent_body: Option<usize>, body: &mut Option<usize>, sma: &mut Distance, mass: &mut Mass) {
+    let orbiting: Vec<usize> = (0..bodies.len())
+        .filter(|&i| parent_body.is_some() && bodies[i].primary(bodies) == parent_body)
+        .collect();
+    if orbiting.is_empty() {
+        return;
+    }
+    ComboBox::from_id_source(id)
+        .selected_text(body.map(|i| bodies[i].name).unwrap_or("Custom"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(body, None, "Custom");
+            for i in orbiting {
+                if ui.selectable_value(body, Some(i), bodies[i].name).clicked() {
+                    if let Some((_, body_sma)) = bodies[i].orbit {*sma = body_sma}
+                    *mass = bodies[i].mass;
+                }
+            }
+        });
+}
+
+//The altitude can only be entered when the radius of the parent is known
+fn input_mode(ui: &mut Ui, id: &str, mode: &mut InputMode, altitude: bool) {
+    ComboBox::from_id_source(id)
+        .selected_text(mode.name())
+        .show_ui(ui, |ui| {
+            for option in [InputMode::Sma, InputMode::Altitude, InputMode::Period, InputMode::Velocity] {
+                if option != InputMode::Altitude || altitude {
+                    ui.selectable_value(mode, option, option.name());
+                }
+            }
+        });
+}
+
+impl Gui {
+    fn scenario(&self) -> Scenario {
+        Scenario {
+            state: self.state.clone(),
+            mission: self.mission,
+        }
+    }
+
+    fn calendar(&self, transfer: &Transfer) -> Calendar {
+        let rotation_period = self.state.target_body.and_then(|i| self.bodies[i].rotation_period);
+        match (self.time_units, rotation_period) {
+            (TimeUnits::Earth, _) => Calendar::EARTH,
+            (TimeUnits::Sols, _) => Calendar::SOLS,
+            (TimeUnits::Kerbin, _) => Calendar::KERBIN,
+            (TimeUnits::Custom, _) => Calendar::custom(self.custom_day, self.custom_year),
+            (TimeUnits::TargetSidereal, Some(rotation_period)) => Calendar::sidereal(rotation_period),
+            (TimeUnits::TargetLocal, Some(rotation_period)) => Calendar::local(rotation_period, Duration::from_seconds(transfer.target().period())),
+            (_, None) => Calendar::EARTH,
+        }
+    }
+
+    //Registry of the actions of the command palette, bodies being picked as origin or target along with their parent
+    fn commands(&self) -> Vec<Command<Action>> {
+        let mut commands = vec![
+            Command::new("Transfer tab", Action::Tab(Tab::Transfer)),
+            Command::new("Resonance tab", Action::Tab(Tab::Resonance)),
+            Command::new("Maneuvers tab", Action::Tab(Tab::Maneuvers)),
+            Command::new("ΔV map tab", Action::Tab(Tab::DeltaVMap)),
+            Command::new("Swap bodies", Action::SwapBodies),
+            Command::new("Hohmann transfer", Action::Hohmann),
+            Command::new("Optimize the transfer", Action::Optimize),
+            Command::new("Copy summary", Action::CopySummary),
+            Command::new("Copy derivation (Markdown)", Action::CopyDerivation),
+            Command::new("Save scenario", Action::SaveScenario),
+            Command::new("Open scenario", Action::OpenScenario),
+            Command::new("Toggle time markers", Action::TimeMarkers),
+            Command::new("Toggle launch windows", Action::LaunchWindows),
+            Command::new("Toggle advanced", Action::Advanced),
+            Command::new("Toggle dark mode", Action::DarkMode),
+            Command::new("Take the tour", Action::Tour),
+            Command::new("Surprise me (random system)", Action::Surprise),
+        ];
+        for (i, body) in self.bodies.iter().enumerate() {
+            if self.bodies.iter().any(|other| other.primary(&self.bodies) == Some(i)) {
+                commands.push(Command::new(format!("Parent: {}", body.name), Action::Parent(i)));
+            }
+            if body.primary(&self.bodies).is_some() {
+                commands.push(Command::new(format!("Origin: {}", body.name), Action::Origin(i)));
+                commands.push(Command::new(format!("Target: {}", body.name), Action::Target(i)));
+            }
+        }
+        commands
+    }
+
+    fn perform(&mut self, action: Action, ctx: &egui::CtxRef, model: &DerivedModel) {
+        let transfer = &model.transfer;
+        match action {
+            Action::Tab(tab) => self.tab = tab,
+            Action::Parent(i) => self.pick_parent(i),
+            Action::Origin(i) | Action::Target(i) => {
+                //Bodies of another system bring their parent along
+                if let Some(primary) = self.bodies[i].primary(&self.bodies).filter(|&primary| Some(primary) != self.state.parent_body) {
+                    self.pick_parent(primary);
+                    let other = if matches!(action, Action::Origin(_)) {&mut self.state.target_body} else {&mut self.state.origin_body};
+                    *other = None;
+                }
+                let body = &self.bodies[i];
+                let (picked, sma, mass, retrograde) = match action {
+                    Action::Origin(_) => (&mut self.state.origin_body, &mut self.state.origin_sma, &mut self.state.origin_mass, &mut self.state.origin_retrograde),
+                    _ => (&mut self.state.target_body, &mut self.state.target_sma, &mut self.state.target_mass, &mut self.state.target_retrograde),
+                };
+                *picked = Some(i);
+                if let Some((_, body_sma)) = body.orbit {*sma = body_sma}
+                *mass = body.mass;
+                *retrograde = body.retrograde;
+            }
+            Action::SwapBodies => self.swap_bodies(transfer),
+            Action::Hohmann => self.state.hohmann = true,
+            Action::Optimize => self.optimize(transfer, model.min, model.max),
+            Action::CopySummary => ctx.output().copied_text = self.summary(model).to_text(),
+            Action::CopyDerivation => ctx.output().copied_text = transfer.derivation().to_markdown(),
+            Action::SaveScenario => self.save_scenario(transfer),
+            Action::OpenScenario => self.open_scenario(),
+            Action::TimeMarkers => self.time_markers = !self.time_markers,
+            Action::LaunchWindows => self.launch_windows = !self.launch_windows,
+            Action::Advanced => self.state.advanced = !self.state.advanced,
+            Action::Tour => {
+                self.tab = Tab::Transfer;
+                self.tour.start();
+            }
+            //Time since the start mixed in so that every session draws different systems
+            Action::Surprise => self.random_system(RandomSystem::next_seed(self.seed ^ ctx.input().time.to_bits())),
+            Action::DarkMode => {
+                self.dark_mode = !self.dark_mode;
+                ctx.set_visuals(if self.dark_mode {egui::Visuals::dark()} else {egui::Visuals::light()});
+            }
+        }
+    }
+
+    //Random system around a custom parent, its seed shown so that it can be drawn again
+    fn random_system(&mut self, seed: u64) {
+        let system = RandomSystem::new(seed);
+        self.seed = seed;
+        self.seed_text = seed.to_string();
+        self.state.parent_body = None;
+        self.state.origin_body = None;
+        self.state.target_body = None;
+        self.state.mass = system.mass;
+        self.state.binary = false;
+        self.state.origin_sma = system.origin_sma;
+        self.state.target_sma = system.target_sma;
+        self.state.origin_mode = InputMode::Sma;
+        self.state.target_mode = InputMode::Sma;
+        self.state.origin_parameters = OrbitParameters::default();
+        self.state.target_parameters = OrbitParameters::default();
+        self.state.origin_retrograde = false;
+        self.state.target_retrograde = false;
+        self.state.revolutions = 0;
+        self.state.hohmann = system.extra_delta_v == 0.0;
+        self.state.velocity = system.delta_v();
+    }
+
+    fn pick_parent(&mut self, i: usize) {
+        self.state.parent_body = Some(i);
+        self.state.mass = self.bodies[i].mass;
+    }
+
+    //Cheapest departure and arrival within the range of the slider
+    fn optimize(&mut self, transfer: &Transfer, min: Velocity, max: Velocity) {
+        let arrival = if self.state.advanced {Arrival::Capture(self.mission.capture_radius)} else {Arrival::Rendezvous};
+        self.optimizer.start(Optimization {scenario: self.scenario(), transfer: *transfer, arrival, min, max});
+    }
+
+    fn summary(&self, model: &DerivedModel) -> TransferSummary {
+        let summary = model.summary.clone();
+        if self.launch_windows {
+            summary.with_next_window(model.transfer.wait_time_until_window(self.phase_angle.to_radians()))
+        } else {
+            summary
+        }
+    }
+
+    fn save_scenario(&mut self, transfer: &Transfer) {
+        let status = match std::fs::write(&self.scenario_path, self.scenario_file(transfer).to_json()) {
+            Ok(()) => format!("Saved the scenario to {}.", self.scenario_path),
+            Err(error) => format!("Could not save the scenario: {}.", error),
+        };
+        self.scenario_status = Some(status);
+    }
+
+    fn open_scenario(&mut self) {
+        let status = match std::fs::read_to_string(&self.scenario_path).map_err(|error| error.to_string())
+            .and_then(|text| ScenarioFile::from_json(&text).map_err(|error| error.to_string()))
+        {
+            Ok(file) => self.load_scenario(&file),
+            Err(error) => format!("Could not open the scenario: {}.", error),
+        };
+        self.scenario_status = Some(status);
+    }
+
+    fn save_recording(&mut self, recorder: Recorder) {
+        let name = std::path::Path::new(&self.scenario_path).file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let status = match recorder.finish(name) {
+            Some(file) => match std::fs::write(&self.scenario_path, file.to_json()) {
+                Ok(()) => format!("Saved the recording of {} changes to {}.", file.recording.len(), self.scenario_path),
+                Err(error) => format!("Could not save the recording: {}.", error),
+            },
+            None => "Nothing was recorded.".to_string(),
+        };
+        self.scenario_status = Some(status);
+    }
+
+    //Replays the recording of the scenario file from the time given on
+    fn open_replay(&mut self, time: f64) {
+        let status = match std::fs::read_to_string(&self.scenario_path).map_err(|error| error.to_string())
+            .and_then(|text| ScenarioFile::from_json(&text).map_err(|error| error.to_string()))
+        {
+            Ok(file) => match Replay::new(&file) {
+                Some(replay) => {
+                    let status = format!("Replaying {} over {}.", file.name, replay.duration().formatted());
+                    self.replay = Some((time, replay));
+                    status
+                }
+                None => format!("{} has no recording to replay.", file.name),
+            },
+            Err(error) => format!("Could not open the scenario: {}.", error),
+        };
+        self.scenario_status = Some(status);
+    }
+
+    fn scenario_file(&self, transfer: &Transfer) -> ScenarioFile {
+        let name = |body: Option<usize>| body.map(|i| self.bodies[i].name);
+        let parent = ParentEntry {
+            body: name(self.state.parent_body).map(str::to_string),
+            gravitational_parameter: self.state.mass.mu(),
+        };
+        let mut leg = Leg::from_transfer(transfer, name(self.state.origin_body), name(self.state.target_body));
+        if self.state.hohmann {leg.delta_v = None}
+        if self.state.advanced {leg.arrival = ArrivalEntry::Capture { radius: self.mission.capture_radius.m() }}
+        let file_name = std::path::Path::new(&self.scenario_path).file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        ScenarioFile::new(file_name, parent, leg)
+    }
+
+    //Loads the first leg of a scenario, bodies being picked from the catalog by name
+    fn load_scenario(&mut self, file: &ScenarioFile) -> String {
+        //Files whose transfers can't be made are reported rather than loaded
+        if let Err(error) = file.transfers() {
+            return format!("Could not open the scenario: {}.", error);
+        }
+        self.load_leg(&file.parent, &file.legs[0]);
+        match file.legs.len() {
+            1 => format!("Opened {}.", file.name),
+            legs => format!("Opened the first of the {} legs of {}.", legs, file.name),
+        }
+    }
+
+    fn load_leg(&mut self, parent: &ParentEntry, leg: &Leg) {
+        let find = |body: &Option<String>| body.as_ref().and_then(|name| self.bodies.iter().position(|body| body.name == name));
+        let (parent_body, origin_body, target_body) = (find(&parent.body), find(&leg.origin.body), find(&leg.target.body));
+
+        self.state.parent_body = parent_body;
+        self.state.mass = Mass::from_mu(parent.gravitational_parameter);
+        self.state.origin_body = origin_body;
+        self.state.target_body = target_body;
+        self.state.origin_sma = Distance::from_meters(leg.origin.sma);
+        self.state.target_sma = Distance::from_meters(leg.target.sma);
+        self.state.origin_retrograde = leg.origin.retrograde;
+        self.state.target_retrograde = leg.target.retrograde;
+        self.state.origin_mode = InputMode::Sma;
+        self.state.target_mode = InputMode::Sma;
+        self.state.hohmann = leg.delta_v.is_none();
+        if let Some(delta_v) = leg.delta_v {self.state.velocity = Velocity::from_meters_per_second(delta_v)}
+        //Solutions are only offered up to MAX_REVOLUTIONS
+        self.state.revolutions = leg.revolutions.min(MAX_REVOLUTIONS);
+        if let ArrivalEntry::Capture { radius } = leg.arrival {self.mission.capture_radius = Distance::from_meters(radius)}
+    }
+
+    //Bodies of the custom system with their orbit, mass and color, shared as .system files
+    fn system_editor(&mut self, ui: &mut Ui) {
+        let (mut picked, mut removed) = (None, None);
+        let distance_units = distance_units();
+        Grid::new("system").striped(true).show(ui, |ui| {
+            for (i, body) in self.system.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut body.name).desired_width(80.0));
+                //Moons as well as planets, in the units fitting their orbit
+                let mut sma = Distance::from_meters(body.sma);
+                let range = Distance::from_kilometers(10.0)..=Distance::from_astronomical_unit(1000.0);
+                if ui.add(QuantityDragValue::new(&mut sma, range, &distance_units)).changed() {
+                    body.sma = sma.m();
+                }
+                //No mass for bodies of negligible mass
+                let mut mass = body.mass.map_or(0.0, |mass| Mass::from_kilograms(mass).earth());
+                if ui.add(DragValue::new(&mut mass).speed(0.01).clamp_range(0.0..=100000.0).suffix(" M⊕")).changed() {
+                    body.mass = (mass > 0.0).then(|| Mass::from_earth(mass).kg());
+                }
+                ui.color_edit_button_srgb(&mut body.color);
+                ui.checkbox(&mut body.retrograde, "Retrograde");
+                if ui.selectable_label(self.origin_system == Some(i), "Origin").clicked() {picked = Some((i, true))}
+                if ui.selectable_label(self.target_system == Some(i), "Target").clicked() {picked = Some((i, false))}
+                if ui.small_button("🗑").on_hover_text("Remove this body").clicked() {removed = Some(i)}
+                ui.end_row();
+            }
+        });
+
+        if let Some((i, origin)) = picked {
+            let body = &self.system[i];
+            let (system, catalog, sma, mass, mode) = if origin {
+                (&mut self.origin_system, &mut self.state.origin_body, &mut self.state.origin_sma, &mut self.state.origin_mass, &mut self.state.origin_mode)
+            } else {
+                (&mut self.target_system, &mut self.state.target_body, &mut self.state.target_sma, &mut self.state.target_mass, &mut self.state.target_mode)
+            };
+            *system = Some(i);
+            *catalog = None;
+            *sma = Distance::from_meters(body.sma);
+            if let Some(kg) = body.mass {*mass = Mass::from_kilograms(kg)}
+            *mode = InputMode::Sma;
+            self.state.parent_body = None;
+        }
+        if let Some(i) = removed {
+            self.system.remove(i);
+            for picked in [&mut self.origin_system, &mut self.target_system] {
+                *picked = match *picked {
+                    Some(j) if j == i => None,
+                    Some(j) if j > i => Some(j - 1),
+                    picked => picked,
+                };
+            }
+        }
+        //Picked bodies follow their edits
+        if let Some(i) = self.origin_system {
+            self.state.origin_sma = Distance::from_meters(self.system[i].sma);
+            self.state.origin_retrograde = self.system[i].retrograde;
+        }
+        if let Some(i) = self.target_system {
+            self.state.target_sma = Distance::from_meters(self.system[i].sma);
+            self.state.target_retrograde = self.system[i].retrograde;
+        }
+
+        if ui.button("Add body").clicked() {
+            let sma = self.system.last().map_or(self.state.target_sma, |body| Distance::from_meters(body.sma * 1.5));
+            let mut body = SystemBody::new(&format!("Body {}", self.system.len() + 1), sma);
+            body.color = SYSTEM_COLORS[self.system.len() % SYSTEM_COLORS.len()];
+            self.system.push(body);
+        }
+        ui.horizontal(|ui| {
+            ui.label("System file:");
+            ui.text_edit_singleline(&mut self.system_path);
+            if ui.button("Save").clicked() {
+                self.save_system();
+            }
+            if ui.button("Open").clicked() {
+                self.open_system();
+            }
+        });
+        if let Some(status) = &self.system_status {
+            ui.label(status);
+        }
+    }
+
+    fn save_system(&mut self) {
+        let name = std::path::Path::new(&self.system_path).file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let parent = ParentEntry { body: None, gravitational_parameter: self.state.mass.mu() };
+        let status = match std::fs::write(&self.system_path, SystemFile::new(name, parent, self.system.clone()).to_json()) {
+            Ok(()) => format!("Saved the system to {}.", self.system_path),
+            Err(error) => format!("Could not save the system: {}.", error),
+        };
+        self.system_status = Some(status);
+    }
+
+    //Opening a system makes its parent the custom one
+    fn open_system(&mut self) {
+        let status = match std::fs::read_to_string(&self.system_path).map_err(|error| error.to_string())
+            .and_then(|text| SystemFile::from_json(&text).map_err(|error| error.to_string()))
+        {
+            Ok(file) => {
+                self.state.parent_body = None;
+                self.state.mass = Mass::from_mu(file.parent.gravitational_parameter);
+                self.system = file.bodies;
+                self.origin_system = None;
+                self.target_system = None;
+                format!("Opened {} with {} bodies.", file.name, self.system.len())
+            }
+            Err(error) => format!("Could not open the system: {}.", error),
+        };
+        self.system_status = Some(status);
+    }
+
+    //Exchanges the origin and target, a custom delta-v becoming the one of the return leg
+    fn swap_bodies(&mut self, transfer: &Transfer) {
+        std::mem::swap(&mut self.state.origin_sma, &mut self.state.target_sma);
+        std::mem::swap(&mut self.state.origin_body, &mut self.state.target_body);
+        std::mem::swap(&mut self.origin_system, &mut self.target_system);
+        std::mem::swap(&mut self.state.origin_mode, &mut self.state.target_mode);
+        std::mem::swap(&mut self.state.origin_parameters, &mut self.state.target_parameters);
+        std::mem::swap(&mut self.state.origin_mass, &mut self.state.target_mass);
+        std::mem::swap(&mut self.state.origin_retrograde, &mut self.state.target_retrograde);
+        let return_leg = transfer.reversed();
+        self.state.velocity = return_leg.launch_velocity() - return_leg.origin().orbital_velocity();
+    }
+
+    //Resonant orbit sharing an apsis with the orbit of the target body
+    fn resonance(&mut self, ui: &mut Ui, target: &Planet, calendar: &Calendar, color_mode: Color32) {
+        ui.horizontal(|ui| {
+            ui.label("Resonance:");
+            ui.add(DragValue::new(&mut self.resonance).clamp_range(1..=20));
+            ui.label(":");
+            ui.add(DragValue::new(&mut self.resonance_revolutions).clamp_range(1..=20));
+        });
+        ui.add_space(5.0);
+
+        let resonant_orbit = match ResonantOrbit::new(target, self.resonance, self.resonance_revolutions) {
+            Some(resonant_orbit) => resonant_orbit,
+            None => {
+                ui.label("This resonance would bring the orbit through the parent body.");
+                return;
+            }
+        };
+
+        ui.label(format!(
+            "The resonant orbit has a periapsis of {} and an apoapsis of {}, and a period of {}.",
+            resonant_orbit.periapsis.formatted(),
+            resonant_orbit.apoapsis.formatted(),
+            resonant_orbit.period.formatted_in(calendar),
+        ));
+        ui.label(format!(
+            "It takes {} from the orbit of the target, consecutive passes being {} ° apart.",
+            resonant_orbit.delta_v.formatted(),
+            significant(resonant_orbit.separation.to_degrees(), SIGNIFICANT_FIGURES),
+        ));
+
+        let resonance_plot = ResonancePlot::new(target, &resonant_orbit, color_mode);
+
+        Plot::new("resonance_plot")
+        .allow_zoom(false)
+        .allow_drag(false)
+        .show_background(false)
+        .show_axes([false; 2])
+        .show_x(false).show_y(false)
+        .data_aspect(1.0)
+
+        .show(ui, |plot_ui| {
+            for orbit in resonance_plot.orbit_all() {
+                plot_ui.line(orbit);
+            }
+            plot_ui.points(resonance_plot.passes());
+        });
+    }
+
+    //Single burns at the apsides of an orbit around the parent
+    fn maneuvers(&mut self, ui: &mut Ui, parent: Parent) {
+        let distance = |ui: &mut Ui, label: &str, distance: &mut Distance| {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                let mut km = distance.km();
+                let speed = km * 1E-3;
+                ui.add(DragValue::new(&mut km).speed(speed).clamp_range(1.0..=f64::MAX).suffix(" km"));
+                *distance = Distance::from_kilometers(km);
+            });
+        };
+        distance(ui, "Periapsis:", &mut self.maneuver_periapsis);
+        distance(ui, "Apoapsis:", &mut self.maneuver_apoapsis);
+        distance(ui, "New apsis:", &mut self.maneuver_apsis);
+        ui.horizontal(|ui| {
+            ui.label("Inclination change:");
+            ui.add(DragValue::new(&mut self.maneuver_inclination).speed(0.1).clamp_range(0.0..=180.0).suffix(" °"));
+        });
+        ui.add_space(5.0);
+
+        self.maneuver_apoapsis = self.maneuver_apoapsis.max(self.maneuver_periapsis);
+        let orbit = Orbit::from_apsides(self.maneuver_periapsis, self.maneuver_apoapsis, parent);
+
+        let describe = |maneuver: Option<Maneuver>| match maneuver {
+            Some(maneuver) => format!(
+                "{}{} at {}.",
+                Velocity::from_meters_per_second(maneuver.delta_v.mps().abs()).formatted(),
+                //Burns changing the plane are neither prograde nor retrograde
+                if maneuver.orbit.inclination != orbit.inclination {""} else if maneuver.delta_v.mps() < 0.0 {" retrograde"} else {" prograde"},
+                match maneuver.location {
+                    Apsis::Periapsis => "periapsis",
+                    Apsis::Apoapsis => "apoapsis",
+                },
+            ),
+            None => "Not possible.".to_string(),
+        };
+
+        ui.label(format!("Moving the apoapsis to the new apsis takes {}", describe(maneuvers::change_apoapsis(&orbit, self.maneuver_apsis))));
+        ui.add_space(5.0);
+        ui.label(format!("Moving the periapsis to the new apsis takes {}", describe(maneuvers::change_periapsis(&orbit, self.maneuver_apsis))));
+        ui.add_space(5.0);
+        ui.label(format!("Circularizing at periapsis takes {}", describe(maneuvers::circularize(&orbit, Apsis::Periapsis))));
+        ui.add_space(5.0);
+        ui.label(format!("Circularizing at apoapsis takes {}", describe(maneuvers::circularize(&orbit, Apsis::Apoapsis))));
+
+        if self.maneuver_inclination > 0.0 {
+            let inclination = self.maneuver_inclination.to_radians();
+            ui.add_space(5.0);
+            ui.label(format!("Changing the inclination alone takes {}", describe(maneuvers::change_plane(&orbit, Apsis::Apoapsis, inclination))));
+            ui.add_space(5.0);
+            ui.label(format!(
+                "Changing the inclination while moving the periapsis to the new apsis takes {}",
+                describe(maneuvers::change_apsis_and_plane(&orbit, Apsis::Apoapsis, self.maneuver_apsis, inclination)),
+            ));
+        }
+    }
+
+    //Hohmann transfers between low orbits of every pair of bodies of the catalog
+    fn delta_v_map(&mut self, ui: &mut Ui) {
+        let bodies = &self.bodies;
+        let mut entries = self.delta_v_map.clone();
+        entries.sort_by(|a, b| {
+            let ordering = match self.delta_v_column {
+                DeltaVColumn::Origin => bodies[a.origin].name.cmp(bodies[b.origin].name),
+                DeltaVColumn::Target => bodies[a.target].name.cmp(bodies[b.target].name),
+                DeltaVColumn::DeltaV => a.delta_v.partial_cmp(&b.delta_v).unwrap_or(std::cmp::Ordering::Equal),
+            };
+            if self.delta_v_descending {ordering.reverse()} else {ordering}
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Low orbits are at {} times the radius of the bodies, moons being reached through a low orbit of their planet.", LOW_ORBIT_RADII));
+            if ui.button("Copy as CSV").clicked() {
+                let mut csv = "Origin,Target,Delta-v (m/s)\n".to_string();
+                for entry in &entries {
+                    csv.push_str(&format!("{},{},{}\n", bodies[entry.origin].name, bodies[entry.target].name, round_to(entry.delta_v.mps(), 0)));
+                }
+                ui.output().copied_text = csv;
+            }
+        });
+        ui.add_space(5.0);
+
+        ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("delta_v_map").striped(true).show(ui, |ui| {
+                //Clicking a header sorts by its column, clicking it again reverses the order
+                for (column, header) in [(DeltaVColumn::Origin, "Origin"), (DeltaVColumn::Target, "Target"), (DeltaVColumn::DeltaV, "Delta-v")] {
+                    if ui.selectable_label(self.delta_v_column == column, header).clicked() {
+                        self.delta_v_descending = self.delta_v_column == column && !self.delta_v_descending;
+                        self.delta_v_column = column;
+                    }
+                }
+                ui.end_row();
+
+                for entry in &entries {
+                    ui.label(bodies[entry.origin].name);
+                    ui.label(bodies[entry.target].name);
+                    ui.label(format!("{} m/s", round_to(entry.delta_v.mps(), 0)));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
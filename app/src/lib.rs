@@ -1,6 +1,8 @@
 mod app;
-pub mod widgets;
-pub mod plotting;
+pub mod model;
+mod palette;
+mod tour;
+pub mod worker;
 
 pub use app::Gui;
 
@@ -0,0 +1,538 @@
+//Inputs of the transfer and what is derived from them, apart from the interface so that it can be checked on its own
+
+use eframe::epi;
+
+use planetary_transfer::catalog::{self, Body};
+use planetary_transfer::delta_v_map::LOW_ORBIT_RADII;
+use planetary_transfer::{BurnLocation, CaptureAdvisory, Distance, Duration, EscapeBurn, Finite, FiniteBurn, LandingEstimate, Launch, LaunchEstimate, ManeuverNode, Mass, ParkingOrbit, Parent, Planet, StationKeeping, SurfaceForces, Transfer, TransferError, TransferSummary, Velocity, STANDARD_GRAVITY};
+
+use transfer_widget::plotting::TransferGeometry;
+use transfer_widget::widgets::{unit_for, distance_units, mass_units, velocity_units, InputMode, OrbitParameters};
+
+//Inputs the transfer is made from, as set through the controls
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AppState {
+    pub origin_sma: Distance,
+    pub target_sma: Distance,
+    pub origin_body: Option<usize>,
+    pub target_body: Option<usize>,
+    pub origin_mode: InputMode,
+    pub target_mode: InputMode,
+    pub origin_parameters: OrbitParameters,
+    pub target_parameters: OrbitParameters,
+    pub mass: Mass,
+    pub parent_body: Option<usize>,
+    pub binary: bool,
+    pub companion_mass: Mass,
+    pub companion_separation: Distance,
+    pub companion_eccentricity: f64,
+    pub velocity: Velocity,
+    pub hohmann: bool,
+    pub revolutions: u32,
+    pub headroom: f64,
+    pub advanced: bool,
+    pub origin_mass: Mass,
+    pub target_mass: Mass,
+    pub origin_retrograde: bool,
+    pub target_retrograde: bool,
+    pub barycentric: bool,
+    //Semi-major axes of the last transfer that could be made, restored when the origin and target end up sharing an orbit
+    #[serde(skip)]
+    pub valid_smas: Option<(Distance, Distance)>,
+    #[serde(skip)]
+    pub degenerate: bool,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            origin_sma: Distance::from_astronomical_unit(1.0),
+            target_sma: Distance::from_astronomical_unit(1.52366),
+            origin_body: None,
+            target_body: None,
+            origin_mode: InputMode::Sma,
+            target_mode: InputMode::Sma,
+            origin_parameters: OrbitParameters::default(),
+            target_parameters: OrbitParameters::default(),
+            mass: catalog::bodies()[0].mass,
+            parent_body: Some(0),
+            binary: false,
+            companion_mass: Mass::from_solar(0.5),
+            companion_separation: Distance::from_astronomical_unit(0.2),
+            companion_eccentricity: 0.0,
+            velocity: Velocity::from_kilometers_per_second(30.0),
+            hohmann: true,
+            revolutions: 0,
+            headroom: planetary_transfer::DEFAULT_HEADROOM,
+            advanced: false,
+            origin_mass: Mass::from_earth(1.0),
+            target_mass: Mass::from_earth(0.107),
+            origin_retrograde: false,
+            target_retrograde: false,
+            barycentric: false,
+            valid_smas: None,
+            degenerate: false,
+        }
+    }
+}
+
+impl AppState {
+    //Orbits stay above the surface of the parent when its radius is known, and within its Hill sphere when it orbits
+    //a body of the catalog, the range otherwise scaling with its mass as the periods around the Sun
+    pub fn sma_range(&self, parent: &Parent, bodies: &[Body]) -> (Distance, Distance) {
+        let min = parent.radius().unwrap_or(Distance::from_kilometers(10.0));
+        let hill_sphere = self.parent_body.and_then(|i| {
+            let (_, sma) = bodies[i].orbit?;
+            let primary = &bodies[bodies[i].primary(bodies)?];
+            Planet::new(sma, primary.parent()).with_mass(self.mass).hill_sphere()
+        });
+        let max = hill_sphere.unwrap_or(Distance::from_astronomical_unit(50.0 * self.mass.solar().cbrt()));
+        (min, max.max(min * 2.0))
+    }
+
+    //Saves from before the inputs were nested under `state` kept them among the other settings, read back from there
+    //when the save has no `state` of its own
+    pub fn migrate(storage: &dyn epi::Storage) -> Option<Self> {
+        #[derive(serde::Deserialize)]
+        struct Saved {
+            state: Option<serde::de::IgnoredAny>,
+        }
+        let saved: Saved = epi::get_value(storage, epi::APP_KEY)?;
+        if saved.state.is_some() {
+            return None;
+        }
+        epi::get_value(storage, epi::APP_KEY)
+    }
+}
+
+//Inputs of the departure from the origin and the arrival at the target, as set through the advanced controls
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct MissionState {
+    pub capture_radius: Distance,
+    //Years spent in the capture orbit, kept with station-keeping burns
+    pub station_keeping_years: f64,
+    pub origin_rotation: bool,
+    //In percent of the ideal delta-v of the launch
+    pub gravity_losses: f64,
+    pub drag_losses: f64,
+    //In m/s²
+    pub engine_acceleration: f64,
+    pub finite_burn: bool,
+    pub eccentric_parking: bool,
+    //Altitude of the apoapsis in km, the periapsis being the low orbit
+    pub parking_apoapsis_altitude: f64,
+    pub departure_burn: BurnLocation,
+    pub fixed_apsides: bool,
+    //Angle from the periapsis to the departure asymptote in degrees
+    pub asymptote_angle: f64,
+    //Inclination of the parking orbit to the equator of the origin in degrees
+    pub parking_inclination: f64,
+    pub surface_forces: SurfaceForces,
+    //Integration of the transfer checked against the analytic one, and its perturbation by a third body of the system
+    pub numerical_check: bool,
+    pub third_body: bool,
+    pub third_body_sma: Distance,
+    pub third_body_mass: Mass,
+    //In degrees
+    pub third_body_longitude: f64,
+}
+
+impl Default for MissionState {
+    fn default() -> Self {
+        Self {
+            capture_radius: Distance::from_kilometers(3800.0),
+            station_keeping_years: 1.0,
+            origin_rotation: true,
+            gravity_losses: Launch::default().gravity_losses * 100.0,
+            drag_losses: Launch::default().drag_losses * 100.0,
+            engine_acceleration: 1.0,
+            finite_burn: false,
+            eccentric_parking: false,
+            parking_apoapsis_altitude: 35_786.0,
+            departure_burn: BurnLocation::Periapsis,
+            fixed_apsides: false,
+            asymptote_angle: 0.0,
+            parking_inclination: 28.5,
+            surface_forces: SurfaceForces::default(),
+            numerical_check: false,
+            third_body: false,
+            third_body_sma: Distance::from_astronomical_unit(5.2),
+            third_body_mass: Mass::from_jovian(1.0),
+            third_body_longitude: 0.0,
+        }
+    }
+}
+
+impl MissionState {
+    //Saves from before the inputs were nested under `mission`, read back as for `AppState`
+    pub fn migrate(storage: &dyn epi::Storage) -> Option<Self> {
+        #[derive(serde::Deserialize)]
+        struct Saved {
+            mission: Option<serde::de::IgnoredAny>,
+        }
+        let saved: Saved = epi::get_value(storage, epi::APP_KEY)?;
+        if saved.mission.is_some() {
+            return None;
+        }
+        epi::get_value(storage, epi::APP_KEY)
+    }
+
+    pub fn launch(&self) -> Launch {
+        Launch {
+            gravity_losses: self.gravity_losses / 100.0,
+            drag_losses: self.drag_losses / 100.0,
+            rotation: self.origin_rotation,
+            ..Launch::default()
+        }
+    }
+}
+
+//Everything the transfer panel shows that follows from the inputs alone
+pub struct DerivedModel {
+    pub parent: Parent,
+    pub origin: Planet,
+    pub target: Planet,
+    pub transfer: Transfer,
+    //Range of the semi-major axes of the origin and target around the parent
+    pub sma_min: Distance,
+    pub sma_max: Distance,
+    //Ends of the delta-v slider, from the Hohmann transfer on, the start being above the end for inward transfers
+    pub min: Velocity,
+    pub max: Velocity,
+    pub summary: TransferSummary,
+    //Polylines of the diagram, and the Hohmann transfer between the same orbits drawn behind faster ones
+    pub geometry: TransferGeometry,
+    pub hohmann: (Transfer, TransferGeometry),
+    //Units the sliders show the orbits, the mass of the parent and the delta-v in
+    pub origin_sma_unit: &'static str,
+    pub target_sma_unit: &'static str,
+    pub mass_unit: &'static str,
+    pub velocity_unit: &'static str,
+}
+
+impl DerivedModel {
+    //Inputs the transfer can't be made from are brought back to ones it can, as the state of the controls. None when
+    //the orbits had to be restored, the model being derived from them again on the next frame.
+    pub fn new(state: &mut AppState, bodies: &[Body]) -> Option<Self> {
+        //Create the parent
+        //Forget the body picked as parent once its mass is changed
+        if let Some(body) = state.parent_body.map(|i| &bodies[i]) {
+            if (body.mass.kg() - state.mass.kg()).abs() > body.mass.kg() * 1E-9 {
+                state.parent_body = None;
+            }
+        }
+        //A mass that isn't finite, as from an older save, is reset rather than spreading through the plots
+        let parent = match state.parent_body {
+            Some(i) => bodies[i].parent(),
+            None => match Parent::try_new(state.mass) {
+                Ok(parent) => parent,
+                Err(_) => {
+                    let default = AppState::default();
+                    state.mass = default.mass;
+                    state.parent_body = default.parent_body;
+                    return None;
+                }
+            },
+        };
+        let parent = match Finite::new(state.companion_eccentricity) {
+            Ok(eccentricity) if state.binary => parent.with_companion(state.companion_mass, state.companion_separation, eccentricity),
+            Ok(_) => parent,
+            Err(_) => {
+                state.companion_eccentricity = 0.0;
+                parent
+            }
+        };
+        //Orbits out of the range allowed around the parent, as after changing its mass, are brought back within it
+        //Both ending up on the same end of the range, they are spread over it instead, keeping their order
+        let (sma_min, sma_max) = state.sma_range(&parent, bodies);
+        let clamp = |sma: Distance| Distance::from_meters(sma.m().clamp(sma_min.m(), sma_max.m()));
+        let (origin_sma, target_sma) = (clamp(state.origin_sma), clamp(state.target_sma));
+        if origin_sma == target_sma && state.origin_sma != state.target_sma {
+            let (origin_sma, target_sma) = if state.origin_sma.m() < state.target_sma.m() {(sma_min, sma_max)} else {(sma_max, sma_min)};
+            state.origin_sma = origin_sma;
+            state.target_sma = target_sma;
+        } else {
+            state.origin_sma = origin_sma;
+            state.target_sma = target_sma;
+        }
+        //Forget the bodies picked as origin or target once their orbit or the parent is changed
+        for (body, sma, retrograde) in [(&mut state.origin_body, state.origin_sma, state.origin_retrograde), (&mut state.target_body, state.target_sma, state.target_retrograde)] {
+            if let Some(i) = *body {
+                let orbits = matches!(bodies[i].orbit, Some((_, body_sma)) if (body_sma.m() - sma.m()).abs() <= body_sma.m() * 1E-9);
+                if !orbits || bodies[i].retrograde != retrograde || bodies[i].primary(bodies) != state.parent_body {
+                    *body = None;
+                }
+            }
+        }
+        if parent.radius().is_none() {
+            if state.origin_mode == InputMode::Altitude {state.origin_mode = InputMode::Sma}
+            if state.target_mode == InputMode::Altitude {state.target_mode = InputMode::Sma}
+        }
+
+        //Create the two planet used for the transfer
+        //Transfers only use their semi-major axes, the other parameters being shown on the plot
+        let origin = Planet::from_orbit(state.origin_parameters.orbit(state.origin_sma, parent)).with_retrograde(state.origin_retrograde);
+        let target = Planet::from_orbit(state.target_parameters.orbit(state.target_sma, parent)).with_retrograde(state.target_retrograde);
+        let (origin, target) = if state.advanced {
+            (origin.with_mass(state.origin_mass), target.with_mass(state.target_mass))
+        } else {
+            (origin, target)
+        };
+
+        //Create a transfer with the two previously created planets
+        //Orbits or a mass that aren't finite, as from an older save, are reset rather than spreading through the plots
+        let mut transfer = match Transfer::try_new(origin, target) {
+            Ok(transfer) => {
+                if state.valid_smas != Some((state.origin_sma, state.target_sma)) {
+                    state.valid_smas = Some((state.origin_sma, state.target_sma));
+                    state.degenerate = false;
+                }
+                transfer
+            }
+            //Orbits too close to each other leave nothing to transfer between, the last ones that did are kept instead
+            Err(TransferError::DegenerateTransfer) if state.valid_smas.is_some() => {
+                if let Some((origin_sma, target_sma)) = state.valid_smas {
+                    state.origin_sma = origin_sma;
+                    state.target_sma = target_sma;
+                }
+                state.degenerate = true;
+                return None;
+            }
+            Err(_) => {
+                let default = AppState::default();
+                state.origin_sma = default.origin_sma;
+                state.target_sma = default.target_sma;
+                state.mass = default.mass;
+                state.parent_body = default.parent_body;
+                return None;
+            }
+        };
+        transfer.set_barycentric(state.advanced && state.barycentric);
+        if state.hohmann {state.velocity = transfer.delta_v_hohmann()};
+        //Same for a delta-v whose results aren't finite, falling back to the Hohmann transfer
+        if transfer.try_set_delta_v(state.velocity).is_err() {
+            state.velocity = transfer.delta_v_hohmann();
+            transfer.set_delta_v(state.velocity);
+        }
+        transfer.set_revolutions(state.revolutions);
+
+        //The slider covers the delta-v suggested for the transfer, from the Hohmann transfer on
+        let suggested = transfer.suggested_delta_v_range(state.headroom);
+        let (min, max) = if transfer.origin().sma() < transfer.target().sma() {(*suggested.start(), *suggested.end())} else {(*suggested.end(), *suggested.start())};
+        //A delta-v left out of the range by a change of the orbits or of the parent is brought back within it
+        let clamped = state.velocity.mps().clamp(min.mps().min(max.mps()), min.mps().max(max.mps()));
+        if !state.hohmann && clamped != state.velocity.mps() {
+            state.velocity = Velocity::from_meters_per_second(clamped);
+            transfer.set_delta_v(state.velocity);
+        }
+
+        let summary = match state.parent_body.map(|i| &bodies[i]) {
+            Some(body) => TransferSummary::new(&transfer).with_parent(body.name),
+            None => TransferSummary::new(&transfer),
+        };
+
+        let mut hohmann = transfer;
+        hohmann.set_revolutions(0);
+        hohmann.set_delta_v(transfer.delta_v_hohmann());
+
+        Some(Self {
+            parent,
+            origin,
+            target,
+            transfer,
+            sma_min,
+            sma_max,
+            min,
+            max,
+            summary,
+            geometry: TransferGeometry::new(&transfer),
+            hohmann: (hohmann, TransferGeometry::new(&hohmann)),
+            origin_sma_unit: unit_for(&distance_units(), &state.origin_sma).suffix(),
+            target_sma_unit: unit_for(&distance_units(), &state.target_sma).suffix(),
+            mass_unit: unit_for(&mass_units(), &state.mass).suffix(),
+            velocity_unit: unit_for(&velocity_units(), &state.velocity).suffix(),
+        })
+    }
+}
+
+//Departure from the origin picked from the catalog, launched into a low parking orbit and escaping from it
+pub struct DepartureModel {
+    pub launch: Option<LaunchEstimate>,
+    //Escapes from the eccentric parking orbit at its periapsis, its apoapsis and at best
+    pub escapes: Option<[EscapeBurn; 3]>,
+    //Impulsive escape, from where it is picked on the eccentric parking orbit
+    pub escape: Velocity,
+    pub burn_inputs: BurnInputs,
+    //Integrated apart from the rest, None until they are
+    pub burns: Option<Burns>,
+    //Time drag takes to bring the parking orbit down, None above the atmosphere
+    pub lifetime: Option<Duration>,
+    //Turn of the plane of the parking orbit from the oblateness of the origin, in radians a day and by the window
+    pub nodal_drift: Option<(f64, f64)>,
+    pub synchronous_orbit: Option<Distance>,
+}
+
+impl DepartureModel {
+    //Time until the launch window, over which the plane of the parking orbit drifts
+    pub fn new(mission: &MissionState, body: &Body, transfer: &Transfer, wait_time: Duration) -> Self {
+        let parent = body.parent();
+        let parking_orbit = body.radius * LOW_ORBIT_RADII;
+        let v_infinity = transfer.departure_v_infinity();
+
+        let eccentric = mission.eccentric_parking.then(|| {
+            let apoapsis = body.radius + Distance::from_kilometers(mission.parking_apoapsis_altitude);
+            let eccentric = ParkingOrbit::new(parking_orbit, apoapsis.max(parking_orbit));
+            if mission.fixed_apsides {eccentric.with_asymptote_angle(mission.asymptote_angle.to_radians())} else {eccentric}
+        });
+        let escapes = eccentric.map(|eccentric| eccentric.escapes(&parent, v_infinity));
+        let escape = match escapes {
+            Some([periapsis, apoapsis, optimal]) => match mission.departure_burn {
+                BurnLocation::Periapsis => periapsis.delta_v,
+                BurnLocation::Apoapsis => apoapsis.delta_v,
+                BurnLocation::Optimal => optimal.delta_v,
+            },
+            None => parent.escape_delta_v(parking_orbit, v_infinity),
+        };
+        let burn_inputs = BurnInputs {
+            parent,
+            parking_orbit,
+            transfer: *transfer,
+            escape,
+            engine_acceleration: mission.engine_acceleration,
+            finite_burn: mission.finite_burn,
+        };
+
+        //The oblateness of the origin turns the plane of the parking orbit while waiting for the window
+        let parking = eccentric.unwrap_or_else(|| ParkingOrbit::circular(parking_orbit));
+        let inclination = mission.parking_inclination.to_radians();
+        let nodal_drift = parking.nodal_precession_rate(&parent, inclination)
+            .zip(parking.nodal_drift(&parent, inclination, wait_time))
+            .map(|(rate, drift)| (rate * Duration::from_days(1.0).s(), drift));
+
+        Self {
+            launch: mission.launch().estimate(&parent, parking_orbit),
+            escapes,
+            escape,
+            burn_inputs,
+            burns: None,
+            lifetime: mission.surface_forces.orbit_lifetime(&parent, parking_orbit),
+            nodal_drift,
+            synchronous_orbit: parent.synchronous_orbit(),
+        }
+    }
+}
+
+//Inputs the departure burns are integrated from, only done again once the parking orbit or the engine change
+#[derive(Clone, Copy)]
+pub struct BurnInputs {
+    pub parent: Parent,
+    pub parking_orbit: Distance,
+    pub transfer: Transfer,
+    pub escape: Velocity,
+    //In m/s²
+    pub engine_acceleration: f64,
+    pub finite_burn: bool,
+}
+
+//The burns only depend on the parent through its gravitational parameter and on the transfer through the excess
+//velocity it departs with
+impl PartialEq for BurnInputs {
+    fn eq(&self, other: &Self) -> bool {
+        self.parent.gravitational_parameter() == other.parent.gravitational_parameter()
+            && self.parking_orbit == other.parking_orbit
+            && self.transfer.departure_v_infinity() == other.transfer.departure_v_infinity()
+            && self.escape == other.escape
+            && self.engine_acceleration == other.engine_acceleration
+            && self.finite_burn == other.finite_burn
+    }
+}
+
+//Departure burns of finite duration, integrated numerically
+#[derive(Clone, Copy)]
+pub struct Burns {
+    //None with the finite burn off or an engine too weak to depart in a single burn
+    pub finite_burn: Option<FiniteBurn>,
+    //None with an engine too weak to depart in a single burn
+    pub node: Option<ManeuverNode>,
+}
+
+impl Burns {
+    pub fn new(inputs: &BurnInputs) -> Self {
+        Self {
+            finite_burn: Self::finite_burn(inputs),
+            node: Self::node(inputs),
+        }
+    }
+
+    pub fn finite_burn(inputs: &BurnInputs) -> Option<FiniteBurn> {
+        if inputs.finite_burn {
+            FiniteBurn::new(&inputs.parent, inputs.parking_orbit, inputs.escape, inputs.engine_acceleration / STANDARD_GRAVITY)
+        } else {
+            None
+        }
+    }
+
+    //The plane change is left to the arrival, the departure node staying in the plane of the origin
+    pub fn node(inputs: &BurnInputs) -> Option<ManeuverNode> {
+        ManeuverNode::departure(&inputs.transfer, &inputs.parent, inputs.parking_orbit, 0.0, inputs.engine_acceleration)
+    }
+}
+
+//Arrival at the target, captured into an orbit of the radius of the capture orbit
+pub struct ArrivalModel {
+    pub capture: Option<CaptureAdvisory>,
+    pub station_keeping: Option<StationKeeping>,
+    //Only known for a target picked from the catalog, whose radius and atmosphere are known
+    pub landing: Option<LandingEstimate>,
+}
+
+impl ArrivalModel {
+    pub fn new(state: &AppState, mission: &MissionState, model: &DerivedModel, bodies: &[Body]) -> Self {
+        //The target picked from the catalog brings its atmosphere along
+        let body = state.target_body.map(|i| bodies[i].parent());
+        let target_parent = body.unwrap_or_else(|| Parent::new(state.target_mass));
+        Self {
+            capture: CaptureAdvisory::new(&model.target, model.transfer.arrival_v_infinity(), mission.capture_radius),
+            station_keeping: StationKeeping::new(&model.target, &target_parent, mission.capture_radius, &mission.surface_forces),
+            landing: body.and_then(|body| mission.launch().landing(&body, mission.capture_radius)),
+        }
+    }
+}
+
+//Delta-v from the pad to the surface of the target, as far as the bodies are known
+pub struct Budget {
+    pub steps: Vec<(&'static str, Velocity)>,
+}
+
+impl Budget {
+    pub fn new(mission: &MissionState, departure: Option<&DepartureModel>, arrival: &ArrivalModel) -> Self {
+        let mut steps = Vec::new();
+        if let Some(departure) = departure {
+            if let Some(launch) = departure.launch {
+                steps.push(("Launch to a low orbit", launch.total));
+            }
+            match departure.burns.and_then(|burns| burns.finite_burn) {
+                Some(burn) => steps.push(("Departure from the parking orbit, finite burn", burn.adjusted)),
+                None => steps.push(("Departure from the parking orbit", departure.escape)),
+            }
+        }
+        if let Some(capture) = arrival.capture {
+            steps.push(("Capture at the target", capture.direct_insertion));
+            if let Some(station_keeping) = arrival.station_keeping.filter(|_| mission.station_keeping_years > 0.0) {
+                steps.push(("Station-keeping in the capture orbit", station_keeping.annual() * mission.station_keeping_years));
+            }
+            match arrival.landing {
+                Some(landing) if landing.aerobraking => steps.push(("Deorbit, the atmosphere braking", landing.delta_v)),
+                Some(landing) => steps.push(("Landing", landing.delta_v)),
+                None => (),
+            }
+        }
+        Self {steps}
+    }
+
+    pub fn total(&self) -> Velocity {
+        self.steps.iter().fold(Velocity::from_meters_per_second(0.0), |total, (_, delta_v)| total + *delta_v)
+    }
+}
@@ -0,0 +1,107 @@
+// Searchable list of the actions of the app, opened with Ctrl+K (Cmd+K on macOS), so that bodies and actions can
+// be picked by typing part of their name rather than by hunting through the panels.
+
+use egui::{Align2, CtxRef, Key, ScrollArea, Window};
+
+const SHOWN_COMMANDS: usize = 12;
+
+// Action of the app along with the text it is searched by.
+pub struct Command<A> {
+    pub label: String,
+    pub action: A,
+}
+
+impl<A> Command<A> {
+    pub fn new(label: impl Into<String>, action: A) -> Self {
+        Self {
+            label: label.into(),
+            action,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Palette {
+    open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl Palette {
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    // Shows the palette while it is open, returning the action picked this frame.
+    pub fn show<A: Copy>(&mut self, ctx: &CtxRef, commands: &[Command<A>]) -> Option<A> {
+        let input = ctx.input();
+        if input.modifiers.command && input.key_pressed(Key::K) {
+            if self.open {self.open = false} else {self.open()}
+        }
+        if input.key_pressed(Key::Escape) {
+            self.open = false;
+        }
+        let (up, down, enter) = (input.key_pressed(Key::ArrowUp), input.key_pressed(Key::ArrowDown), input.key_pressed(Key::Enter));
+        if !self.open {
+            return None;
+        }
+
+        // Best matches first, commands matching as well keeping the order they were registered in.
+        let mut matches: Vec<(i32, &Command<A>)> = commands.iter()
+            .filter_map(|command| fuzzy_score(&self.query, &command.label).map(|score| (score, command)))
+            .collect();
+        matches.sort_by_key(|&(score, _)| -score);
+        matches.truncate(SHOWN_COMMANDS);
+
+        if down {self.selected += 1}
+        if up {self.selected = self.selected.saturating_sub(1)}
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        let mut picked = enter.then(|| matches.get(self.selected).map(|(_, command)| command.action)).flatten();
+        Window::new("Command palette")
+            .anchor(Align2::CENTER_TOP, [0.0, 40.0])
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let query = ui.text_edit_singleline(&mut self.query);
+                if query.changed() {self.selected = 0}
+                query.request_focus();
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (i, (_, command)) in matches.iter().enumerate() {
+                        if ui.selectable_label(i == self.selected, &command.label).clicked() {
+                            picked = Some(command.action);
+                        }
+                    }
+                    if matches.is_empty() {
+                        ui.label("No matching command.");
+                    }
+                });
+            });
+
+        if picked.is_some() {
+            self.open = false;
+        }
+        picked
+    }
+}
+
+// Score of a label against a query whose characters must all appear in it in order, regardless of case.
+// Characters following each other or starting a word score higher, None when the label does not match.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut start = 0;
+    let mut previous = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = start + label[start..].iter().position(|&l| l == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {score += 5}
+        if found == 0 || !label[found - 1].is_alphanumeric() {score += 3}
+        previous = Some(found);
+        start = found + 1;
+    }
+    Some(score)
+}
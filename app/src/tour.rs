@@ -0,0 +1,169 @@
+// Guided walkthrough of the app shown on its first run, one callout at a time next to the control or part of the
+// diagram it explains, the diagram itself highlighting what the callout talks about.
+
+use egui::{Align2, Color32, CtxRef, Id, Key, LayerId, Order, Rect, Stroke, Window, vec2};
+
+pub const HIGHLIGHT: Color32 = Color32::from_rgb(255, 200, 0);
+// Room kept for the callout below its control before it goes above instead.
+const CALLOUT_HEIGHT: f32 = 180.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Welcome,
+    Origin,
+    Target,
+    Parent,
+    Hohmann,
+    Velocity,
+    Diagram,
+    PhaseAngle,
+    Protractor,
+    Commands,
+}
+
+const STEPS: [Step; 10] = [
+    Step::Welcome,
+    Step::Origin,
+    Step::Target,
+    Step::Parent,
+    Step::Hohmann,
+    Step::Velocity,
+    Step::Diagram,
+    Step::PhaseAngle,
+    Step::Protractor,
+    Step::Commands,
+];
+
+// Values of the current transfer the callouts are written with, so that they explain what the diagram shows.
+pub struct Facts {
+    pub delta_v: String,
+    pub time_of_flight: String,
+    pub phase_angle: String,
+    pub synodic_period: String,
+}
+
+#[derive(Default)]
+pub struct Tour {
+    step: Option<usize>,
+    // Area of the control of the current step, registered while the panels are drawn.
+    target: Option<Rect>,
+}
+
+impl Tour {
+    pub fn start(&mut self) {
+        self.step = Some(0);
+    }
+
+    pub fn step(&self) -> Option<Step> {
+        self.step.map(|i| STEPS[i])
+    }
+
+    // Whether the tour is at a step, for the diagram to highlight what it explains.
+    pub fn at(&self, step: Step) -> bool {
+        self.step() == Some(step)
+    }
+
+    // Area of the control or part of the diagram a step explains, the callout being placed next to it.
+    pub fn target(&mut self, step: Step, rect: Rect) {
+        if self.at(step) {
+            self.target = Some(rect);
+        }
+    }
+
+    // Shows the callout of the current step once the panels have registered the areas of their controls.
+    pub fn show(&mut self, ctx: &CtxRef, facts: &Facts) {
+        let i = match self.step {
+            Some(i) => i,
+            None => return,
+        };
+        if ctx.input().key_pressed(Key::Escape) {
+            self.step = None;
+            return;
+        }
+        let step = STEPS[i];
+        let (title, text) = callout(step, facts);
+
+        let mut window = Window::new(title)
+            .id(Id::new("tour"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(320.0);
+        window = match self.target.take() {
+            Some(rect) => {
+                ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("tour_highlight")))
+                    .rect_stroke(rect.expand(4.0), 4.0, Stroke::new(2.0, HIGHLIGHT));
+                let below = rect.left_bottom().y + CALLOUT_HEIGHT < ctx.input().screen_rect.bottom();
+                window.fixed_pos(if below {rect.left_bottom() + vec2(0.0, 10.0)} else {rect.left_top() - vec2(0.0, CALLOUT_HEIGHT)})
+            }
+            None => window.anchor(Align2::CENTER_CENTER, [0.0, 0.0]),
+        };
+
+        let mut next = self.step;
+        window.show(ctx, |ui| {
+            ui.label(text);
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if ui.add_enabled(i > 0, egui::Button::new("Back")).clicked() {
+                    next = Some(i - 1);
+                }
+                let last = i + 1 == STEPS.len();
+                if ui.button(if last {"Finish"} else {"Next"}).clicked() {
+                    next = (!last).then_some(i + 1);
+                }
+                if !last && ui.button("Skip tour").clicked() {
+                    next = None;
+                }
+                ui.label(format!("{}/{}", i + 1, STEPS.len()));
+            });
+        });
+        self.step = next;
+    }
+}
+
+fn callout(step: Step, facts: &Facts) -> (&'static str, String) {
+    match step {
+        Step::Welcome => ("Welcome", String::from(
+            "This calculator finds the orbit taking a spacecraft from one circular orbit to another around the same \
+            parent body, and how much it costs. This tour goes through the controls on the live diagram; press \
+            Escape to leave it at any time."
+        )),
+        Step::Origin => ("Origin", String::from(
+            "The orbit the spacecraft departs from. Pick a body of the catalog or drag the slider to set the size of \
+            its orbit, which turns red on the diagram while you change it."
+        )),
+        Step::Target => ("Target", String::from(
+            "The orbit the spacecraft goes to, set the same way. Transfers can go outward or inward."
+        )),
+        Step::Parent => ("Parent body", String::from(
+            "The body at the center, whose mass sets how fast everything orbits it: the heavier the parent, the \
+            faster the orbits and the shorter the transfers."
+        )),
+        Step::Hohmann => ("Hohmann transfer", format!(
+            "With Hohmann ticked, the transfer is the ellipse touching both orbits, the cheapest way between them \
+            with two burns, flown over half a revolution. Here it takes {} and lasts {}.",
+            facts.delta_v, facts.time_of_flight,
+        )),
+        Step::Velocity => ("Departure burn", String::from(
+            "Untick Hohmann to burn harder at departure: the transfer gets faster and more expensive, crossing the \
+            orbit of the target before reaching the opposite side of the parent."
+        )),
+        Step::Diagram => ("Diagram", String::from(
+            "The orbits drawn to scale, with the bodies at departure and at arrival and the transfer in between. \
+            Hovering a slider highlights the orbit it changes."
+        )),
+        Step::PhaseAngle => ("Phase angle", format!(
+            "The spacecraft only meets the target if it leaves when the target is ahead of the origin by the phase \
+            angle, here {}. Both bodies line up like this again every synodic period, {}.",
+            facts.phase_angle, facts.synodic_period,
+        )),
+        Step::Protractor => ("Protractor", format!(
+            "The protractor, highlighted on the diagram, measures the phase angle between the origin and the target \
+            at departure: {}. Tick Launch windows to find when the bodies are next in place.",
+            facts.phase_angle,
+        )),
+        Step::Commands => ("Commands", String::from(
+            "Every action is also a few keystrokes away: Ctrl+K (Cmd+K on macOS) opens the command palette, where \
+            this tour can be taken again."
+        )),
+    }
+}
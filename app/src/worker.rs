@@ -0,0 +1,108 @@
+// Heavy computations kept out of the update function so that the interface stays responsive.
+// Natively they run on a thread of their own, while on the web, where there are no threads, they advance
+// by a slice of work every frame. Either way the `Gui` polls the worker every frame for the result.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+// Computation split into slices of work, each short enough not to hold up a frame on its own.
+pub trait Job: Send + 'static {
+    type Output: Send + 'static;
+
+    // Does the next slice of work, returning the output once there is none left.
+    fn step(&mut self) -> Option<Self::Output>;
+}
+
+pub struct Worker<J: Job> {
+    #[cfg(not(target_arch = "wasm32"))]
+    running: Option<(Receiver<J::Output>, Arc<AtomicBool>)>,
+    #[cfg(target_arch = "wasm32")]
+    running: Option<J>,
+}
+
+impl<J: Job> Default for Worker<J> {
+    fn default() -> Self {
+        Self { running: None }
+    }
+}
+
+impl<J: Job> Worker<J> {
+    // Starts a job, abandoning the one running if any.
+    pub fn start(&mut self, job: J) {
+        self.cancel();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (sender, receiver) = mpsc::channel();
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let flag = Arc::clone(&cancelled);
+            let mut job = job;
+            std::thread::spawn(move || {
+                while !flag.load(Ordering::Relaxed) {
+                    if let Some(output) = job.step() {
+                        // The worker may have been dropped in the meantime.
+                        let _ = sender.send(output);
+                        break;
+                    }
+                }
+            });
+            self.running = Some((receiver, cancelled));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.running = Some(job);
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((_, cancelled)) = self.running.take() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.running = None;
+        }
+    }
+
+    pub fn busy(&self) -> bool {
+        self.running.is_some()
+    }
+
+    // Output of the job once it is done, to be called every frame.
+    pub fn poll(&mut self) -> Option<J::Output> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (receiver, _) = self.running.as_ref()?;
+            match receiver.try_recv() {
+                Err(TryRecvError::Empty) => None,
+                // A job that panicked is given up on.
+                result => {
+                    self.running = None;
+                    result.ok()
+                }
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let output = self.running.as_mut()?.step();
+            if output.is_some() {
+                self.running = None;
+            }
+            output
+        }
+    }
+}
+
+impl<J: Job> Drop for Worker<J> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
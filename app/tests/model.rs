@@ -0,0 +1,193 @@
+// Inputs of the panels and what they show, brought back within what the transfer allows, without the interface.
+
+use std::collections::HashMap;
+
+use app::model::{AppState, ArrivalModel, Budget, Burns, DepartureModel, DerivedModel, MissionState};
+use eframe::epi;
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, Duration, Mass, Velocity};
+
+#[test]
+fn hohmann_lock() {
+    let bodies = catalog::bodies();
+    let mut state = AppState::default();
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!(state.velocity.mps(), model.transfer.delta_v_hohmann().mps());
+    assert_eq!(model.min.mps(), state.velocity.mps());
+
+    // Unlocked, the delta-v is kept as long as it stays within the slider.
+    state.hohmann = false;
+    state.velocity = Velocity::from_meters_per_second((model.min.mps() + model.max.mps()) / 2.0);
+    let velocity = state.velocity;
+    DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!(state.velocity.mps(), velocity.mps());
+}
+
+#[test]
+fn slider_range() {
+    let bodies = catalog::bodies();
+    let mut state = AppState {hohmann: false, velocity: Velocity::from_kilometers_per_second(1000.0), ..AppState::default()};
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!(state.velocity.mps(), model.max.mps());
+
+    // Inward transfers slow down, the slider running from the Hohmann transfer down.
+    let mut state = AppState {origin_sma: Distance::from_astronomical_unit(1.52366), target_sma: Distance::from_astronomical_unit(1.0), ..AppState::default()};
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert!(model.min.mps() > model.max.mps());
+}
+
+#[test]
+fn restored_inputs() {
+    let bodies = catalog::bodies();
+    // Changing the mass of the parent forgets the body it was picked from, the orbits staying within its reach.
+    let mut state = AppState {mass: Mass::from_solar(2.0), target_sma: Distance::from_astronomical_unit(1000.0), ..AppState::default()};
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!(state.parent_body, None);
+    assert_eq!(state.target_sma.m(), model.sma_max.m());
+
+    // Orbits both beyond the reach of a lighter parent are spread over it rather than left sharing its edge.
+    let earth = bodies.iter().position(|body| body.name == "Earth").unwrap();
+    let mut state = AppState {mass: bodies[earth].mass, parent_body: Some(earth), ..AppState::default()};
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!((state.origin_sma.m(), state.target_sma.m()), (model.sma_min.m(), model.sma_max.m()));
+
+    // Orbits sharing a semi-major axis leave nothing to transfer between, the last ones that did being restored.
+    let mut state = AppState::default();
+    DerivedModel::new(&mut state, &bodies).unwrap();
+    let (origin_sma, target_sma) = (state.origin_sma, state.target_sma);
+    state.target_sma = state.origin_sma;
+    assert!(DerivedModel::new(&mut state, &bodies).is_none());
+    assert!(state.degenerate);
+    assert_eq!((state.origin_sma, state.target_sma), (origin_sma, target_sma));
+}
+
+#[test]
+fn unit_switching() {
+    let bodies = catalog::bodies();
+    let mut state = AppState::default();
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!((model.origin_sma_unit, model.mass_unit, model.velocity_unit), (" au", " M☉", " km/s"));
+
+    // Around the Earth orbits are shown in kilometers, the mass in Earth masses and the small burns between close
+    // orbits in meters per second.
+    let earth = bodies.iter().position(|body| body.name == "Earth").unwrap();
+    let mut state = AppState {
+        mass: bodies[earth].mass,
+        parent_body: Some(earth),
+        origin_sma: Distance::from_kilometers(7_000.0),
+        target_sma: Distance::from_kilometers(7_500.0),
+        ..AppState::default()
+    };
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!((model.origin_sma_unit, model.target_sma_unit, model.mass_unit, model.velocity_unit), (" km", " km", " Me", " m/s"));
+}
+
+#[test]
+fn mission_panels() {
+    let bodies = catalog::bodies();
+    let (earth, mars) = (&bodies[3], &bodies[5]);
+    assert_eq!((earth.name, mars.name), ("Earth", "Mars"));
+    let mut state = AppState {
+        origin_sma: earth.orbit.unwrap().1,
+        target_sma: mars.orbit.unwrap().1,
+        origin_body: Some(3),
+        target_body: Some(5),
+        advanced: true,
+        ..AppState::default()
+    };
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    assert_eq!((state.origin_body, state.target_body), (Some(3), Some(5)));
+
+    let mission = MissionState {finite_burn: true, ..MissionState::default()};
+    let mut departure = DepartureModel::new(&mission, earth, &model.transfer, Duration::from_days(100.0));
+    assert!(departure.escape.mps() > 3000.0 && departure.escape.mps() < 4000.0, "{} m/s", departure.escape.mps());
+    let burns = Burns::new(&departure.burn_inputs);
+    assert!(burns.finite_burn.unwrap().adjusted.mps() > departure.escape.mps());
+    assert!(burns.node.is_some());
+    departure.burns = Some(burns);
+    assert!(departure.nodal_drift.is_some() && departure.synchronous_orbit.is_some());
+
+    // The budget runs from the pad to the surface of Mars, the finite burn standing in for the impulsive one.
+    let arrival = ArrivalModel::new(&state, &mission, &model, &bodies);
+    let budget = Budget::new(&mission, Some(&departure), &arrival);
+    let steps: Vec<&str> = budget.steps.iter().map(|(step, _)| *step).collect();
+    assert_eq!(steps[..3], ["Launch to a low orbit", "Departure from the parking orbit, finite burn", "Capture at the target"]);
+    assert!(arrival.landing.is_some());
+    let total: f64 = budget.steps.iter().map(|(_, delta_v)| delta_v.mps()).sum();
+    assert!((budget.total().mps() - total).abs() < 1E-6);
+}
+
+// Storage of the settings kept in memory, as saved by eframe.
+#[derive(Default)]
+struct Memory(HashMap<String, String>);
+
+impl epi::Storage for Memory {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+#[test]
+fn legacy_save() {
+    // Settings saved before the inputs were nested, among the others.
+    #[derive(serde::Serialize)]
+    struct Legacy {
+        dark_mode: bool,
+        layers: (bool, bool),
+        frame: Option<String>,
+        origin_sma: Distance,
+        hohmann: bool,
+        revolutions: u32,
+        capture_radius: Distance,
+        finite_burn: bool,
+    }
+    let mut storage = Memory::default();
+    epi::set_value(&mut storage, epi::APP_KEY, &Legacy {
+        dark_mode: false,
+        layers: (true, false),
+        frame: Some("Synodic".to_string()),
+        origin_sma: Distance::from_astronomical_unit(0.72),
+        hohmann: false,
+        revolutions: 2,
+        capture_radius: Distance::from_kilometers(5_000.0),
+        finite_burn: true,
+    });
+    let state = AppState::migrate(&storage).unwrap();
+    assert_eq!((state.origin_sma, state.hohmann, state.revolutions), (Distance::from_astronomical_unit(0.72), false, 2));
+    assert_eq!(state.target_sma, AppState::default().target_sma);
+    let mission = MissionState::migrate(&storage).unwrap();
+    assert_eq!((mission.capture_radius, mission.finite_burn), (Distance::from_kilometers(5_000.0), true));
+
+    // Saves with the inputs nested are read as they are.
+    #[derive(serde::Serialize)]
+    struct Nested {
+        state: AppState,
+        mission: MissionState,
+    }
+    epi::set_value(&mut storage, epi::APP_KEY, &Nested {state: AppState::default(), mission: MissionState::default()});
+    assert!(AppState::migrate(&storage).is_none() && MissionState::migrate(&storage).is_none());
+}
+
+#[test]
+fn burn_inputs() {
+    let bodies = catalog::bodies();
+    let mut state = AppState {origin_sma: bodies[3].orbit.unwrap().1, origin_body: Some(3), ..AppState::default()};
+    let model = DerivedModel::new(&mut state, &bodies).unwrap();
+    let mission = MissionState::default();
+    let departure = DepartureModel::new(&mission, &bodies[3], &model.transfer, Duration::from_days(100.0));
+
+    // The burns don't depend on when the window opens nor on how the spacecraft is captured.
+    let later = DepartureModel::new(&MissionState {capture_radius: Distance::from_kilometers(10_000.0), ..mission}, &bodies[3], &model.transfer, Duration::from_days(200.0));
+    assert!(departure.burn_inputs == later.burn_inputs);
+    // They do on the engine and the orbit they depart from.
+    let weaker = DepartureModel::new(&MissionState {engine_acceleration: 0.5, ..mission}, &bodies[3], &model.transfer, Duration::from_days(100.0));
+    assert!(departure.burn_inputs != weaker.burn_inputs);
+    let eccentric = DepartureModel::new(&MissionState {eccentric_parking: true, ..mission}, &bodies[3], &model.transfer, Duration::from_days(100.0));
+    assert!(departure.burn_inputs != eccentric.burn_inputs);
+}
@@ -0,0 +1,246 @@
+// Frames of the interface drawn without a window, driven by the pointer and the keyboard and read back from the text they
+// show.
+
+use std::sync::Arc;
+
+use eframe::epi::{self, App};
+use egui::epaint::{ClippedShape, Shape};
+use egui::{CtxRef, Event, Key, Modifiers, PointerButton, Pos2, RawInput, Rect, Vec2};
+
+use app::Gui;
+
+// Fewer points than any orbit on the diagram, and more than any widget is drawn with.
+const ORBIT_POINTS: usize = 100;
+
+// Fields of the sliders are drawn on the right of the panels, past their rail.
+const FIELD_LEFT: f32 = 1200.0;
+
+// Frames drawn in a row on request of the previous one before the interface is taken as settled.
+const MAX_REPAINTS: usize = 10;
+
+// Pointer moves a drag is made of, as a hand would go over the rail of a slider.
+const DRAG_STEPS: usize = 10;
+
+struct NoRepaint;
+
+impl epi::backend::RepaintSignal for NoRepaint {
+    fn request_repaint(&self) {}
+}
+
+struct Harness {
+    gui: Gui,
+    ctx: CtxRef,
+    frame: epi::Frame,
+    time: f64,
+    shapes: Vec<ClippedShape>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let frame = epi::Frame::new(epi::backend::FrameData {
+            info: epi::IntegrationInfo {
+                name: "harness",
+                web_info: None,
+                prefer_dark_mode: Some(true),
+                cpu_usage: None,
+                native_pixels_per_point: Some(1.0),
+            },
+            output: Default::default(),
+            repaint_signal: Arc::new(NoRepaint),
+        });
+        let mut harness = Self {gui: Gui::default(), ctx: CtxRef::default(), frame, time: 0.0, shapes: Vec::new()};
+        // Windows are laid out over their first frame, the tour shown on the first run only being clickable after it.
+        harness.run(Vec::new());
+        harness.run(Vec::new());
+        harness.click("Skip tour");
+        harness
+    }
+
+    // Draws a frame with the events, then the ones it asks for as the integration would, up to a point.
+    fn run(&mut self, events: Vec<Event>) {
+        let mut repaint = self.frame(events);
+        for _ in 0..MAX_REPAINTS {
+            if !repaint {
+                break;
+            }
+            repaint = self.frame(Vec::new());
+        }
+    }
+
+    fn frame(&mut self, events: Vec<Event>) -> bool {
+        self.time += 1.0 / 60.0;
+        self.ctx.begin_frame(RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(1500.0, 1000.0))),
+            pixels_per_point: Some(1.0),
+            time: Some(self.time),
+            events,
+            ..RawInput::default()
+        });
+        self.gui.update(&self.ctx, &self.frame);
+        let (output, shapes) = self.ctx.end_frame();
+        self.shapes = shapes;
+        output.needs_repaint
+    }
+
+    // Moves the pointer over the first text shown as it is and clicks it.
+    fn click(&mut self, text: &str) {
+        let pos = self.rect(text).center();
+        self.click_at(pos);
+    }
+
+    fn click_at(&mut self, pos: Pos2) {
+        self.run(vec![Event::PointerMoved(pos)]);
+        self.run(vec![Event::PointerButton {pos, button: PointerButton::Primary, pressed: true, modifiers: Modifiers::default()}]);
+        self.run(vec![Event::PointerButton {pos, button: PointerButton::Primary, pressed: false, modifiers: Modifiers::default()}]);
+        self.run(vec![Event::PointerGone]);
+    }
+
+    // Presses the pointer down at a point, moves it to another in a few steps and releases it there.
+    fn drag(&mut self, from: Pos2, to: Pos2) {
+        self.run(vec![Event::PointerMoved(from)]);
+        self.run(vec![Event::PointerButton {pos: from, button: PointerButton::Primary, pressed: true, modifiers: Modifiers::default()}]);
+        for step in 1..=DRAG_STEPS {
+            self.run(vec![Event::PointerMoved(from + (to - from) * step as f32 / DRAG_STEPS as f32)]);
+        }
+        self.run(vec![Event::PointerButton {pos: to, button: PointerButton::Primary, pressed: false, modifiers: Modifiers::default()}]);
+        self.run(vec![Event::PointerGone]);
+    }
+
+    // Replaces the text of the field at a point, as typed over it and entered.
+    fn type_at(&mut self, pos: Pos2, text: &str) {
+        self.click_at(pos);
+        let command = Modifiers {ctrl: true, command: true, ..Modifiers::default()};
+        self.run(vec![
+            Event::Key {key: Key::A, pressed: true, modifiers: command},
+            Event::Key {key: Key::A, pressed: false, modifiers: command},
+        ]);
+        self.run(vec![Event::Text(text.to_string())]);
+        self.run(vec![
+            Event::Key {key: Key::Enter, pressed: true, modifiers: Modifiers::default()},
+            Event::Key {key: Key::Enter, pressed: false, modifiers: Modifiers::default()},
+        ]);
+    }
+
+    fn rect(&self, text: &str) -> Rect {
+        match self.texts().into_iter().find(|(shown, _)| shown == text) {
+            Some((_, rect)) => rect,
+            None => panic!("{:?} is not shown", text),
+        }
+    }
+
+    // Text of the field of a slider, the first one shown on the right of the panels below a label.
+    fn field_below(&self, label: &str) -> (String, Rect) {
+        let above = self.rect(label);
+        let mut texts: Vec<(String, Rect)> = self.texts().into_iter().filter(|(_, rect)| rect.top() > above.bottom() && rect.left() > FIELD_LEFT).collect();
+        texts.sort_by(|(_, a), (_, b)| a.top().partial_cmp(&b.top()).unwrap());
+        texts.into_iter().next().unwrap_or_else(|| panic!("No field is shown below {:?}", label))
+    }
+
+    // Text shown where another was, as the field of a slider once it was dragged.
+    fn text_at(&self, rect: Rect) -> String {
+        match self.texts().into_iter().find(|(_, shown)| shown.contains(rect.left_center() + Vec2::new(1.0, 0.0))) {
+            Some((text, _)) => text,
+            None => panic!("Nothing is shown at {:?}", rect),
+        }
+    }
+
+    fn texts(&self) -> Vec<(String, Rect)> {
+        fn collect(shape: &Shape, texts: &mut Vec<(String, Rect)>) {
+            match shape {
+                Shape::Vec(shapes) => shapes.iter().for_each(|shape| collect(shape, texts)),
+                Shape::Text(text) => texts.push((text.galley.job.text.clone(), Rect::from_min_size(text.pos, text.galley.size()))),
+                _ => (),
+            }
+        }
+        let mut texts = Vec::new();
+        for ClippedShape(_, shape) in &self.shapes {
+            collect(shape, &mut texts);
+        }
+        texts
+    }
+
+    fn shows(&self, text: &str) -> bool {
+        self.texts().iter().any(|(shown, _)| shown == text)
+    }
+
+    // Orbits drawn on the diagram, as the only lines made of that many points.
+    fn curves(&self) -> usize {
+        fn count(shape: &Shape) -> usize {
+            match shape {
+                Shape::Vec(shapes) => shapes.iter().map(count).sum(),
+                Shape::Path(path) => (path.points.len() > ORBIT_POINTS) as usize,
+                _ => 0,
+            }
+        }
+        self.shapes.iter().map(|ClippedShape(_, shape)| count(shape)).sum()
+    }
+}
+
+#[test]
+fn tour() {
+    let harness = Harness::new();
+    assert!(!harness.shows("Skip tour"));
+    assert!(harness.shows("Show:"));
+}
+
+// Delta-v in km/s shown by the field of the slider.
+fn kmps(text: &str) -> f64 {
+    text.trim_end_matches(" km/s").parse().unwrap_or_else(|_| panic!("{:?} is not in km/s", text))
+}
+
+#[test]
+fn hohmann_mode() {
+    let mut harness = Harness::new();
+    assert!(harness.shows("2.9446 km/s"));
+    assert!(harness.shows("The transfer will take 8.5 months, departing with a phase angle of 44.3 °."));
+
+    // Dragging the slider once Hohmann mode is off, the Hohmann transfer being drawn behind the faster one.
+    harness.click("Hohmann");
+    let field = harness.rect("2.9446 km/s");
+    let rail = |offset: f32| Pos2::new(field.left() - offset, field.center().y);
+    harness.drag(rail(600.0), rail(400.0));
+    let faster = kmps(&harness.text_at(field));
+    assert!(faster > 2.9446, "{} km/s", faster);
+    assert!(harness.shows("  8.5 months"));
+    assert!(!harness.shows("The transfer will take 8.5 months, departing with a phase angle of 44.3 °."));
+    // Further right along the rail, faster still.
+    harness.drag(rail(400.0), rail(200.0));
+    assert!(kmps(&harness.text_at(field)) > faster);
+
+    harness.click("Hohmann");
+    assert!(harness.shows("2.9446 km/s"));
+    assert!(!harness.shows("  8.5 months"));
+}
+
+#[test]
+fn velocity_units() {
+    // Switching back to Hohmann mode around a lighter parent shows the delta-v in the units fitting it.
+    let mut harness = Harness::new();
+    harness.click("Sun");
+    harness.click("Earth");
+    let (_, origin) = harness.field_below("Semi-major axis of the origin body:");
+    harness.type_at(origin.center(), "7000 km");
+    let (_, target) = harness.field_below("Semi-major axis of the target body:");
+    harness.type_at(target.center(), "8000 km");
+    harness.click("Hohmann");
+    let (_, field) = harness.field_below("Optimize");
+    harness.drag(Pos2::new(field.left() - 600.0, field.center().y), Pos2::new(field.left() - 200.0, field.center().y));
+    assert!(!harness.shows("247.477 m/s"));
+    harness.click("Hohmann");
+    assert!(harness.shows("247.477 m/s"));
+}
+
+#[test]
+fn legend() {
+    let mut harness = Harness::new();
+    assert_eq!(harness.curves(), 3);
+    harness.click("Origin orbit");
+    harness.click("Target orbit");
+    assert_eq!(harness.curves(), 1);
+    assert!(harness.shows("44.3 °"));
+    harness.click("Protractor");
+    assert!(!harness.shows("44.3 °"));
+
+    harness.click("Origin orbit");
+    assert_eq!(harness.curves(), 2);
+}
@@ -0,0 +1,32 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use planetary_transfer::{kepler, Distance, Mass, Parent, Planet, Transfer, Velocity};
+
+fn time_of_flight(c: &mut Criterion) {
+    let parent = Parent::new(Mass::from_solar(1.0));
+    let origin = Planet::new(Distance::from_astronomical_unit(1.0), parent);
+    let target = Planet::new(Distance::from_astronomical_unit(1.52366), parent);
+    let mut transfer = Transfer::new(origin, target);
+    transfer.set_delta_v(Velocity::from_kilometers_per_second(3.5));
+
+    c.bench_function("time_of_flight", |b| b.iter(|| black_box(&transfer).time_of_flight()));
+}
+
+fn kepler(c: &mut Criterion) {
+    let n = 4096;
+    let mean_anomalies: Vec<f64> = (0..n).map(|i| i as f64 / n as f64 * std::f64::consts::TAU).collect();
+    let mut output = vec![0.0; n];
+
+    c.bench_function("kepler_single", |b| b.iter(|| {
+        for (anomaly, &mean_anomaly) in output.iter_mut().zip(&mean_anomalies) {
+            *anomaly = kepler::solve(black_box(mean_anomaly), black_box(0.6));
+        }
+    }));
+
+    c.bench_function("kepler_batch", |b| b.iter(|| {
+        kepler::solve_batch(black_box(&mean_anomalies), black_box(0.6), &mut output)
+    }));
+}
+
+criterion_group!(benches, time_of_flight, kepler);
+criterion_main!(benches);
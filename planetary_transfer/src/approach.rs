@@ -0,0 +1,78 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{Transfer, Vec2, Velocity};
+
+// Hyperbolic excess velocity of the spacecraft at arrival, relative to the target. Its direction sets the side of the
+// target the spacecraft comes from, and so the orientation of the capture orbit or of an aerocapture pass.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Approach {
+    // Component along the velocity of the target, in m/s.
+    pub along_track: f64,
+    // Component away from the parent, in m/s.
+    pub radial: f64,
+    // Excess velocity in the frame of the parent, in m/s, on the axes of the transfer diagram.
+    pub inertial: Vec2,
+}
+
+impl Approach {
+    pub fn v_infinity(&self) -> Velocity {
+        Velocity::from_meters_per_second(self.along_track.hypot(self.radial))
+    }
+
+    // Angle of the excess velocity from the velocity of the target, positive away from the parent, in ]-PI, PI].
+    pub fn angle(&self) -> f64 {
+        self.radial.atan2(self.along_track)
+    }
+
+    // Whether the spacecraft comes from ahead of the target, the target catching up with it, None when it comes
+    // straight from the side of the parent or from beyond.
+    pub fn leading_side(&self) -> Option<bool> {
+        self.side(self.along_track).map(|behind| !behind)
+    }
+
+    // Whether the spacecraft comes from the side of the parent, moving away from it faster than the target. None when
+    // it arrives at an apsis of the transfer, tangent to the orbit of the target.
+    pub fn day_side(&self) -> Option<bool> {
+        self.side(self.radial)
+    }
+
+    // Sign of a component, a negligible one leaving the side undetermined.
+    fn side(&self, component: f64) -> Option<bool> {
+        (component.abs() > 1E-6 * self.v_infinity().mps()).then_some(component > 0.0)
+    }
+
+    pub fn description(&self) -> String {
+        let sides: Vec<&str> = [
+            self.leading_side().map(|leading| if leading {"leading"} else {"trailing"}),
+            self.day_side().map(|day| if day {"day"} else {"night"}),
+        ].into_iter().flatten().collect();
+        if sides.is_empty() {
+            return String::from("The spacecraft meets the target at rest relative to it.");
+        }
+        // Rounded to whole degrees, without the sign of a negative zero.
+        let angle = self.angle().to_degrees().round() + 0.0;
+        format!(
+            "The spacecraft approaches the {} side of the target, its excess velocity {}° from the velocity of the target.",
+            sides.join(", "),
+            angle,
+        )
+    }
+}
+
+impl Transfer {
+    pub fn arrival_approach(&self) -> Approach {
+        let tangential = self.origin.sma().m() * self.launch_velocity().mps() / self.target.sma().m();
+        // Outbound transfers depart from their periapsis and inbound ones from their apoapsis, the sign of the
+        // eccentricity telling them apart, and arrive before reaching the other apsis.
+        let radial = (self.arrival_velocity().mps().powi(2) - tangential.powi(2)).max(0.0).sqrt() * self.eccentricity().signum();
+        let along_track = tangential - self.target_velocity().mps();
+
+        let outward = Vec2::from_polar(1.0, self.target_true_anomaly_arrival());
+        let prograde = outward.rotated(FRAC_PI_2);
+        Approach {
+            along_track,
+            radial,
+            inertial: prograde * along_track + outward * radial,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::{env, fs, process};
+
+use planetary_transfer::ksp::KspSave;
+
+// Usage: ksp_phase <save file> <vessel> <target body or vessel>
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        eprintln!("Usage: {} <save file> <vessel> <target body or vessel>", args[0]);
+        process::exit(2);
+    }
+
+    if let Err(error) = run(&args[1], &args[2], &args[3]) {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(path: &str, vessel: &str, target: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let save = KspSave::parse(&fs::read_to_string(path)?)?;
+
+    let vessel = save.vessel(vessel).ok_or(format!("no vessel named {}", vessel))?;
+    let target = save.body(target).and_then(|body| body.orbit)
+        .or_else(|| save.vessel(target).map(|vessel| vessel.orbit))
+        .ok_or(format!("no body or vessel named {}", target))?;
+
+    let phase = save.phase(vessel, &target)?;
+    let transfer = &phase.transfer;
+
+    println!("Origin semi-major axis: {} km", transfer.origin().sma().km());
+    println!("Target semi-major axis: {} km", transfer.target().sma().km());
+    println!("Hohmann delta-v: {} m/s", transfer.delta_v_hohmann().mps());
+    println!("Transfer time: {}", transfer.time_of_flight().formatted());
+    println!("Ideal phase angle: {:.2} °", phase.ideal.to_degrees());
+    println!("Current phase angle: {:.2} °", phase.current.to_degrees());
+    println!("Phase angle error: {:.2} °", phase.error.to_degrees());
+    println!("Next window in: {}", transfer.wait_time_until_window(phase.current).formatted());
+
+    Ok(())
+}
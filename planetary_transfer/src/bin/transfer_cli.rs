@@ -0,0 +1,113 @@
+use std::{env, fs, process};
+
+use planetary_transfer::catalog::{self, Body};
+use planetary_transfer::{Duration, Planet, Porkchop, Transfer};
+
+const USAGE: &str = "Usage: transfer_cli porkchop <origin> <target> [options]
+
+Writes the delta-v of the transfers between two bodies of the catalog orbiting the same primary as CSV, over a grid
+of departure times and times of flight, for plotting with external tools.
+
+Options:
+    --departure <first> <last>  Departure times in days from now, over one synodic period by default
+    --tof <shortest> <longest>  Times of flight in days, around the one of the Hohmann transfer by default
+    --steps <departures> <tofs> Points along each axis, 100 by default
+    --phase <angle>             Current angle in degrees the target leads the origin by, the one of the Hohmann
+                                transfer by default
+    --grid                      Writes the total delta-v as a matrix instead of one line per point
+    --output <file>             Writes to a file instead of the standard output";
+
+const DEFAULT_STEPS: usize = 100;
+
+// Usage: transfer_cli porkchop <origin> <target> [options]
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("porkchop") => porkchop(&args[1..]),
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn porkchop(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (origin, target) = match args {
+        [origin, target, ..] if !origin.starts_with("--") && !target.starts_with("--") => (origin, target),
+        _ => return Err(format!("expected an origin and a target\n\n{}", USAGE).into()),
+    };
+    let bodies = catalog::bodies();
+    let (origin, target) = planets(&bodies, origin, target)?;
+    let transfer = Transfer::try_new(origin, target)?;
+
+    let hohmann = transfer.time_of_flight();
+    let mut departures = (Duration::from_days(0.0), transfer.synodic_period());
+    let mut times_of_flight = (hohmann * 0.5, hohmann * 1.5);
+    let mut steps = (DEFAULT_STEPS, DEFAULT_STEPS);
+    let mut phase_angle = transfer.target_true_anomaly_departure();
+    let mut grid = false;
+    let mut output = None;
+
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        let mut value = |name: &str| options.next().ok_or(format!("missing value for {}", name));
+        match option.as_str() {
+            "--departure" => departures = (Duration::try_from_days(value(option)?.parse()?)?, Duration::try_from_days(value(option)?.parse()?)?),
+            "--tof" => times_of_flight = (Duration::try_from_days(value(option)?.parse()?)?, Duration::try_from_days(value(option)?.parse()?)?),
+            "--steps" => steps = (value(option)?.parse()?, value(option)?.parse()?),
+            "--phase" => phase_angle = value(option)?.parse::<f64>()?.to_radians(),
+            "--grid" => grid = true,
+            "--output" => output = Some(value(option)?.clone()),
+            _ => return Err(format!("unknown option {}\n\n{}", option, USAGE).into()),
+        }
+    }
+    if times_of_flight.0.s() <= 0.0 {
+        return Err("times of flight must be positive".into());
+    }
+
+    let porkchop = Porkchop::new(&origin, &target, phase_angle, departures, times_of_flight, steps);
+    let csv = if grid {porkchop.to_grid_csv()} else {porkchop.to_csv()};
+    match output {
+        Some(path) => fs::write(path, csv)?,
+        None => print!("{}", csv),
+    }
+
+    if let Some((departure, time_of_flight, point)) = porkchop.cheapest() {
+        eprintln!(
+            "Cheapest: {} departing in {} and arriving {} later.",
+            point.total().formatted(),
+            departure.formatted(),
+            time_of_flight.formatted(),
+        );
+    }
+    Ok(())
+}
+
+// Both bodies on their orbits around the primary they share.
+fn planets(bodies: &[Body], origin: &str, target: &str) -> Result<(Planet, Planet), String> {
+    let find = |name: &str| {
+        bodies.iter()
+            .find(|body| body.name.eq_ignore_ascii_case(name))
+            .ok_or(format!("no body named {} in the catalog", name))
+    };
+    let (origin, target) = (find(origin)?, find(target)?);
+    let primary = match (origin.primary(bodies), target.primary(bodies)) {
+        (Some(primary), Some(other)) if primary == other => &bodies[primary],
+        _ => return Err(format!("{} and {} do not orbit the same body", origin.name, target.name)),
+    };
+
+    let planet = |body: &Body| {
+        let (_, sma) = body.orbit.expect("bodies with a primary have an orbit");
+        Planet::new(sma, primary.parent()).with_mass(body.mass).with_retrograde(body.retrograde)
+    };
+    Ok((planet(origin), planet(target)))
+}
@@ -0,0 +1,84 @@
+use std::{env, process};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use planetary_transfer::{Distance, Mass, Parent, Planet, QuantityError, Transfer, TransferSummary, Velocity};
+
+// Usage: transfer_server [address]
+// Serves the summary of transfers as JSON, for web tools and bots to query the calculator:
+// GET /transfer?origin_sma=<m>&target_sma=<m>&mass=<kg>[&delta_v=<m/s>][&phase_angle=<°>]
+// The delta-v defaults to the Hohmann transfer, and the current phase angle gives the wait until the next window.
+fn main() {
+    let address = env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8000".to_string());
+    let server = Server::http(&address).unwrap_or_else(|error| {
+        eprintln!("Error: cannot listen on {}: {}", address, error);
+        process::exit(1);
+    });
+    println!("Listening on http://{}", address);
+
+    for request in server.incoming_requests() {
+        let (status, body) = respond(&request);
+        let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(body).with_status_code(status).with_header(header);
+        if let Err(error) = request.respond(response) {
+            eprintln!("Error: {}", error);
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct Query {
+    origin_sma: f64,
+    target_sma: f64,
+    mass: f64,
+    delta_v: Option<f64>,
+    phase_angle: Option<f64>,
+}
+
+fn respond(request: &Request) -> (u16, String) {
+    let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    if *request.method() != Method::Get || path != "/transfer" {
+        return (404, error("not found, expected GET /transfer"));
+    }
+
+    let query: Query = match serde_urlencoded::from_str(query) {
+        Ok(query) => query,
+        Err(err) => return (400, error(&err.to_string())),
+    };
+    match summary(&query) {
+        Ok(summary) => (200, serde_json::to_string(&summary).unwrap()),
+        Err(err) => (400, error(&err)),
+    }
+}
+
+fn summary(query: &Query) -> Result<TransferSummary, String> {
+    let parent = Parent::new(Mass::try_from_kilograms(query.mass).map_err(field("mass"))?);
+    let origin = Planet::new(Distance::try_from_meters(query.origin_sma).map_err(field("origin_sma"))?, parent);
+    let target = Planet::new(Distance::try_from_meters(query.target_sma).map_err(field("target_sma"))?, parent);
+    let mut transfer = Transfer::try_new(origin, target).map_err(|err| err.to_string())?;
+
+    if let Some(delta_v) = query.delta_v {
+        let (min, max) = (transfer.min_velocity().mps(), transfer.max_velocity().mps());
+        if !(min.min(max)..=min.max(max)).contains(&delta_v) {
+            return Err(format!("delta_v: outside of the range of the transfer, from {} to {} m/s", min, max));
+        }
+        transfer.try_set_delta_v(Velocity::from_meters_per_second(delta_v)).map_err(field("delta_v"))?;
+    } else {
+        transfer.set_delta_v(transfer.delta_v_hohmann());
+    }
+
+    let summary = TransferSummary::new(&transfer);
+    Ok(match query.phase_angle {
+        Some(phase_angle) => summary.with_next_window(transfer.wait_time_until_window(phase_angle.to_radians())),
+        None => summary,
+    })
+}
+
+// Names the parameter an error comes from.
+fn field(name: &'static str) -> impl Fn(QuantityError) -> String {
+    move |err| format!("{}: {}", name, err)
+}
+
+fn error(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
@@ -0,0 +1,41 @@
+use crate::{Distance, Mass};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Pair of bodies orbiting each other, acting as a single parent from far enough away.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Binary {
+    pub primary: Mass,
+    pub secondary: Mass,
+    pub separation: Distance,
+    pub eccentricity: f64,
+}
+
+impl Binary {
+    pub fn new(primary: Mass, secondary: Mass, separation: Distance, eccentricity: f64) -> Self {
+        Self {
+            primary,
+            secondary,
+            separation,
+            eccentricity,
+        }
+    }
+
+    pub fn mass(&self) -> Mass {
+        self.primary + self.secondary
+    }
+
+    // Fraction of the total mass in the secondary.
+    pub fn mass_ratio(&self) -> f64 {
+        self.secondary.mu() / self.mass().mu()
+    }
+
+    // Smallest stable circumbinary orbit, from the fit of Holman & Wiegert (1999) to numerical integrations.
+    // Closer orbits are ejected or collide within a few thousand binary periods.
+    pub fn critical_sma(&self) -> Distance {
+        let (e, mu) = (self.eccentricity, self.mass_ratio());
+        self.separation * (
+            1.60 + 5.10 * e - 2.22 * e.powi(2) + 4.12 * mu - 4.27 * e * mu - 5.09 * mu.powi(2) + 4.61 * e.powi(2) * mu.powi(2)
+        )
+    }
+}
@@ -0,0 +1,107 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::maneuvers::{combined_burn, plane_change};
+use crate::solvers::{golden_section, Tolerance};
+use crate::{Distance, Duration, Transfer, Vec3, Velocity};
+
+// Margin in radians keeping the mid-course burn away from departure and arrival, where the plane through the burn
+// point and the arrival point is undefined for transfers of half a revolution.
+const ENDPOINT_MARGIN: f64 = 1E-3;
+const ANGLE_TOLERANCE: f64 = 1E-7;
+
+// Transfer between circular orbits of different planes, flown in the plane it departs into until a mid-course burn
+// rotates it about the radius vector into a plane reaching the target. The plane change is split between the departure
+// and the mid-course burn, with what remains between the planes of the spacecraft and of the target left to the
+// arrival. The shape of the transfer is the one of the coplanar transfer, which holds for small inclinations.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BrokenPlane {
+    // Rotation of the plane of the transfer at departure in radians, combined with the departure burn.
+    pub departure_plane_change: f64,
+    pub departure: Velocity,
+    // Angle travelled from departure to the mid-course burn in radians, and time it takes.
+    pub mid_course_angle: f64,
+    pub mid_course_time: Duration,
+    pub mid_course_radius: Distance,
+    pub mid_course_plane_change: f64,
+    pub mid_course: Velocity,
+    // Angle between the planes of the spacecraft and of the target at arrival, in radians.
+    pub arrival_plane_change: f64,
+    // Velocity relative to the target at arrival.
+    pub arrival: Velocity,
+}
+
+impl BrokenPlane {
+    pub fn total(&self) -> Velocity {
+        self.departure + self.mid_course + self.arrival
+    }
+}
+
+impl Transfer {
+    // Broken-plane transfer to a target orbit inclined relative to the orbit of the origin by an angle in radians,
+    // minimising the total delta-v over the split of the plane change and the location of the mid-course burn. The
+    // target is met an argument of latitude past the line of nodes of both orbits, the cost of the plane change being
+    // the highest a quarter revolution from the nodes. None for retrograde transfers, which fly against both orbits,
+    // and when the transfer travels no angle around the parent.
+    pub fn broken_plane(&self, relative_inclination: f64, arrival_argument_of_latitude: f64) -> Option<BrokenPlane> {
+        let travelled = self.target_true_anomaly_arrival();
+        if self.retrograde() || travelled < 2.0 * ENDPOINT_MARGIN {
+            return None;
+        }
+
+        // Axes of the orbit of the origin, the origin at departure on the x axis and the transfer travelling towards y.
+        let (x, y, z) = (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        let node_longitude = travelled - arrival_argument_of_latitude;
+        let node = x * node_longitude.cos() + y * node_longitude.sin();
+        let ascending = z.cross(node) * relative_inclination.cos() + z * relative_inclination.sin();
+        let target_normal = node.cross(ascending);
+        let arrival = node * arrival_argument_of_latitude.cos() + ascending * arrival_argument_of_latitude.sin();
+
+        let approach = self.arrival_approach();
+        // Negative when the target orbits the other way, its velocity then pointing backwards.
+        let target_speed = self.target_velocity().mps();
+        let angular_momentum = self.origin.sma().m() * self.launch_velocity().mps();
+        let mean_anomaly = |true_anomaly: f64| self.mean_anomaly(self.eccentric_anomaly_cos(true_anomaly));
+        let time_scale = (self.sma().m().abs().powi(3) / self.parent.gravitational_parameter.m3ps2()).sqrt();
+
+        let broken_plane = |departure_plane_change: f64, mid_course_angle: f64| {
+            let normal = Vec3::new(0.0, -departure_plane_change.sin(), departure_plane_change.cos());
+            let burn_point = x * mid_course_angle.cos() + normal.cross(x) * mid_course_angle.sin();
+            // Plane through the mid-course burn point and the arrival point, the spacecraft still travelling forward.
+            let broken_normal = burn_point.cross(arrival).normalized().unwrap_or(normal);
+            let mid_course_radius = self.radius_at(mid_course_angle);
+            let mid_course_plane_change = normal.angle_to(broken_normal);
+
+            let spacecraft = arrival * approach.radial + broken_normal.cross(arrival) * (approach.along_track + target_speed);
+            let target = target_normal.cross(arrival) * target_speed;
+            BrokenPlane {
+                departure_plane_change,
+                departure: combined_burn(self.origin.orbital_velocity(), self.launch_velocity(), departure_plane_change),
+                mid_course_angle,
+                mid_course_time: Duration::from_seconds((mean_anomaly(mid_course_angle) - mean_anomaly(0.0)) * time_scale),
+                mid_course_radius,
+                mid_course_plane_change,
+                mid_course: plane_change(Velocity::from_meters_per_second(angular_momentum / mid_course_radius.m()), mid_course_plane_change),
+                arrival_plane_change: broken_normal.angle_to(target_normal),
+                arrival: Velocity::from_meters_per_second((spacecraft - target).norm()),
+            }
+        };
+
+        let tolerance = Tolerance::new(ANGLE_TOLERANCE, 100);
+        let best_mid_course = |departure_plane_change: f64| {
+            golden_section(
+                |angle| broken_plane(departure_plane_change, angle).total().mps(),
+                ENDPOINT_MARGIN,
+                travelled - ENDPOINT_MARGIN,
+                tolerance,
+            ).x
+        };
+        let limit = relative_inclination.abs().min(FRAC_PI_2);
+        let split = golden_section(
+            |departure_plane_change| broken_plane(departure_plane_change, best_mid_course(departure_plane_change)).total().mps(),
+            -limit,
+            limit,
+            tolerance,
+        ).x;
+        Some(broken_plane(split, best_mid_course(split)))
+    }
+}
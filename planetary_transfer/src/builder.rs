@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::Deref;
+
+use crate::{CaptureAdvisory, Distance, Duration, Planet, QuantityError, Transfer, TransferError, Velocity};
+
+// Burn leaving the orbit of the origin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Departure {
+    Hohmann,
+    // Total delta-v of the burn, negative when burning retrograde.
+    DeltaV(Velocity),
+}
+
+// What the spacecraft does on reaching the orbit of the target.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Arrival {
+    // Passes by the target without burning.
+    Flyby,
+    // Matches the orbital velocity of the target, staying outside of its gravity well.
+    Rendezvous,
+    // Burns at the periapsis of the approach into a circular orbit of given radius around the target,
+    // only possible when the target has a mass.
+    Capture(Distance),
+}
+
+impl Arrival {
+    // Delta-v of the arrival burn given the excess velocity on reaching the target, none when capturing around a target
+    // without mass.
+    pub fn delta_v(&self, target: &Planet, v_infinity: Velocity) -> Option<Velocity> {
+        match *self {
+            Arrival::Flyby => Some(Velocity::from_meters_per_second(0.0)),
+            Arrival::Rendezvous => Some(v_infinity),
+            Arrival::Capture(radius) => CaptureAdvisory::new(target, v_infinity, radius).map(|capture| capture.direct_insertion),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BuildError {
+    DifferentParents,
+    Quantity(QuantityError),
+    // The origin and target share an orbit.
+    DegenerateTransfer,
+    // Delta-v of the departure burn outside of the range reaching the target.
+    DeltaVOutOfRange {
+        min: Velocity,
+        max: Velocity,
+    },
+    MasslessTarget,
+    UnknownBody(usize),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::DifferentParents => write!(f, "{}", TransferError::DifferentParents),
+            BuildError::Quantity(error) => write!(f, "{}", error),
+            BuildError::DegenerateTransfer => write!(f, "{}", TransferError::DegenerateTransfer),
+            BuildError::DeltaVOutOfRange { min, max } => write!(f, "the delta-v of the departure burn must be between {} and {}", min.formatted(), max.formatted()),
+            BuildError::MasslessTarget => write!(f, "capturing around the target requires its mass"),
+            BuildError::UnknownBody(index) => write!(f, "{}", TransferError::UnknownBody(*index)),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+impl From<QuantityError> for BuildError {
+    fn from(error: QuantityError) -> Self {
+        BuildError::Quantity(error)
+    }
+}
+
+impl From<TransferError> for BuildError {
+    fn from(error: TransferError) -> Self {
+        match error {
+            TransferError::DifferentParents => BuildError::DifferentParents,
+            TransferError::Quantity(error) => BuildError::Quantity(error),
+            TransferError::DegenerateTransfer => BuildError::DegenerateTransfer,
+            TransferError::UnknownBody(index) => BuildError::UnknownBody(index),
+        }
+    }
+}
+
+// Fluent configuration of a transfer, checked all at once by `build`.
+#[derive(Copy, Clone, Debug)]
+pub struct TransferBuilder {
+    origin: Planet,
+    target: Planet,
+    departure: Departure,
+    arrival: Arrival,
+    revolutions: u32,
+    barycentric: bool,
+    // Phase angle between the origin and target at the epoch, in radians.
+    epoch_phase_angle: Option<f64>,
+}
+
+impl TransferBuilder {
+    pub fn new(origin: Planet, target: Planet) -> Self {
+        Self {
+            origin,
+            target,
+            departure: Departure::Hohmann,
+            arrival: Arrival::Flyby,
+            revolutions: 0,
+            barycentric: false,
+            epoch_phase_angle: None,
+        }
+    }
+
+    pub fn with_departure(mut self, departure: Departure) -> Self {
+        self.departure = departure;
+        self
+    }
+
+    pub fn with_arrival(mut self, arrival: Arrival) -> Self {
+        self.arrival = arrival;
+        self
+    }
+
+    pub fn with_revolutions(mut self, revolutions: u32) -> Self {
+        self.revolutions = revolutions;
+        self
+    }
+
+    // See `Transfer::set_barycentric`, the planets needing a mass for it to have any effect.
+    pub fn with_barycentric(mut self, barycentric: bool) -> Self {
+        self.barycentric = barycentric;
+        self
+    }
+
+    // Departs at the first launch window after the epoch, given the phase angle at the epoch in radians.
+    pub fn with_epoch(mut self, phase_angle: f64) -> Self {
+        self.epoch_phase_angle = Some(phase_angle);
+        self
+    }
+
+    pub fn build(self) -> Result<SolvedTransfer, BuildError> {
+        if self.origin.orbit().parent.gravitational_parameter() != self.target.orbit().parent.gravitational_parameter() {
+            return Err(BuildError::DifferentParents);
+        }
+        let mut transfer = Transfer::try_new(self.origin, self.target)?;
+        transfer.set_barycentric(self.barycentric);
+
+        let delta_v = match self.departure {
+            Departure::Hohmann => transfer.delta_v_hohmann(),
+            Departure::DeltaV(delta_v) => delta_v,
+        };
+        let (min, max) = (transfer.min_velocity(), transfer.max_velocity());
+        if delta_v.mps() < min.mps().min(max.mps()) || delta_v.mps() > min.mps().max(max.mps()) {
+            return Err(BuildError::DeltaVOutOfRange { min, max });
+        }
+        transfer.try_set_delta_v(delta_v)?;
+        transfer.set_revolutions(self.revolutions);
+
+        if let Arrival::Capture(radius) = self.arrival {
+            Distance::try_from_meters(radius.m())?;
+        }
+        let arrival_delta_v = self.arrival
+            .delta_v(&self.target, transfer.arrival_v_infinity())
+            .ok_or(BuildError::MasslessTarget)?;
+
+        Ok(SolvedTransfer {
+            transfer,
+            arrival: self.arrival,
+            departure_delta_v: Velocity::from_meters_per_second(delta_v.mps().abs()),
+            arrival_delta_v,
+            departure_time: self.epoch_phase_angle.map(|phase_angle| transfer.wait_time_until_window(phase_angle)),
+            time_of_flight: transfer.time_of_flight(),
+        })
+    }
+}
+
+// Transfer whose configuration was validated, its results being computed once.
+// It derefs to the underlying `Transfer` for everything else, without allowing it to be changed.
+#[derive(Copy, Clone)]
+pub struct SolvedTransfer {
+    transfer: Transfer,
+    arrival: Arrival,
+    departure_delta_v: Velocity,
+    arrival_delta_v: Velocity,
+    departure_time: Option<Duration>,
+    time_of_flight: Duration,
+}
+
+impl SolvedTransfer {
+    pub fn arrival(&self) -> Arrival {
+        self.arrival
+    }
+
+    pub fn departure_delta_v(&self) -> Velocity {
+        self.departure_delta_v
+    }
+
+    pub fn arrival_delta_v(&self) -> Velocity {
+        self.arrival_delta_v
+    }
+
+    pub fn total_delta_v(&self) -> Velocity {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+
+    // Time since the epoch, only known when one was given.
+    pub fn departure_time(&self) -> Option<Duration> {
+        self.departure_time
+    }
+
+    pub fn arrival_time(&self) -> Option<Duration> {
+        self.departure_time.map(|departure_time| departure_time + self.time_of_flight)
+    }
+
+    pub fn time_of_flight(&self) -> Duration {
+        self.time_of_flight
+    }
+}
+
+impl Deref for SolvedTransfer {
+    type Target = Transfer;
+
+    fn deref(&self) -> &Transfer {
+        &self.transfer
+    }
+}
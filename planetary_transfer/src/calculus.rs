@@ -1,7 +1,34 @@
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+pub const SIGNIFICANT_FIGURES: usize = 3;
+
 pub fn round_to(value: f64, decimal: usize) -> f64 {
     (value * (10 as f64).powi(decimal as i32)).round() / (10 as f64).powi(decimal as i32)
 }
 
+// Formats a value rounded to a number of significant figures, trailing zeros after the decimal point being dropped.
+#[cfg(feature = "std")]
+pub fn significant(value: f64, figures: usize) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let decimals = |value: f64| figures as i32 - 1 - value.abs().log10().floor() as i32;
+    let factor = 10f64.powi(decimals(value));
+    let rounded = (value * factor).round() / factor;
+
+    // Rounding up may add a digit, as from 9.996 to 10.0.
+    let text = format!("{:.*}", decimals(rounded).max(0) as usize, rounded);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
 pub trait Calculus {
     type Output;
 
@@ -40,7 +67,49 @@ macro_rules! calculus {
                 Self::new(self.base_quantity() / rhs)
             }
         }
+        // Ratio of two quantities of the same kind.
+        impl Div for $t {
+            type Output = f64;
+
+            fn div(self, rhs: $t) -> f64 {
+                self.base_quantity() / rhs.base_quantity()
+            }
+        }
+    )*)
+}
+
+pub(crate) use calculus;
+
+macro_rules! comparison {
+    ($($t:ty),*) => ($(
+        impl PartialEq for $t {
+            fn eq(&self, other: &$t) -> bool {
+                self.base_quantity() == other.base_quantity()
+            }
+        }
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                self.base_quantity().partial_cmp(&other.base_quantity())
+            }
+        }
+        impl $t {
+            pub fn min(self, other: $t) -> $t {
+                Self::new(self.base_quantity().min(other.base_quantity()))
+            }
+
+            pub fn max(self, other: $t) -> $t {
+                Self::new(self.base_quantity().max(other.base_quantity()))
+            }
+
+            pub fn clamp(self, min: $t, max: $t) -> $t {
+                Self::new(self.base_quantity().clamp(min.base_quantity(), max.base_quantity()))
+            }
+
+            pub fn abs(self) -> $t {
+                Self::new(self.base_quantity().abs())
+            }
+        }
     )*)
 }
 
-pub(crate) use calculus;
\ No newline at end of file
+pub(crate) use comparison;
\ No newline at end of file
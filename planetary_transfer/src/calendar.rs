@@ -0,0 +1,116 @@
+use std::fmt::{self, Display};
+
+use crate::{Duration, SECONDS_DAY, SECONDS_YEAR, SECONDS_SOL, SECONDS_KERBIN_DAY, SECONDS_KERBIN_YEAR, SECONDS_HOUR, SECONDS_MINUTE};
+
+// Days and years durations and dates can be expressed in, for transfers thought of in the time of another body than Earth.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Calendar {
+    // Length of the day and of the year in seconds, calendars without years counting in days.
+    pub day: f64,
+    pub year: Option<f64>,
+    pub day_name: &'static str,
+    pub year_name: &'static str,
+}
+
+impl Calendar {
+    // Days, months and years of Earth, the default formatting of durations.
+    pub const EARTH: Self = Self {
+        day: SECONDS_DAY,
+        year: Some(SECONDS_YEAR),
+        day_name: "days",
+        year_name: "years",
+    };
+
+    // Martian solar days.
+    pub const SOLS: Self = Self {
+        day: SECONDS_SOL,
+        year: None,
+        day_name: "sols",
+        year_name: "",
+    };
+
+    // Six-hour days and 426-day years of Kerbin, as counted by Kerbal Space Program.
+    pub const KERBIN: Self = Self {
+        day: SECONDS_KERBIN_DAY,
+        year: Some(SECONDS_KERBIN_YEAR),
+        day_name: "Kerbin days",
+        year_name: "Kerbin years",
+    };
+
+    // Days and years of arbitrary lengths.
+    pub fn custom(day: Duration, year: Duration) -> Self {
+        Self {
+            day: day.s(),
+            year: Some(year.s()),
+            day_name: "days",
+            year_name: "years",
+        }
+    }
+
+    // Rotations of a body relative to the stars, given its sidereal rotation period.
+    pub fn sidereal(rotation_period: Duration) -> Self {
+        Self {
+            day: rotation_period.s().abs(),
+            year: None,
+            day_name: "sidereal days",
+            year_name: "",
+        }
+    }
+
+    // Solar days and years of a body, given its sidereal rotation period, negative for retrograde rotation, and the
+    // period of its orbit.
+    pub fn local(rotation_period: Duration, orbital_period: Duration) -> Self {
+        Self {
+            day: (1.0 / (1.0 / rotation_period.s() - 1.0 / orbital_period.s())).abs(),
+            year: Some(orbital_period.s()),
+            day_name: "local days",
+            year_name: "local years",
+        }
+    }
+
+    // Date at a time since the epoch of the calendar, times before it being clamped to it.
+    pub fn date(&self, time: Duration) -> Date {
+        let mut seconds = time.s().max(0.0);
+        let year = self.year.map(|year| {
+            let years = (seconds / year).floor();
+            seconds -= years * year;
+            years as u64 + 1
+        });
+        let days = (seconds / self.day).floor();
+        seconds -= days * self.day;
+        Date {
+            year,
+            day: days as u64 + 1,
+            hour: (seconds / SECONDS_HOUR).floor() as u64,
+            minute: (seconds % SECONDS_HOUR / SECONDS_MINUTE).floor() as u64,
+            second: (seconds % SECONDS_MINUTE).floor() as u64,
+        }
+    }
+
+    // Time since the epoch at the start of a day, counted from one, the year being ignored by calendars without years.
+    pub fn time(&self, year: u64, day: u64) -> Duration {
+        let years = self.year.map_or(0.0, |length| year.saturating_sub(1) as f64 * length);
+        Duration::from_seconds(years + day.saturating_sub(1) as f64 * self.day)
+    }
+}
+
+// Date of a calendar, years and days counting from one as in Kerbal Space Program.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Date {
+    // None for calendars without years.
+    pub year: Option<u64>,
+    pub day: u64,
+    pub hour: u64,
+    pub minute: u64,
+    pub second: u64,
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "Year {}, day {}", year, self.day)?,
+            None => write!(f, "Day {}", self.day)?,
+        }
+        write!(f, ", {:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{Planet, Distance, Velocity};
+
+// Rough advisory on whether a low-energy ballistic capture is worth considering at the target.
+// A capture is deemed plausible when the arrival v-infinity does not exceed the circular velocity
+// of the target at the edge of its sphere of influence, where solar perturbations are strong enough
+// to bend a slow approach into a weakly bound orbit.
+#[derive(Copy, Clone, Debug)]
+pub struct CaptureAdvisory {
+    pub plausible: bool,
+    pub direct_insertion: Velocity,
+    pub ballistic_insertion: Velocity,
+    pub savings: Velocity,
+}
+
+impl CaptureAdvisory {
+    pub fn new(target: &Planet, v_infinity: Velocity, capture_radius: Distance) -> Option<Self> {
+        let mu = target.mass()?.mu();
+        let soi = target.sphere_of_influence()?;
+
+        let circular = (mu / capture_radius.m()).sqrt();
+        let direct = (v_infinity.mps().powi(2) + 2.0 * mu / capture_radius.m()).sqrt() - circular;
+
+        // Weakly bound orbit with its apoapsis at the edge of the sphere of influence.
+        let periapsis = (2.0 * mu / capture_radius.m() - 2.0 * mu / (capture_radius.m() + soi.m())).sqrt();
+        let ballistic = periapsis - circular;
+
+        let plausible = capture_radius.m() < soi.m() && v_infinity.mps() <= (mu / soi.m()).sqrt();
+
+        Some(Self {
+            plausible,
+            direct_insertion: Velocity::from_meters_per_second(direct),
+            ballistic_insertion: Velocity::from_meters_per_second(ballistic),
+            savings: Velocity::from_meters_per_second(if plausible {direct - ballistic} else {0.0}),
+        })
+    }
+}
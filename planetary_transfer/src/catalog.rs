@@ -0,0 +1,154 @@
+use crate::{Atmosphere, Distance, Duration, Finite, GravitationalParameter, Mass, Parent};
+
+// Shape of the marker of a body on diagrams, left to frontends to draw.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Marker {
+    Circle,
+    Diamond,
+    Square,
+}
+
+// Bodies of the solar system and of KSP's stock system that can be picked as parents, origins or targets,
+// along with their orbits and how they are displayed.
+#[derive(Clone, Debug)]
+pub struct Body {
+    pub name: &'static str,
+    pub mass: Mass,
+    pub radius: Distance,
+    // Name of the body it orbits and semi-major axis of its orbit, None for stars.
+    pub orbit: Option<(&'static str, Distance)>,
+    // Whether it orbits against the rotation of its primary, as Triton.
+    pub retrograde: bool,
+    // Sidereal rotation period, negative for retrograde rotation.
+    pub rotation_period: Option<Duration>,
+    pub atmosphere: Option<Atmosphere>,
+    // Second zonal harmonic of the gravity field, None for the bodies of KSP, point masses in the game.
+    pub j2: Option<f64>,
+    // Luminosity in solar luminosities, None for the bodies that don't shine.
+    pub luminosity: Option<f64>,
+    // Luminosity of the star lighting the body and distance to it, through the orbit of its primary for moons.
+    pub star: Option<(f64, Distance)>,
+    // Display color as RGB.
+    pub color: [u8; 3],
+    pub marker: Marker,
+}
+
+impl Body {
+    fn new(name: &'static str, gravitational_parameter: GravitationalParameter, radius: Distance) -> Self {
+        Self {
+            name,
+            mass: Mass::from_gravitational_parameter(gravitational_parameter),
+            radius,
+            orbit: None,
+            retrograde: false,
+            rotation_period: None,
+            atmosphere: None,
+            j2: None,
+            luminosity: None,
+            star: None,
+            color: [255, 255, 255],
+            marker: Marker::Circle,
+        }
+    }
+
+    fn display(mut self, color: [u8; 3], marker: Marker) -> Self {
+        self.color = color;
+        self.marker = marker;
+        self
+    }
+
+    fn orbiting(mut self, primary: &'static str, sma: Distance) -> Self {
+        self.orbit = Some((primary, sma));
+        self
+    }
+
+    fn retrograde(mut self) -> Self {
+        self.retrograde = true;
+        self
+    }
+
+    fn rotating(mut self, rotation_period: Duration) -> Self {
+        self.rotation_period = Some(rotation_period);
+        self
+    }
+
+    fn atmosphere(mut self, surface_pressure: f64, height: Distance) -> Self {
+        self.atmosphere = Some(Atmosphere::new(surface_pressure, height));
+        self
+    }
+
+    fn shining(mut self, luminosity: f64) -> Self {
+        self.luminosity = Some(luminosity);
+        self
+    }
+
+    fn oblate(mut self, j2: f64) -> Self {
+        self.j2 = Some(j2);
+        self
+    }
+
+    pub fn parent(&self) -> Parent {
+        let mut parent = Parent::new(self.mass).with_radius(self.radius);
+        if let Some(rotation_period) = self.rotation_period {
+            parent = parent.with_rotation_period(rotation_period);
+        }
+        if let Some(atmosphere) = self.atmosphere {
+            parent = parent.with_atmosphere(atmosphere);
+        }
+        if let Some(Ok(j2)) = self.j2.map(Finite::new) {
+            parent = parent.with_j2(j2);
+        }
+        if let Some(Ok(luminosity)) = self.luminosity.map(Finite::new) {
+            parent = parent.with_luminosity(luminosity);
+        }
+        if let Some((Ok(luminosity), distance)) = self.star.map(|(luminosity, distance)| (Finite::new(luminosity), distance)) {
+            parent = parent.with_star(luminosity, distance);
+        }
+        parent
+    }
+
+    pub fn primary(&self, bodies: &[Body]) -> Option<usize> {
+        let (primary, _) = self.orbit?;
+        bodies.iter().position(|body| body.name == primary)
+    }
+}
+
+// Bodies are defined by their gravitational parameter in km³/s², measured far more precisely than their mass.
+fn gm(gravitational_parameter: f64) -> GravitationalParameter {
+    GravitationalParameter::from_cubic_kilometers_per_second_squared(gravitational_parameter)
+}
+
+// Kerbol lights Kerbin as the Sun does the Earth.
+const KERBOL_LUMINOSITY: f64 = (13_599_840_256.0 / 149_597_870_700.0) * (13_599_840_256.0 / 149_597_870_700.0);
+
+pub fn bodies() -> Vec<Body> {
+    let mut bodies = vec![
+        Body::new("Sun", gm(132_712_440_018.0), Distance::from_kilometers(695_700.0)).rotating(Duration::from_days(25.38)).shining(1.0).display([255, 204, 0], Marker::Diamond),
+        Body::new("Mercury", gm(22_031.868), Distance::from_kilometers(2_439.7)).orbiting("Sun", Distance::from_kilometers(57_909_050.0)).rotating(Duration::from_days(58.646)).oblate(5.03E-5).display([151, 151, 159], Marker::Circle),
+        Body::new("Venus", gm(324_858.592), Distance::from_kilometers(6_051.8)).orbiting("Sun", Distance::from_kilometers(108_208_000.0)).rotating(Duration::from_days(-243.025)).atmosphere(9_200.0, Distance::from_kilometers(250.0)).oblate(4.458E-6).display([227, 187, 118], Marker::Circle),
+        Body::new("Earth", gm(398_600.435), Distance::from_kilometers(6_371.0)).orbiting("Sun", Distance::from_kilometers(149_598_023.0)).rotating(Duration::from_seconds(86_164.1)).atmosphere(101.325, Distance::from_kilometers(100.0)).oblate(1.082_63E-3).display([40, 122, 184], Marker::Circle),
+        Body::new("Moon", gm(4_902.800), Distance::from_kilometers(1_737.4)).orbiting("Earth", Distance::from_kilometers(384_399.0)).rotating(Duration::from_days(27.321_661)).oblate(2.033E-4).display([190, 190, 190], Marker::Square),
+        Body::new("Mars", gm(42_828.375), Distance::from_kilometers(3_389.5)).orbiting("Sun", Distance::from_kilometers(227_939_200.0)).rotating(Duration::from_hours(24.622_9)).atmosphere(0.636, Distance::from_kilometers(125.0)).oblate(1.960_45E-3).display([193, 68, 14], Marker::Circle),
+        Body::new("Jupiter", gm(126_686_531.9), Distance::from_kilometers(69_911.0)).orbiting("Sun", Distance::from_kilometers(778_570_000.0)).rotating(Duration::from_hours(9.925)).oblate(1.473_6E-2).display([201, 144, 57], Marker::Circle),
+        Body::new("Saturn", gm(37_931_206.2), Distance::from_kilometers(58_232.0)).orbiting("Sun", Distance::from_kilometers(1_433_530_000.0)).rotating(Duration::from_hours(10.656)).oblate(1.629_8E-2).display([227, 205, 150], Marker::Circle),
+        Body::new("Uranus", gm(5_793_951.3), Distance::from_kilometers(25_362.0)).orbiting("Sun", Distance::from_kilometers(2_875_040_000.0)).rotating(Duration::from_hours(-17.24)).oblate(3.343_43E-3).display([172, 229, 238], Marker::Circle),
+        Body::new("Neptune", gm(6_835_099.5), Distance::from_kilometers(24_622.0)).orbiting("Sun", Distance::from_kilometers(4_500_000_000.0)).rotating(Duration::from_hours(16.11)).oblate(3.411E-3).display([91, 93, 223], Marker::Circle),
+        Body::new("Kerbol", gm(1_172_332_800.0), Distance::from_kilometers(261_600.0)).rotating(Duration::from_seconds(432_000.0)).shining(KERBOL_LUMINOSITY).display([255, 204, 0], Marker::Diamond),
+        Body::new("Kerbin", gm(3_531.6), Distance::from_kilometers(600.0)).orbiting("Kerbol", Distance::from_meters(13_599_840_256.0)).rotating(Duration::from_seconds(21_549.425)).atmosphere(101.325, Distance::from_kilometers(70.0)).display([72, 132, 199], Marker::Circle),
+        Body::new("Mun", gm(65.138_398), Distance::from_kilometers(200.0)).orbiting("Kerbin", Distance::from_meters(12_000_000.0)).rotating(Duration::from_seconds(138_984.38)).display([160, 160, 160], Marker::Square),
+        Body::new("Duna", gm(301.363_21), Distance::from_kilometers(320.0)).orbiting("Kerbol", Distance::from_meters(20_726_155_264.0)).rotating(Duration::from_seconds(65_517.859)).atmosphere(6.755, Distance::from_kilometers(50.0)).display([196, 92, 57], Marker::Circle),
+        Body::new("Jool", gm(282_528.0), Distance::from_kilometers(6_000.0)).orbiting("Kerbol", Distance::from_meters(68_773_560_320.0)).rotating(Duration::from_seconds(36_000.0)).display([120, 176, 60], Marker::Circle),
+        // Appended rather than listed with Neptune, indices into the catalog being saved by frontends.
+        Body::new("Triton", gm(1_427.598), Distance::from_kilometers(1_353.4)).orbiting("Neptune", Distance::from_kilometers(354_759.0)).retrograde().rotating(Duration::from_days(-5.876_854)).display([200, 180, 170], Marker::Square),
+    ];
+    // Bodies are lit by the star at the root of their primaries, from the distance of the one orbiting it.
+    for i in 0..bodies.len() {
+        let (mut body, mut distance) = (i, None);
+        while let (Some((_, sma)), Some(primary)) = (bodies[body].orbit, bodies[body].primary(&bodies)) {
+            distance = Some(sma);
+            body = primary;
+        }
+        bodies[i].star = bodies[body].luminosity.zip(distance);
+    }
+    bodies
+}
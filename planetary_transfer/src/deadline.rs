@@ -0,0 +1,60 @@
+use crate::solvers::{self, Tolerance};
+use crate::{Duration, Transfer, Velocity};
+
+// Departure delta-v scanned from the Hohmann transfer before refining the cheapest one making the deadline, the
+// windows of faster transfers moving with their phase angle.
+const SAMPLES: usize = 256;
+// In m/s.
+const TOLERANCE: f64 = 1E-6;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DeadlineTransfer {
+    // Delta-v as given to `Transfer::set_delta_v`, negative when burning retrograde.
+    pub delta_v: Velocity,
+    // Times from the epoch of the departure and of the arrival.
+    pub departure: Duration,
+    pub arrival: Duration,
+}
+
+impl Transfer {
+    // Cheapest transfer reaching the target by a deadline after the epoch, departing at the latest launch window that
+    // still makes it, given the phase angle at the epoch in radians. Starting from the Hohmann transfer, delta-v is
+    // only added when none of its windows arrives in time. None when even the fastest transfer of the range arrives
+    // too late.
+    pub fn solve_for_arrival(&self, phase_angle: f64, deadline: Duration) -> Option<DeadlineTransfer> {
+        let solution = |delta_v: f64| {
+            let mut transfer = *self;
+            transfer.try_set_delta_v(Velocity::from_meters_per_second(delta_v)).ok()?;
+            let time_of_flight = transfer.time_of_flight();
+            let first = transfer.wait_time_until_window(phase_angle);
+            let windows = ((deadline - time_of_flight - first) / transfer.synodic_period()).floor();
+            if windows < 0.0 {
+                return None;
+            }
+            let departure = first + transfer.synodic_period() * windows;
+            Some(DeadlineTransfer {
+                delta_v: Velocity::from_meters_per_second(delta_v),
+                departure,
+                arrival: departure + time_of_flight,
+            })
+        };
+
+        let (hohmann, fastest) = (self.min_velocity().mps(), self.max_velocity().mps());
+        let step = (fastest - hohmann) / SAMPLES as f64;
+        let first = (0..=SAMPLES).find(|&i| solution(hohmann + step * i as f64).is_some())?;
+        if first == 0 {
+            return solution(hohmann);
+        }
+
+        // Narrows down to the least delta-v making the deadline between the last sample missing it and the first one
+        // making it, the windows jumping by a synodic period when the phase angle of the transfer wraps around.
+        let (missed, made) = (hohmann + step * (first - 1) as f64, hohmann + step * first as f64);
+        let root = solvers::bisection(
+            |delta_v| if solution(delta_v).is_some() {1.0} else {-1.0},
+            missed,
+            made,
+            Tolerance::new(TOLERANCE, 100),
+        )?;
+        solution(root.x + (made - missed).signum() * TOLERANCE).or_else(|| solution(made))
+    }
+}
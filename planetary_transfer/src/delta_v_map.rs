@@ -0,0 +1,95 @@
+use crate::catalog::Body;
+use crate::{Distance, Planet, Transfer, Velocity};
+
+// Delta-v between low orbits of every pair of bodies of a catalog, in the style of the community delta-v maps.
+// Legs are Hohmann transfers between circular orbits, following the hierarchy of the bodies: going from a moon
+// to another planet goes through a low orbit of the moon's planet, costs of every leg being summed.
+
+// Radius of a low orbit, in radii of the body.
+pub const LOW_ORBIT_RADII: f64 = 1.1;
+
+#[derive(Copy, Clone, Debug)]
+pub struct DeltaV {
+    pub origin: usize,
+    pub target: usize,
+    pub delta_v: Velocity,
+}
+
+// Pairs of bodies orbiting the same star, each pair appearing once, the cost being the same both ways.
+pub fn delta_v_map(bodies: &[Body]) -> Vec<DeltaV> {
+    (0..bodies.len())
+        .flat_map(|origin| (origin + 1..bodies.len()).map(move |target| (origin, target)))
+        .filter_map(|(origin, target)| Some(DeltaV {
+            origin,
+            target,
+            delta_v: delta_v(bodies, origin, target)?,
+        }))
+        .collect()
+}
+
+// None when the bodies do not share a star.
+pub fn delta_v(bodies: &[Body], origin: usize, target: usize) -> Option<Velocity> {
+    let origin_chain = ancestry(bodies, origin);
+    let target_chain = ancestry(bodies, target);
+    let common = origin_chain.iter().position(|body| target_chain.contains(body))?;
+    let common_in_target = target_chain.iter().position(|body| *body == origin_chain[common])?;
+
+    // Bodies between the origin or the target and their common ancestor.
+    let (mut climb, mut descent) = (&origin_chain[..common], &target_chain[..common_in_target]);
+
+    // Unless one of the bodies orbits the other, directly or not, the last leg is between the two bodies orbiting the common ancestor.
+    let mut total = 0.0;
+    if let (Some((&origin_side, rest_climb)), Some((&target_side, rest_descent))) = (climb.split_last(), descent.split_last()) {
+        let (departure, arrival) = sibling_burns(bodies, origin_side, target_side)?;
+        total += departure + arrival;
+        climb = rest_climb;
+        descent = rest_descent;
+    }
+    total += climb.iter().chain(descent).map(|&body| down(bodies, body)).sum::<Option<f64>>()?;
+
+    Some(Velocity::from_meters_per_second(total))
+}
+
+// Body and the bodies it orbits, up to its star.
+fn ancestry(bodies: &[Body], body: usize) -> Vec<usize> {
+    let mut chain = vec![body];
+    let mut current = body;
+    while let Some(primary) = bodies[current].primary(bodies).filter(|primary| !chain.contains(primary)) {
+        chain.push(primary);
+        current = primary;
+    }
+    chain
+}
+
+fn planet(bodies: &[Body], body: usize) -> Option<Planet> {
+    let (_, sma) = bodies[body].orbit?;
+    let primary = &bodies[bodies[body].primary(bodies)?];
+    Some(Planet::new(sma, primary.parent()).with_mass(bodies[body].mass).with_retrograde(bodies[body].retrograde))
+}
+
+fn low_orbit(body: &Body) -> f64 {
+    body.radius.m() * LOW_ORBIT_RADII
+}
+
+// Burn from a low orbit onto an escape trajectory with a given excess velocity.
+fn escape(body: &Body, v_infinity: Velocity) -> f64 {
+    body.parent().escape_delta_v(Distance::from_meters(low_orbit(body)), v_infinity).mps()
+}
+
+// Between a low orbit of a body and a low orbit of its primary.
+fn down(bodies: &[Body], body: usize) -> Option<f64> {
+    let origin = planet(bodies, body)?;
+    let primary = &bodies[bodies[body].primary(bodies)?];
+    let primary_orbit = Planet::new(Distance::from_meters(low_orbit(primary)), primary.parent());
+    let transfer = Transfer::try_new(origin, primary_orbit).ok()?;
+    Some(escape(&bodies[body], transfer.departure_v_infinity()) + transfer.arrival_v_infinity().mps())
+}
+
+// Between low orbits of two bodies orbiting the same primary.
+fn sibling_burns(bodies: &[Body], origin: usize, target: usize) -> Option<(f64, f64)> {
+    let transfer = Transfer::try_new(planet(bodies, origin)?, planet(bodies, target)?).ok()?;
+    Some((
+        escape(&bodies[origin], transfer.departure_v_infinity()),
+        escape(&bodies[target], transfer.arrival_v_infinity()),
+    ))
+}
@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use crate::{Explained, Step, Transfer};
+
+// Every step of the computation of a transfer, from its inputs to its results, for reports to show their working.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Derivation {
+    pub steps: Vec<Step>,
+    // Symbols of the steps that are results of the transfer rather than intermediate quantities.
+    pub results: Vec<&'static str>,
+}
+
+const EXPLAINED: [Explained; 6] = [
+    Explained::HohmannDeltaV,
+    Explained::TransferOrbit,
+    Explained::TimeOfFlight,
+    Explained::PhaseAngle,
+    Explained::C3,
+    Explained::ArrivalVInfinity,
+];
+
+impl Transfer {
+    pub fn derivation(&self) -> Derivation {
+        let mut derivation = Derivation { steps: Vec::new(), results: Vec::new() };
+        for explained in EXPLAINED {
+            let steps = self.explain(explained);
+            // The orbit is explained through both of its apsides.
+            let results = if explained == Explained::TransferOrbit {&steps[steps.len() - self.apoapsis().map_or(1, |_| 2)..]} else {&steps[steps.len() - 1..]};
+            derivation.results.extend(results.iter().map(|step| step.symbol));
+            for step in steps {
+                if !derivation.steps.iter().any(|recorded| recorded.symbol == step.symbol) {
+                    derivation.steps.push(step);
+                }
+            }
+        }
+        derivation
+    }
+}
+
+impl Derivation {
+    pub fn inputs(&self) -> impl Iterator<Item = &Step> {
+        self.steps.iter().filter(|step| step.formula.is_empty())
+    }
+
+    pub fn intermediates(&self) -> impl Iterator<Item = &Step> {
+        self.steps.iter().filter(|step| !step.formula.is_empty() && !self.results.contains(&step.symbol))
+    }
+
+    pub fn results(&self) -> impl Iterator<Item = &Step> {
+        self.results.iter().filter_map(|symbol| self.steps.iter().find(|step| step.symbol == *symbol))
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Transfer derivation\n\n## Inputs\n\n| Symbol | Quantity | Value |\n| --- | --- | --- |\n");
+        for step in self.inputs() {
+            let _ = writeln!(markdown, "| {} | {} | {} |", step.symbol, step.name, step.formatted_value());
+        }
+        // Absolute values would otherwise split the cells of the tables.
+        let cell = |text: &str| text.replace('|', "\\|");
+
+        markdown.push_str("\n## Steps\n\n| Symbol | Quantity | Formula | Substituted | Value |\n| --- | --- | --- | --- | --- |\n");
+        for step in self.steps.iter().filter(|step| !step.formula.is_empty()) {
+            let _ = writeln!(markdown, "| {} | {} | {} = {} | {} | {} |", step.symbol, step.name, step.symbol, cell(step.formula), cell(&step.substituted), step.formatted_value());
+        }
+
+        markdown.push_str("\n## Results\n\n");
+        for step in self.results() {
+            let _ = writeln!(markdown, "- {}: {} = {}", step.name, step.symbol, step.formatted_value());
+        }
+        markdown
+    }
+
+    pub fn to_latex(&self) -> String {
+        let mut latex = String::from("\\documentclass{article}\n\\usepackage[utf8]{inputenc}\n\\usepackage{amsmath}\n\\begin{document}\n\n\\section*{Inputs}\n\\begin{itemize}\n");
+        for step in self.inputs() {
+            let _ = writeln!(latex, "  \\item {}: ${} = {}$", step.name, math(step.symbol), value(step));
+        }
+
+        latex.push_str("\\end{itemize}\n\n\\section*{Steps}\n\\begin{align*}\n");
+        for step in self.intermediates().chain(self.results()) {
+            let _ = writeln!(latex, "  {} &= {} = {} = {} \\\\", math(step.symbol), math(step.formula), math(&step.substituted), value(step));
+        }
+
+        latex.push_str("\\end{align*}\n\n\\section*{Results}\n\\begin{itemize}\n");
+        for step in self.results() {
+            let _ = writeln!(latex, "  \\item {}: ${} = {}$", step.name, math(step.symbol), value(step));
+        }
+        latex.push_str("\\end{itemize}\n\n\\end{document}\n");
+        latex
+    }
+}
+
+fn value(step: &Step) -> String {
+    let value = math(&crate::number(step.value));
+    match step.unit {
+        "" => value,
+        "°" => format!("{}^\\circ", value),
+        unit => format!("{}\\,\\mathrm{{{}}}", value, math(unit)),
+    }
+}
+
+// Converts the Unicode notation of the formulas to LaTeX math, numbers in scientific notation included.
+fn math(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut latex = String::new();
+    // Whether each open parenthesis is that of a square root, closed by a brace.
+    let mut parentheses = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii_alphabetic() {
+            let end = (i..chars.len()).find(|&j| !chars[j].is_ascii_alphabetic()).unwrap_or(chars.len());
+            let word: String = chars[i..end].iter().collect();
+            match word.as_str() {
+                "arccos" | "sin" | "cos" | "sinh" => {
+                    latex.push('\\');
+                    latex.push_str(&word);
+                    latex.push(' ');
+                }
+                "arcosh" => latex.push_str("\\operatorname{arcosh} "),
+                _ => latex.push_str(&word),
+            }
+            i = end;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let end = (i..chars.len()).find(|&j| !(chars[j].is_ascii_digit() || chars[j] == '.')).unwrap_or(chars.len());
+            let mantissa: String = chars[i..end].iter().collect();
+            i = end;
+            // Exponent of a number in scientific notation, the number being parenthesized when raised to a power.
+            if chars.get(i) == Some(&'E') && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit() || *c == '-') {
+                let end = (i + 2..chars.len()).find(|&j| !chars[j].is_ascii_digit()).unwrap_or(chars.len());
+                let exponent: String = chars[i + 1..end].iter().collect();
+                i = end;
+                let _ = match chars.get(i) {
+                    Some('²' | '³') => write!(latex, "({} \\times 10^{{{}}})", mantissa, exponent),
+                    _ => write!(latex, "{} \\times 10^{{{}}}", mantissa, exponent),
+                };
+            } else {
+                latex.push_str(&mantissa);
+            }
+            continue;
+        }
+        if subscript(c).is_some() {
+            let end = (i..chars.len()).find(|&j| subscript(chars[j]).is_none()).unwrap_or(chars.len());
+            let subscripts: Vec<&str> = chars[i..end].iter().filter_map(|&c| subscript(c)).collect();
+            let _ = write!(latex, "_{{{}}}", subscripts.join(" "));
+            i = end;
+            continue;
+        }
+
+        match c {
+            '√' if chars.get(i + 1) == Some(&'(') => {
+                latex.push_str("\\sqrt{");
+                parentheses.push(true);
+                i += 1;
+            }
+            '(' => {
+                latex.push('(');
+                parentheses.push(false);
+            }
+            ')' => latex.push(if parentheses.pop() == Some(true) {'}'} else {')'}),
+            '²' => latex.push_str("^{2}"),
+            '³' => latex.push_str("^{3}"),
+            '°' => latex.push_str("^\\circ"),
+            '×' => latex.push_str(" \\times "),
+            '−' => latex.push_str(" - "),
+            '±' => latex.push_str("\\pm "),
+            'μ' => latex.push_str("\\mu "),
+            'θ' => latex.push_str("\\theta"),
+            'φ' => latex.push_str("\\varphi"),
+            'π' => latex.push_str("\\pi "),
+            'Δ' => latex.push_str("\\Delta "),
+            '%' => latex.push_str("\\%"),
+            c => latex.push(c),
+        }
+        i += 1;
+    }
+    latex
+}
+
+// Subscripts of the symbols, the infinity of the hyperbolic excess speeds included.
+fn subscript(c: char) -> Option<&'static str> {
+    Some(match c {
+        '₀' => "0",
+        '₁' => "1",
+        '₂' => "2",
+        'ₕ' => "h",
+        'ₚ' => "p",
+        'ₐ' => "a",
+        'ₜ' => "t",
+        'ᵣ' => "r",
+        '∞' => "\\infty",
+        _ => return None,
+    })
+}
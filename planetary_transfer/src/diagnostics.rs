@@ -0,0 +1,13 @@
+// Convergence of the iterative solvers, reported as tracing events with the `tracing` feature and compiled out otherwise.
+// `residual` is the error left in the equation being solved, in its own unit.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn convergence(solver: &'static str, iterations: usize, residual: f64, converged: bool) {
+    #[cfg(feature = "tracing")]
+    {
+        if converged {
+            tracing::trace!(solver, iterations, residual, "converged");
+        } else {
+            tracing::warn!(solver, iterations, residual, "did not converge");
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{geometry, significant, Transfer, SIGNIFICANT_FIGURES};
+
+// Results of a transfer that can be explained, from the inputs to the result through each intermediate quantity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Explained {
+    HohmannDeltaV,
+    TransferOrbit,
+    TimeOfFlight,
+    PhaseAngle,
+    C3,
+    ArrivalVInfinity,
+}
+
+impl Explained {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Explained::HohmannDeltaV => "Hohmann delta-v",
+            Explained::TransferOrbit => "Transfer orbit",
+            Explained::TimeOfFlight => "Time of flight",
+            Explained::PhaseAngle => "Phase angle",
+            Explained::C3 => "C3",
+            Explained::ArrivalVInfinity => "Hyperbolic excess speed at arrival",
+        }
+    }
+}
+
+// Quantity computed along the way to a result, with the formula it came from and the values substituted in it.
+// Values are in SI units, angles in degrees, apart from C3 in km²/s² as it is usually given.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    // Empty for the inputs of the transfer.
+    pub formula: &'static str,
+    pub substituted: String,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+impl Step {
+    fn input(symbol: &'static str, name: &'static str, value: f64, unit: &'static str) -> Self {
+        Self {
+            symbol,
+            name,
+            formula: "",
+            substituted: String::new(),
+            value,
+            unit,
+        }
+    }
+
+    fn new(symbol: &'static str, name: &'static str, formula: &'static str, substituted: String, value: f64, unit: &'static str) -> Self {
+        Self {
+            symbol,
+            name,
+            formula,
+            substituted,
+            value,
+            unit,
+        }
+    }
+
+    pub fn formatted_value(&self) -> String {
+        let value = number(self.value);
+        match self.unit {
+            "" => value,
+            "°" => format!("{}°", value),
+            unit => format!("{} {}", value, unit),
+        }
+    }
+}
+
+// Rounded to the significant figures of the results, in scientific notation when far from unity.
+pub fn number(value: f64) -> String {
+    if value != 0.0 && value.is_finite() && !(1E-3..1E6).contains(&value.abs()) {
+        let exponent = value.abs().log10().floor();
+        format!("{}E{}", significant(value / 10_f64.powf(exponent), SIGNIFICANT_FIGURES), exponent)
+    } else {
+        significant(value, SIGNIFICANT_FIGURES)
+    }
+}
+
+// Steps leading to a result, each only recorded once.
+#[derive(Default)]
+struct Steps(Vec<Step>);
+
+impl Steps {
+    fn push(&mut self, step: Step) {
+        if !self.0.iter().any(|recorded| recorded.symbol == step.symbol) {
+            self.0.push(step);
+        }
+    }
+}
+
+impl Transfer {
+    // Steps of the computation of a result, using the same functions as the result itself.
+    pub fn explain(&self, explained: Explained) -> Vec<Step> {
+        let mut steps = Steps::default();
+        match explained {
+            Explained::HohmannDeltaV => self.hohmann_steps(&mut steps),
+            Explained::TransferOrbit => {
+                self.orbit_steps(&mut steps);
+                self.apsides_steps(&mut steps);
+            }
+            Explained::TimeOfFlight => self.time_of_flight_steps(&mut steps),
+            Explained::PhaseAngle => self.phase_angle_steps(&mut steps),
+            Explained::C3 => self.c3_steps(&mut steps),
+            Explained::ArrivalVInfinity => self.arrival_steps(&mut steps),
+        }
+        steps.0
+    }
+
+    fn input_steps(&self, steps: &mut Steps) {
+        steps.push(Step::input("μ", "Gravitational parameter of the parent", self.parent.gravitational_parameter.m3ps2(), "m³/s²"));
+        steps.push(Step::input("r₁", "Semi-major axis of the origin", self.origin.sma().m(), "m"));
+        steps.push(Step::input("r₂", "Semi-major axis of the target", self.target.sma().m(), "m"));
+    }
+
+    fn hohmann_steps(&self, steps: &mut Steps) {
+        self.input_steps(steps);
+        let (mu, r1, r2) = (self.parent.gravitational_parameter.m3ps2(), self.origin.sma().m(), self.target.sma().m());
+        let v1 = self.origin.orbital_velocity().mps();
+        steps.push(Step::new("v₁", "Orbital speed of the origin", "√(μ / r₁)",
+            format!("√({} / {})", number(mu), number(r1)), v1, "m/s"));
+        steps.push(Step::new("vₕ", "Speed at departure of the Hohmann transfer", "v₁ √(2 r₂ / (r₁ + r₂))",
+            format!("{} × √(2 × {} / ({} + {}))", number(v1), number(r2), number(r1), number(r2)), self.velocity_hohmann().mps(), "m/s"));
+        steps.push(Step::new("Δvₕ", "Delta-v of the Hohmann transfer", "vₕ − v₁",
+            format!("{} − {}", number(self.velocity_hohmann().mps()), number(v1)), self.delta_v_hohmann().mps(), "m/s"));
+    }
+
+    fn orbit_steps(&self, steps: &mut Steps) {
+        self.input_steps(steps);
+        let (mu, r1) = (self.parent.gravitational_parameter.m3ps2(), self.origin.sma().m());
+        let (v1, v0) = (self.origin.orbital_velocity().mps(), self.launch_velocity().mps());
+        steps.push(Step::input("Δv", "Delta-v of the departure burn", v0 - v1, "m/s"));
+        steps.push(Step::new("v₁", "Orbital speed of the origin", "√(μ / r₁)",
+            format!("√({} / {})", number(mu), number(r1)), v1, "m/s"));
+        steps.push(Step::new("v₀", "Speed after the departure burn", "v₁ + Δv",
+            format!("{} + {}", number(v1), number(v0 - v1)), v0, "m/s"));
+        steps.push(Step::new("a", "Semi-major axis of the transfer", "r₁ μ / (2 μ − r₁ v₀²)",
+            format!("{} × {} / (2 × {} − {} × {}²)", number(r1), number(mu), number(mu), number(r1), number(v0)), self.sma().m(), "m"));
+        steps.push(Step::new("e", "Eccentricity of the transfer, negative when departing from the apoapsis", "1 − r₁ / a",
+            format!("1 − {} / {}", number(r1), number(self.sma().m())), self.eccentricity(), ""));
+    }
+
+    fn apsides_steps(&self, steps: &mut Steps) {
+        let (a, e) = (self.sma().m(), self.eccentricity());
+        steps.push(Step::new("rₚ", "Periapsis of the transfer", "a (1 − |e|)",
+            format!("{} × (1 − {})", number(a), number(e.abs())), self.periapsis().m(), "m"));
+        if let Some(apoapsis) = self.apoapsis() {
+            steps.push(Step::new("rₐ", "Apoapsis of the transfer", "a (1 + |e|)",
+                format!("{} × (1 + {})", number(a), number(e.abs())), apoapsis.m(), "m"));
+        }
+    }
+
+    fn time_of_flight_steps(&self, steps: &mut Steps) {
+        self.orbit_steps(steps);
+        let (a, e, r2) = (self.sma().m(), self.eccentricity(), self.target.sma().m());
+        let (departure, arrival) = (self.origin_true_anomaly_departure(), self.target_true_anomaly_arrival());
+        let (cos_departure, cos_arrival) = (self.eccentric_anomaly_cos(departure), self.eccentric_anomaly_cos(arrival));
+        let (mean_departure, mean_arrival) = (self.mean_anomaly(cos_departure), self.mean_anomaly(cos_arrival));
+
+        steps.push(Step::new("θ₁", "True anomaly at departure, the origin being on an apsis", "arccos((a (1 − e²) / r₁ − 1) / e)",
+            format!("{}°", number(departure.to_degrees())), departure.to_degrees(), "°"));
+        steps.push(Step::new("θ₂", "True anomaly at arrival", "±arccos((a (1 − e²) / r₂ − 1) / e)",
+            format!("±arccos(({} × (1 − {}²) / {} − 1) / {})", number(a), number(e), number(r2), number(e)), arrival.to_degrees(), "°"));
+        steps.push(Step::new("cos E₁", "Cosine of the eccentric anomaly at departure", "(e + cos θ₁) / (1 + e cos θ₁)",
+            format!("({} + cos {}°) / (1 + {} × cos {}°)", number(e), number(departure.to_degrees()), number(e), number(departure.to_degrees())), cos_departure, ""));
+        steps.push(Step::new("cos E₂", "Cosine of the eccentric anomaly at arrival", "(e + cos θ₂) / (1 + e cos θ₂)",
+            format!("({} + cos {}°) / (1 + {} × cos {}°)", number(e), number(arrival.to_degrees()), number(e), number(arrival.to_degrees())), cos_arrival, ""));
+
+        // Kepler's equation, in its hyperbolic form for open transfers.
+        let mean = |symbol, name, cos: f64, value| if e.abs() < 1.0 {
+            Step::new(symbol, name, "E − e sin E", format!("arccos {} − {} × sin(arccos {})", number(cos), number(e), number(cos)), value, "rad")
+        } else {
+            Step::new(symbol, name, "e sinh H − H", format!("{} × sinh(arcosh {}) − arcosh {}", number(e), number(cos), number(cos)), value, "rad")
+        };
+        steps.push(mean("M₁", "Mean anomaly at departure", cos_departure, mean_departure));
+        steps.push(mean("M₂", "Mean anomaly at arrival", cos_arrival, mean_arrival));
+
+        let mu = self.parent.gravitational_parameter.m3ps2();
+        let time_of_flight = self.time_of_flight().s();
+        match self.period().filter(|_| self.revolutions() > 0) {
+            Some(period) => {
+                steps.push(Step::new("T", "Period of the transfer", "2π √(a³ / μ)",
+                    format!("2π × √({}³ / {})", number(a), number(mu)), period.s(), "s"));
+                steps.push(Step::new("t", "Time of flight", "√(|a|³ / μ) (M₂ − M₁) + N T",
+                    format!("√({}³ / {}) × ({} − {}) + {} × {}", number(a.abs()), number(mu), number(mean_arrival), number(mean_departure), self.revolutions(), number(period.s())),
+                    time_of_flight, "s"));
+            }
+            None => steps.push(Step::new("t", "Time of flight", "√(|a|³ / μ) (M₂ − M₁)",
+                format!("√({}³ / {}) × ({} − {})", number(a.abs()), number(mu), number(mean_arrival), number(mean_departure)),
+                time_of_flight, "s")),
+        }
+    }
+
+    fn phase_angle_steps(&self, steps: &mut Steps) {
+        self.time_of_flight_steps(steps);
+        let (mu, r2) = (self.parent.gravitational_parameter.m3ps2(), self.target.sma().m());
+        let (arrival, time_of_flight, period) = (self.target_true_anomaly_arrival(), self.time_of_flight().s(), self.target.period());
+        steps.push(Step::new("T₂", "Orbital period of the target", "2π √(r₂³ / μ)",
+            format!("2π × √({}³ / {})", number(r2), number(mu)), period, "s"));
+        // Wrapped the same way as the protractor of the diagram, a target orbiting the other way moving backwards.
+        let phase_angle = geometry::Protractor::new(self.target_true_anomaly_departure(), 1.0).angle();
+        let (formula, sign) = if self.target_mean_motion() < 0.0 {("θ₂ + 360° t / T₂", "+")} else {("θ₂ − 360° t / T₂", "−")};
+        steps.push(Step::new("φ", "Phase angle of the target at departure, within ±180°", formula,
+            format!("{}° {} 360° × {} / {}", number(arrival.to_degrees()), sign, number(time_of_flight), number(period)),
+            phase_angle.to_degrees(), "°"));
+    }
+
+    fn c3_steps(&self, steps: &mut Steps) {
+        self.orbit_steps(steps);
+        let v_infinity = self.departure_v_infinity();
+        steps.push(Step::new("v∞₁", "Hyperbolic excess speed at departure, the burn being tangential", "|Δv|",
+            format!("|{}|", number(v_infinity.mps())), v_infinity.mps(), "m/s"));
+        steps.push(Step::new("C3", "Characteristic energy of the departure", "v∞₁²",
+            format!("({} / 1000)²", number(v_infinity.mps())), self.c3(), "km²/s²"));
+    }
+
+    fn arrival_steps(&self, steps: &mut Steps) {
+        self.orbit_steps(steps);
+        let (mu, r1, r2, a, v0) = (self.parent.gravitational_parameter.m3ps2(), self.origin.sma().m(), self.target.sma().m(), self.sma().m(), self.launch_velocity().mps());
+        let (v2, arrival) = (self.target_velocity().mps(), self.arrival_velocity().mps());
+        let tangential = r1 * v0 / r2;
+        let radial = (arrival.powi(2) - tangential.powi(2)).max(0.0).sqrt();
+
+        let (formula, sign) = if v2 < 0.0 {("−√(μ / r₂)", "−")} else {("√(μ / r₂)", "")};
+        steps.push(Step::new("v₂", "Orbital speed of the target, negative when it orbits the other way", formula,
+            format!("{}√({} / {})", sign, number(mu), number(r2)), v2, "m/s"));
+        steps.push(Step::new("vₐ", "Speed at arrival", "√(μ (2 / r₂ − 1 / a))",
+            format!("√({} × (2 / {} − 1 / {}))", number(mu), number(r2), number(a)), arrival, "m/s"));
+        steps.push(Step::new("vₜ", "Tangential speed at arrival, from the conservation of angular momentum", "r₁ v₀ / r₂",
+            format!("{} × {} / {}", number(r1), number(v0), number(r2)), tangential, "m/s"));
+        steps.push(Step::new("vᵣ", "Radial speed at arrival", "√(vₐ² − vₜ²)",
+            format!("√({}² − {}²)", number(arrival), number(tangential)), radial, "m/s"));
+        steps.push(Step::new("v∞₂", "Hyperbolic excess speed at arrival", "√((vₜ − v₂)² + vᵣ²)",
+            format!("√(({} − {})² + {}²)", number(tangential), number(v2), number(radial)), self.arrival_v_infinity().mps(), "m/s"));
+    }
+}
@@ -0,0 +1,136 @@
+use std::f64::consts::TAU;
+use std::fmt::{self, Display};
+
+use crate::propagator::{propagate, propagate_state, two_body, PROPAGATION_STEPS};
+use crate::{Distance, Duration, Parent, StateVector, Transfer, Vec3};
+
+// Pressure of sunlight 1 au from the Sun on a surface absorbing it, in N/m².
+const SOLAR_PRESSURE: f64 = 4.56E-6;
+// Scale heights within the height of an atmosphere, its density there having fallen by about e^12.
+const SCALE_HEIGHTS: f64 = 12.0;
+// Displacement at arrival, relative to the semi-major axis of the target, above which the forces matter.
+const SURFACE_FORCES_THRESHOLD: f64 = 1E-3;
+
+// Forces on the surface of the spacecraft rather than on its mass, each toggled on its own: the pressure of the light
+// of the star the parent is or orbits, none around a dark parent, and the drag of the atmosphere of the parent, its density
+// falling off exponentially with altitude and the atmosphere rotating with the parent. Real atmospheres heating up
+// high above their height, drag there is underestimated.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SurfaceForces {
+    // Area exposed over mass, in m²/kg.
+    pub area_to_mass: f64,
+    pub radiation_pressure: bool,
+    // From 1 for a surface absorbing all the light to 2 for one reflecting it all.
+    pub reflectivity: f64,
+    pub drag: bool,
+    pub drag_coefficient: f64,
+}
+
+impl Default for SurfaceForces {
+    fn default() -> Self {
+        Self {
+            area_to_mass: 0.01,
+            radiation_pressure: false,
+            reflectivity: 1.3,
+            drag: false,
+            drag_coefficient: 2.2,
+        }
+    }
+}
+
+// Effect of the surface forces on a transfer, estimated by integrating the trajectory with and without them.
+#[derive(Copy, Clone, Debug)]
+pub struct SurfaceEffect {
+    // Distance between the arrival positions with and without the forces.
+    pub displacement: Distance,
+    pub significant: bool,
+}
+
+impl Display for SurfaceEffect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Forces on the surface of the spacecraft shift the arrival by {} km.", self.displacement.km().round())
+    }
+}
+
+impl SurfaceForces {
+    pub fn enabled(&self) -> bool {
+        self.radiation_pressure || self.drag
+    }
+
+    // Acceleration in m/s² of the spacecraft in a state around the parent, in the frame of the parent.
+    pub fn acceleration(&self, parent: &Parent, state: StateVector) -> Vec3 {
+        let none = Vec3::new(0.0, 0.0, 0.0);
+        let radius = state.position.norm();
+        let radiation_pressure = match (self.radiation_pressure, parent.luminosity(), parent.star_distance()) {
+            (true, Some(luminosity), None) if radius > 0.0 => {
+                let pressure = SOLAR_PRESSURE * luminosity * (Distance::from_astronomical_unit(1.0).m() / radius).powi(2) * self.reflectivity;
+                state.position * (pressure * self.area_to_mass / radius)
+            }
+            // Around a planet the light comes from far enough to push the same everywhere, the star taken along -x.
+            (true, Some(luminosity), Some(distance)) => {
+                let pressure = SOLAR_PRESSURE * luminosity * (Distance::from_astronomical_unit(1.0).m() / distance.m()).powi(2) * self.reflectivity;
+                Vec3::new(pressure * self.area_to_mass, 0.0, 0.0)
+            }
+            _ => none,
+        };
+        let density = if self.drag {atmospheric_density(parent, Distance::from_meters(radius))} else {0.0};
+        let drag = if density > 0.0 {
+            let rotation = parent.rotation_period().map_or(0.0, |period| TAU / period.s());
+            let relative = state.velocity - Vec3::new(0.0, 0.0, rotation).cross(state.position);
+            relative * (-0.5 * density * relative.norm() * self.drag_coefficient * self.area_to_mass)
+        } else {
+            none
+        };
+        radiation_pressure + drag
+    }
+
+    // Time a circular orbit of a radius takes to decay down to the surface of the parent from drag, its semi-major
+    // axis shrinking by ρ·Cd·A/m·√(μa) every second. None with drag off, without an atmosphere or above its height,
+    // the orbit lasting for good there.
+    pub fn orbit_lifetime(&self, parent: &Parent, radius: Distance) -> Option<Duration> {
+        let (atmosphere, surface) = (parent.atmosphere().filter(|_| self.drag)?, parent.radius()?);
+        if radius.m() > (surface + atmosphere.height).m() {
+            return None;
+        }
+        let scale_height = atmosphere.height.m() / SCALE_HEIGHTS;
+        let (density, surface_density) = (atmospheric_density(parent, radius), atmospheric_density(parent, surface));
+        let ballistic = self.drag_coefficient * self.area_to_mass * (parent.gravitational_parameter().m3ps2() * radius.m()).sqrt();
+        Some(Duration::from_seconds(scale_height * (1.0 / density - 1.0 / surface_density) / ballistic))
+    }
+}
+
+// Density in kg/m³ of the atmosphere of the parent at a radius, from the pressure at its surface held up by gravity
+// over a scale height. Zero without an atmosphere or a known radius.
+pub fn atmospheric_density(parent: &Parent, radius: Distance) -> f64 {
+    let (atmosphere, surface) = match (parent.atmosphere(), parent.radius()) {
+        (Some(atmosphere), Some(surface)) => (atmosphere, surface),
+        _ => return 0.0,
+    };
+    let scale_height = atmosphere.height.m() / SCALE_HEIGHTS;
+    let gravity = parent.gravitational_parameter().m3ps2() / surface.m().powi(2);
+    // Surface pressure in kPa.
+    let surface_density = atmosphere.surface_pressure * 1E3 / (gravity * scale_height);
+    surface_density * (-(radius - surface).m().max(0.0) / scale_height).exp()
+}
+
+impl Transfer {
+    // None with all the forces off.
+    pub fn surface_effect(&self, forces: &SurfaceForces) -> Option<SurfaceEffect> {
+        if !forces.enabled() {
+            return None;
+        }
+        let (parent, gravitational_parameter) = (self.parent, self.parent.gravitational_parameter());
+        let departure = self.orbit().to_state_vector();
+        let reference = propagate(departure, self.time_of_flight(), PROPAGATION_STEPS, two_body(gravitational_parameter));
+        let states = propagate_state(departure, self.time_of_flight(), PROPAGATION_STEPS, |time, state| {
+            two_body(gravitational_parameter)(time, state.position) + forces.acceleration(&parent, state)
+        });
+        let displacement = states.last()?.position.distance(reference.last()?.position);
+
+        Some(SurfaceEffect {
+            displacement: Distance::from_meters(displacement),
+            significant: displacement > self.target().sma().m() * SURFACE_FORCES_THRESHOLD,
+        })
+    }
+}
@@ -0,0 +1,320 @@
+use std::f64::consts::{TAU, PI};
+use std::ops::RangeInclusive;
+
+use crate::{Distance, Duration, Velocity, Orbit, Planet, Transfer, ResonantOrbit, MoonApproach, Vec2, Vec3};
+
+pub const ORBIT_SAMPLES: usize = 512;
+// Most time markers placed along a transfer, however short their interval.
+pub const MAX_TIME_TICKS: usize = 1000;
+
+// Renderer-agnostic geometry of the transfer diagram, as plain polylines.
+// Coordinates are divided by `scale`, in meters, so that frontends can keep values close to unity.
+
+// Samples the conic of given semi-major axis and eccentricity over a range of true anomalies,
+// returning cartesian coordinates in the same unit as the semi-major axis.
+pub fn orbit(sma: f64, eccentricity: f64, range: RangeInclusive<f64>, n: usize) -> Vec<(f64, f64)> {
+    let (start, end) = (*range.start(), *range.end());
+    (0..=n).map(|i| {
+
+        let theta = start + (end - start) * i as f64 / n as f64;
+        let equation = sma * (1.0 - eccentricity.powi(2)) / (1.0 + eccentricity * theta.cos());
+
+        (equation * theta.cos(), equation * theta.sin())
+    }).collect()
+}
+
+pub fn point(radius: f64, angle: f64) -> (f64, f64) {
+    Vec2::from_polar(radius, angle).into()
+}
+
+// Conic section drawn on the diagram, as a polyline of its samples.
+pub trait Conic {
+    fn sma(&self) -> Distance;
+
+    fn eccentricity(&self) -> f64;
+
+    // Angle of the periapsis from the x axis. Inclinations are ignored, the diagram being a view from above.
+    fn argument_of_periapsis(&self) -> f64 {
+        0.0
+    }
+
+    fn range(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn polyline(&self, scale: f64) -> Vec<(f64, f64)> {
+        let rotation = self.argument_of_periapsis();
+        orbit(self.sma().m() / scale, self.eccentricity(), self.range(), ORBIT_SAMPLES)
+            .into_iter()
+            .map(|(x, y)| point(x.hypot(y), y.atan2(x) + rotation))
+            .collect()
+    }
+
+    // Position on the orbit at a given angle from the x axis.
+    fn position(&self, angle: f64, scale: f64) -> (f64, f64) {
+        let (sma, eccentricity) = (self.sma().m() / scale, self.eccentricity());
+        point(sma * (1.0 - eccentricity.powi(2)) / (1.0 + eccentricity * (angle - self.argument_of_periapsis()).cos()), angle)
+    }
+}
+
+impl Conic for Planet {
+    fn sma(&self) -> Distance {
+        self.sma()
+    }
+
+    fn eccentricity(&self) -> f64 {
+        self.orbit().eccentricity
+    }
+
+    fn argument_of_periapsis(&self) -> f64 {
+        self.orbit().argument_of_periapsis
+    }
+}
+
+impl Conic for Transfer {
+    fn sma(&self) -> Distance {
+        self.sma()
+    }
+
+    fn eccentricity(&self) -> f64 {
+        self.eccentricity()
+    }
+
+    fn range(&self) -> RangeInclusive<f64> {
+        let revolutions = if self.retrograde() {-TAU} else {TAU} * self.revolutions() as f64;
+        self.origin_true_anomaly_departure()..=self.target_true_anomaly_arrival() + revolutions
+    }
+}
+
+impl Conic for ResonantOrbit {
+    fn sma(&self) -> Distance {
+        self.sma
+    }
+
+    fn eccentricity(&self) -> f64 {
+        self.eccentricity()
+    }
+}
+
+// Inbound leg from the edge of the sphere of influence of the target, the periapsis on the orbit of the moon being on the x axis.
+impl Conic for MoonApproach {
+    fn sma(&self) -> Distance {
+        self.approach.sma
+    }
+
+    fn eccentricity(&self) -> f64 {
+        self.approach.eccentricity
+    }
+
+    fn range(&self) -> RangeInclusive<f64> {
+        self.entry_true_anomaly()..=0.0
+    }
+}
+
+// Polylines of the transfer diagram shared by every frontend, the largest orbit being of unit radius.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagram {
+    pub scale: f64,
+    pub origin: Vec<(f64, f64)>,
+    pub target: Vec<(f64, f64)>,
+    pub transfer: Vec<(f64, f64)>,
+    pub protractor: Vec<Vec<(f64, f64)>>,
+}
+
+impl Diagram {
+    pub fn new(transfer: &Transfer) -> Self {
+        let scale = transfer.origin().sma().max(transfer.target().sma()).m();
+        let protractor = Protractor::new(transfer.target_true_anomaly_departure(), 1.0);
+        Self {
+            scale,
+            origin: transfer.origin().polyline(scale),
+            target: transfer.target().polyline(scale),
+            transfer: transfer.polyline(scale),
+            protractor: vec![protractor.hypothenuse(), protractor.adjacent(), protractor.measure()],
+        }
+    }
+}
+
+// Samples a closed orbit in the frame of the parent, its inclination included, coordinates being divided by `scale`.
+pub fn orbit_3d(orbit: &Orbit, scale: f64, n: usize) -> Vec<Vec3> {
+    (0..=n)
+        .map(|i| orbit.with_true_anomaly(TAU * i as f64 / n as f64).to_state_vector().position / scale)
+        .collect()
+}
+
+// Polylines of the transfer in three dimensions, for frontends able to show the inclinations the top-down diagram
+// flattens. The transfer stays in the reference plane, the plane change being left to the arrival.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagram3d {
+    pub scale: f64,
+    pub origin: Vec<Vec3>,
+    pub target: Vec<Vec3>,
+    pub transfer: Vec<Vec3>,
+}
+
+impl Diagram3d {
+    pub fn new(transfer: &Transfer) -> Self {
+        let scale = transfer.origin().sma().max(transfer.target().sma()).m();
+        Self {
+            scale,
+            origin: orbit_3d(&transfer.origin().orbit(), scale, ORBIT_SAMPLES),
+            target: orbit_3d(&transfer.target().orbit(), scale, ORBIT_SAMPLES),
+            transfer: transfer.polyline(scale).into_iter().map(|point| Vec2::from(point).extend(0.0)).collect(),
+        }
+    }
+
+    // Position of the spacecraft at departure.
+    pub fn departure(&self) -> Vec3 {
+        self.transfer[0]
+    }
+
+    pub fn arrival(&self) -> Vec3 {
+        self.transfer[self.transfer.len() - 1]
+    }
+}
+
+// Frames rotating with the target, in which the approach to the target shows rather than being spread over its orbit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Frame {
+    // Centered on the target, the parent staying on the negative x axis.
+    Target,
+    // Centered on the parent, the target staying on the positive x axis.
+    Synodic,
+}
+
+// Position in a frame rotating with the target from one in the frame of the parent, given the position of the target.
+pub fn to_frame(frame: Frame, position: Vec2, target: Vec2) -> Vec2 {
+    let relative = match frame {
+        Frame::Target => position - target,
+        Frame::Synodic => position,
+    };
+    relative.rotated(-target.angle())
+}
+
+// Paths of the spacecraft and of the origin during the transfer in a frame rotating with the target, sampled evenly
+// in time. The planets move at their mean angular rate, as with the phase angle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeMotion {
+    pub spacecraft: Vec<(f64, f64)>,
+    pub origin: Vec<(f64, f64)>,
+    // Positions at arrival.
+    pub target: (f64, f64),
+    pub parent: (f64, f64),
+}
+
+impl RelativeMotion {
+    pub fn new(transfer: &Transfer, frame: Frame, scale: f64, n: usize) -> Self {
+        let (origin, target) = (transfer.origin(), transfer.target());
+        let time_of_flight = transfer.time_of_flight();
+        let position = |planet: &Planet, departure: f64, mean_motion: f64, time: Duration| {
+            Vec2::from(planet.position(departure + mean_motion * time.s(), scale))
+        };
+        let target_at = |time| position(&target, transfer.target_true_anomaly_departure(), transfer.target_mean_motion(), time);
+
+        let times = (0..=n).map(|i| time_of_flight * (i as f64 / n as f64));
+        let spacecraft = times.clone()
+            .map(|time| {
+                let true_anomaly = transfer.true_anomaly_at(time);
+                let position = Vec2::from_polar(transfer.radius_at(true_anomaly).m() / scale, true_anomaly);
+                to_frame(frame, position, target_at(time)).into()
+            })
+            .collect();
+        let origin = times
+            .map(|time| to_frame(frame, position(&origin, transfer.origin_true_anomaly_departure(), origin.mean_motion(), time), target_at(time)).into())
+            .collect();
+
+        let arrival = target_at(time_of_flight);
+        Self {
+            spacecraft,
+            origin,
+            target: to_frame(frame, arrival, arrival).into(),
+            parent: to_frame(frame, Vec2::default(), arrival).into(),
+        }
+    }
+}
+
+// Angle measurer from the positive x axis, the angle being wrapped to ]-PI, PI].
+#[derive(Copy, Clone, Debug)]
+pub struct Protractor {
+    angle: f64,
+    length: f64,
+    protrusion: f64,
+}
+
+impl Protractor {
+    pub fn new(angle: f64, length: f64) -> Self {
+        Self {
+            angle : (angle % TAU + TAU + PI) % TAU - PI,
+            length,
+            protrusion: 1.0 - 0.05,
+        }
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.angle
+    }
+
+    pub fn adjacent(&self) -> Vec<(f64, f64)> {
+        vec![(0.0, 0.0), (self.length, 0.0)]
+    }
+
+    pub fn hypothenuse(&self) -> Vec<(f64, f64)> {
+        vec![(0.0, 0.0), point(self.length, self.angle)]
+    }
+
+    pub fn measure(&self) -> Vec<(f64, f64)> {
+        orbit(self.length * self.protrusion, 0.0, 0.0..=self.angle, ORBIT_SAMPLES)
+    }
+
+    pub fn label_position(&self) -> (f64, f64) {
+        let text_length = (self.length * self.protrusion) * 0.9;
+        let text_angle = self.angle / 2.0;
+        let text_position = if self.angle.abs() > 0.3 {text_angle} else {text_angle + 0.25 * self.angle.signum()};
+        point(text_length, text_position)
+    }
+}
+
+// Positions of the spacecraft along the transfer at every multiple of `interval` before arrival, up to
+// `MAX_TIME_TICKS` of them. Empty for an interval that isn't positive or a time of flight that isn't finite.
+pub fn time_ticks(transfer: &Transfer, interval: Duration, scale: f64) -> Vec<((f64, f64), Duration)> {
+    let time_of_flight = transfer.time_of_flight();
+    if interval.s().is_nan() || interval.s() <= 0.0 || !time_of_flight.s().is_finite() {
+        return Vec::new();
+    }
+    (1..=MAX_TIME_TICKS)
+        .map(|i| interval * i as f64)
+        .take_while(|time| *time < time_of_flight)
+        .map(|time| {
+            let true_anomaly = transfer.true_anomaly_at(time);
+            (point(transfer.radius_at(true_anomaly).m() / scale, true_anomaly), time)
+        })
+        .collect()
+}
+
+// Positions of the apsides the spacecraft goes through during the transfer, with their distance to the parent.
+pub fn apsides(transfer: &Transfer, scale: f64) -> Vec<((f64, f64), Distance)> {
+    let (start, end) = (*transfer.range().start(), *transfer.range().end());
+    let periapsis = transfer.periapsis_true_anomaly();
+    let mut apsides = vec![(periapsis, transfer.periapsis())];
+    if let Some(apoapsis) = transfer.apoapsis() {
+        apsides.push((periapsis + PI, apoapsis));
+    }
+    apsides.into_iter()
+        .filter(|(angle, _)| {
+            // Angle travelled from departure to the first occurrence of the apsis, retrograde transfers going backwards.
+            let travelled = ((end - start).signum() * (angle - start)).rem_euclid(TAU);
+            travelled <= (end - start).abs()
+        })
+        .map(|(angle, distance)| (point(distance.m() / scale, angle), distance))
+        .collect()
+}
+
+// Speed of the spacecraft against time since departure, sampled evenly in time over the transfer.
+pub fn velocity_profile(transfer: &Transfer, n: usize) -> Vec<(Duration, Velocity)> {
+    let time_of_flight = transfer.time_of_flight();
+    (0..=n)
+        .map(|i| time_of_flight * (i as f64 / n as f64))
+        .map(|time| (time, transfer.velocity_at_true_anomaly(transfer.true_anomaly_at(time))))
+        .collect()
+}
@@ -0,0 +1,62 @@
+// Newton-Raphson solutions of Kepler's equation, elliptic (M = E - e sin E) and hyperbolic (M = e sinh H - H).
+
+use crate::diagnostics;
+use crate::solvers::{self, Tolerance};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+const TOLERANCE: f64 = 1E-12;
+const MAX_ITERATIONS: usize = 50;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+pub fn eccentric_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let root = solvers::newton(
+        |anomaly| anomaly - eccentricity * anomaly.sin() - mean_anomaly,
+        |anomaly| 1.0 - eccentricity * anomaly.cos(),
+        if eccentricity < 0.8 {mean_anomaly} else {mean_anomaly.signum() * core::f64::consts::PI},
+        Tolerance::new(TOLERANCE, MAX_ITERATIONS),
+    );
+    diagnostics::convergence("kepler", root.iterations, root.residual, root.converged);
+    root.x
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+pub fn hyperbolic_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let root = solvers::newton(
+        |anomaly| eccentricity * anomaly.sinh() - anomaly - mean_anomaly,
+        |anomaly| eccentricity * anomaly.cosh() - 1.0,
+        (2.0 * mean_anomaly / eccentricity).asinh(),
+        Tolerance::new(TOLERANCE, MAX_ITERATIONS),
+    );
+    diagnostics::convergence("kepler_hyperbolic", root.iterations, root.residual, root.converged);
+    root.x
+}
+
+pub fn solve(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    if eccentricity < 1.0 {
+        eccentric_anomaly(mean_anomaly, eccentricity)
+    } else {
+        hyperbolic_anomaly(mean_anomaly, eccentricity)
+    }
+}
+
+// Solves Kepler's equation for every mean anomaly of a slice, writing the anomalies to `output`
+// without any allocation, as needed when sweeping many transfers at once.
+pub fn solve_batch(mean_anomalies: &[f64], eccentricity: f64, output: &mut [f64]) {
+    assert_eq!(mean_anomalies.len(), output.len(), "Batch sizes differ!");
+    for (anomaly, &mean_anomaly) in output.iter_mut().zip(mean_anomalies) {
+        *anomaly = solve(mean_anomaly, eccentricity);
+    }
+}
+
+pub fn true_anomaly(anomaly: f64, eccentricity: f64) -> f64 {
+    if eccentricity.abs() < 1.0 {
+        2.0 * ((1.0 + eccentricity).sqrt() * (anomaly / 2.0).sin()).atan2((1.0 - eccentricity).sqrt() * (anomaly / 2.0).cos())
+    } else {
+        2.0 * (((eccentricity + 1.0) / (eccentricity - 1.0)).sqrt() * (anomaly / 2.0).tanh()).atan()
+    }
+}
+
+pub fn true_anomaly_from_mean(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    true_anomaly(solve(mean_anomaly, eccentricity), eccentricity)
+}
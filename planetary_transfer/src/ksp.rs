@@ -0,0 +1,247 @@
+use std::f64::consts::{TAU, PI};
+use std::fmt::{self, Display};
+use std::error::Error;
+
+use crate::{kepler, Distance, GravitationalParameter, Parent, Planet, Transfer};
+
+// Reading of Kerbal Space Program save files (.sfs), to fill transfers from the player's current orbits.
+// Only the stock solar system is known, bodies being referenced by their index in saves.
+// Orbits are treated as coplanar, the longitude of a body being the sum of its longitude of the
+// ascending node, argument of periapsis and true anomaly.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KspError {
+    Syntax(usize),
+    Missing(&'static str),
+    Value(String),
+    UnknownBody(usize),
+    DifferentParents,
+}
+
+impl Display for KspError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KspError::Syntax(line) => write!(f, "unbalanced braces at line {}", line),
+            KspError::Missing(key) => write!(f, "missing {} in save file", key),
+            KspError::Value(value) => write!(f, "invalid value {}", value),
+            KspError::UnknownBody(index) => write!(f, "unknown body with index {}", index),
+            KspError::DifferentParents => write!(f, "the vessel and its target orbit different bodies"),
+        }
+    }
+}
+
+impl Error for KspError {}
+
+// Node of KSP's configuration format, made of `key = value` pairs and named child nodes.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigNode {
+    pub name: String,
+    pub values: Vec<(String, String)>,
+    pub nodes: Vec<ConfigNode>,
+}
+
+impl ConfigNode {
+    pub fn parse(text: &str) -> Result<ConfigNode, KspError> {
+        let mut stack = vec![ConfigNode::default()];
+        let mut pending_name = String::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "{" {
+                stack.push(ConfigNode {
+                    name: std::mem::take(&mut pending_name),
+                    ..ConfigNode::default()
+                });
+            } else if line == "}" {
+                let node = stack.pop().filter(|_| !stack.is_empty()).ok_or(KspError::Syntax(number + 1))?;
+                stack.last_mut().ok_or(KspError::Syntax(number + 1))?.nodes.push(node);
+            } else if let Some((key, value)) = line.split_once('=') {
+                stack.last_mut().ok_or(KspError::Syntax(number + 1))?.values.push((key.trim().to_string(), value.trim().to_string()));
+            } else {
+                pending_name = line.to_string();
+            }
+        }
+
+        match stack.len() {
+            1 => Ok(stack.remove(0)),
+            _ => Err(KspError::Syntax(text.lines().count())),
+        }
+    }
+
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, value)| value.as_str())
+    }
+
+    pub fn node(&self, name: &str) -> Option<&ConfigNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    pub fn nodes<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ConfigNode> {
+        self.nodes.iter().filter(move |node| node.name == name)
+    }
+
+    fn parse_value<T: std::str::FromStr>(&self, key: &'static str) -> Result<T, KspError> {
+        let value = self.value(key).ok_or(KspError::Missing(key))?;
+        value.parse().map_err(|_| KspError::Value(value.to_string()))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct KspOrbit {
+    pub sma: f64,
+    pub eccentricity: f64,
+    // Longitude of the ascending node and argument of periapsis, in degrees.
+    pub lan: f64,
+    pub argument_of_periapsis: f64,
+    // Mean anomaly in radians at the epoch, in seconds of universal time.
+    pub mean_anomaly: f64,
+    pub epoch: f64,
+    pub reference: usize,
+}
+
+impl KspOrbit {
+    pub fn from_node(node: &ConfigNode) -> Result<Self, KspError> {
+        Ok(Self {
+            sma: node.parse_value("SMA")?,
+            eccentricity: node.parse_value("ECC")?,
+            lan: node.parse_value("LAN")?,
+            argument_of_periapsis: node.parse_value("LPE")?,
+            mean_anomaly: node.parse_value("MNA")?,
+            epoch: node.parse_value("EPH")?,
+            reference: node.parse_value("REF")?,
+        })
+    }
+
+    pub fn longitude(&self, ut: f64, gravitational_parameter: GravitationalParameter) -> f64 {
+        let mean_motion = (gravitational_parameter.m3ps2() / self.sma.abs().powi(3)).sqrt();
+        let true_anomaly = kepler::true_anomaly_from_mean(self.mean_anomaly + mean_motion * (ut - self.epoch), self.eccentricity);
+        ((self.lan + self.argument_of_periapsis).to_radians() + true_anomaly).rem_euclid(TAU)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KspBody {
+    pub name: &'static str,
+    pub gravitational_parameter: GravitationalParameter,
+    // None for the sun.
+    pub orbit: Option<KspOrbit>,
+}
+
+impl KspBody {
+    // Gravitational parameter in m³/s² as in the game files, the other values as in `KspOrbit`.
+    #[allow(clippy::too_many_arguments)]
+    fn new(name: &'static str, gravitational_parameter: f64, reference: usize, sma: f64, eccentricity: f64, lan: f64, argument_of_periapsis: f64, mean_anomaly: f64) -> Self {
+        Self {
+            name,
+            gravitational_parameter: GravitationalParameter::from_cubic_meters_per_second_squared(gravitational_parameter),
+            orbit: Some(KspOrbit { sma, eccentricity, lan, argument_of_periapsis, mean_anomaly, epoch: 0.0, reference }),
+        }
+    }
+
+    // Stock bodies, in the order of their index in save files.
+    // The game uses 3.14 rather than PI as mean anomaly at epoch.
+    #[allow(clippy::approx_constant)]
+    pub fn stock() -> Vec<KspBody> {
+        vec![
+            KspBody { name: "Kerbol", gravitational_parameter: GravitationalParameter::from_cubic_meters_per_second_squared(1.1723328E18), orbit: None },
+            Self::new("Kerbin", 3.5316E12, 0, 13_599_840_256.0, 0.0, 0.0, 0.0, 3.14),
+            Self::new("Mun", 6.5138398E10, 1, 12_000_000.0, 0.0, 0.0, 0.0, 1.7),
+            Self::new("Minmus", 1.7658E9, 1, 47_000_000.0, 0.0, 78.0, 38.0, 0.9),
+            Self::new("Moho", 1.6860938E11, 0, 5_263_138_304.0, 0.2, 70.0, 15.0, 3.14),
+            Self::new("Eve", 8.1717302E12, 0, 9_832_684_544.0, 0.01, 15.0, 0.0, 3.14),
+            Self::new("Duna", 3.0136321E11, 0, 20_726_155_264.0, 0.051, 135.5, 0.0, 3.14),
+            Self::new("Ike", 1.8568369E10, 6, 3_200_000.0, 0.03, 0.0, 0.0, 1.7),
+            Self::new("Jool", 2.82528E14, 0, 68_773_560_320.0, 0.05, 52.0, 0.0, 0.1),
+            Self::new("Laythe", 1.962E12, 8, 27_184_000.0, 0.0, 0.0, 0.0, 3.14),
+            Self::new("Vall", 2.074815E11, 8, 43_152_000.0, 0.0, 0.0, 0.0, 0.9),
+            Self::new("Bop", 2.4868349E9, 8, 128_500_000.0, 0.235, 10.0, 25.0, 0.9),
+            Self::new("Tylo", 2.82528E12, 8, 68_500_000.0, 0.0, 0.0, 0.0, 3.14),
+            Self::new("Gilly", 8.289449E6, 5, 31_500_000.0, 0.55, 80.0, 10.0, 0.9),
+            Self::new("Pol", 7.2170208E8, 8, 179_890_000.0, 0.171, 2.0, 15.0, 0.9),
+            Self::new("Dres", 2.1484489E10, 0, 40_839_348_203.0, 0.145, 280.0, 90.0, 3.14),
+            Self::new("Eeloo", 7.4410815E10, 0, 90_118_820_000.0, 0.26, 50.0, 260.0, 3.14),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KspVessel {
+    pub name: String,
+    pub orbit: KspOrbit,
+}
+
+#[derive(Clone, Debug)]
+pub struct KspSave {
+    pub ut: f64,
+    pub vessels: Vec<KspVessel>,
+    pub bodies: Vec<KspBody>,
+}
+
+impl KspSave {
+    pub fn parse(text: &str) -> Result<Self, KspError> {
+        let root = ConfigNode::parse(text)?;
+        let game = root.node("GAME").unwrap_or(&root);
+        let flight_state = game.node("FLIGHTSTATE").ok_or(KspError::Missing("FLIGHTSTATE"))?;
+
+        let vessels = flight_state.nodes("VESSEL")
+            .map(|vessel| Ok(KspVessel {
+                name: vessel.value("name").unwrap_or("").to_string(),
+                orbit: KspOrbit::from_node(vessel.node("ORBIT").ok_or(KspError::Missing("ORBIT"))?)?,
+            }))
+            .collect::<Result<Vec<_>, KspError>>()?;
+
+        Ok(Self {
+            ut: flight_state.parse_value("UT")?,
+            vessels,
+            bodies: KspBody::stock(),
+        })
+    }
+
+    pub fn vessel(&self, name: &str) -> Option<&KspVessel> {
+        self.vessels.iter().find(|vessel| vessel.name == name)
+    }
+
+    pub fn body(&self, name: &str) -> Option<&KspBody> {
+        self.bodies.iter().find(|body| body.name.eq_ignore_ascii_case(name))
+    }
+
+    fn gravitational_parameter(&self, reference: usize) -> Result<GravitationalParameter, KspError> {
+        self.bodies.get(reference).map(|body| body.gravitational_parameter).ok_or(KspError::UnknownBody(reference))
+    }
+
+    // Transfer from the vessel's orbit to the target's, with the phase angle it currently has to the target
+    // and the error to the ideal phase angle of the Hohmann transfer, both in radians within ]-PI, PI].
+    pub fn phase(&self, vessel: &KspVessel, target: &KspOrbit) -> Result<KspPhase, KspError> {
+        if vessel.orbit.reference != target.reference {
+            return Err(KspError::DifferentParents);
+        }
+        let gravitational_parameter = self.gravitational_parameter(vessel.orbit.reference)?;
+        let parent = Parent::from_gravitational_parameter(gravitational_parameter);
+
+        let transfer = Transfer::new(
+            Planet::new(Distance::from_meters(vessel.orbit.sma), parent),
+            Planet::new(Distance::from_meters(target.sma), parent),
+        );
+
+        let wrap = |angle: f64| (angle % TAU + TAU + PI) % TAU - PI;
+        let current = wrap(target.longitude(self.ut, gravitational_parameter) - vessel.orbit.longitude(self.ut, gravitational_parameter));
+        let ideal = wrap(transfer.target_true_anomaly_departure());
+
+        Ok(KspPhase {
+            transfer,
+            current,
+            ideal,
+            error: wrap(current - ideal),
+        })
+    }
+}
+
+pub struct KspPhase {
+    pub transfer: Transfer,
+    pub current: f64,
+    pub ideal: f64,
+    pub error: f64,
+}
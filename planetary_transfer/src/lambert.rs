@@ -0,0 +1,72 @@
+use core::f64::consts::{TAU, PI};
+
+use crate::diagnostics;
+use crate::solvers::{self, Tolerance};
+use crate::vector::Vec3;
+use crate::GravitationalParameter;
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+const BISECTIONS: usize = 200;
+
+// Universal variable solution of Lambert's problem for a prograde transfer of less than one revolution.
+// Positions are in meters and the time of flight in seconds, returning the velocities at both ends in m/s.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(r1, r2, gravitational_parameter)))]
+pub fn lambert(r1: Vec3, r2: Vec3, time_of_flight: f64, gravitational_parameter: GravitationalParameter) -> Option<(Vec3, Vec3)> {
+    let mu = gravitational_parameter.m3ps2();
+    let (r1_norm, r2_norm) = (r1.norm(), r2.norm());
+
+    let mut transfer_angle = r1.angle_to(r2);
+    if r1.cross(r2).z < 0.0 {
+        transfer_angle = TAU - transfer_angle;
+    }
+    if transfer_angle.abs() < 1E-12 || (transfer_angle - PI).abs() < 1E-12 || time_of_flight <= 0.0 {
+        // The plane of the transfer is undefined for collinear positions.
+        return None;
+    }
+
+    let a = transfer_angle.sin() * (r1_norm * r2_norm / (1.0 - transfer_angle.cos())).sqrt();
+    let y = |z: f64| r1_norm + r2_norm + a * (z * stumpff_s(z) - 1.0) / stumpff_c(z).sqrt();
+    let time = |z: f64| {
+        let y = y(z);
+        if y < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            ((y / stumpff_c(z)).powf(1.5) * stumpff_s(z) + a * y.sqrt()) / mu.sqrt()
+        }
+    };
+
+    // The time of flight increases with z, bounded by a full revolution for elliptical transfers. The bracket stops
+    // short of it, where the Stumpff function C rounds to zero and sends y to minus infinity for transfers longer than
+    // half a revolution.
+    let root = solvers::bisection(|z| time(z) - time_of_flight, -4.0 * TAU * TAU, TAU * TAU * (1.0 - 1E-6), Tolerance::new(0.0, BISECTIONS))?;
+    let z = root.x;
+    diagnostics::convergence("lambert", root.iterations, root.residual, root.residual.abs() <= time_of_flight * 1E-6);
+
+    let y = y(z);
+    let f = 1.0 - y / r1_norm;
+    let g = a * (y / mu).sqrt();
+    let g_dot = 1.0 - y / r2_norm;
+
+    Some(((r2 - r1 * f) / g, (r2 * g_dot - r1) / g))
+}
+
+fn stumpff_c(z: f64) -> f64 {
+    if z > 1E-8 {
+        (1.0 - z.sqrt().cos()) / z
+    } else if z < -1E-8 {
+        ((-z).sqrt().cosh() - 1.0) / -z
+    } else {
+        0.5 - z / 24.0
+    }
+}
+
+fn stumpff_s(z: f64) -> f64 {
+    if z > 1E-8 {
+        (z.sqrt() - z.sqrt().sin()) / z.powf(1.5)
+    } else if z < -1E-8 {
+        ((-z).sqrt().sinh() - (-z).sqrt()) / (-z).powf(1.5)
+    } else {
+        1.0 / 6.0 - z / 120.0
+    }
+}
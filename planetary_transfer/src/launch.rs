@@ -0,0 +1,103 @@
+use crate::{Distance, Parent, Velocity};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Surface pressure in kPa above which an atmosphere is thick enough to slow a lander down on its own.
+pub const SUBSTANTIAL_ATMOSPHERE: f64 = 5.0;
+
+// Atmosphere of a body, only its presence and thickness mattering to the rough estimates of this crate.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Atmosphere {
+    // Pressure at the surface in kPa.
+    pub surface_pressure: f64,
+    // Altitude above which drag is negligible.
+    pub height: Distance,
+}
+
+impl Atmosphere {
+    pub fn new(surface_pressure: f64, height: Distance) -> Self {
+        Self {
+            surface_pressure,
+            height,
+        }
+    }
+
+    pub fn substantial(&self) -> bool {
+        self.surface_pressure >= SUBSTANTIAL_ATMOSPHERE
+    }
+}
+
+// Rough surface to orbit launch, the losses being fractions of the ideal delta-v as rules of thumb for rockets.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Launch {
+    pub gravity_losses: f64,
+    // Only applied on bodies with an atmosphere.
+    pub drag_losses: f64,
+    // In radians, the launch being eastward.
+    pub latitude: f64,
+    pub rotation: bool,
+}
+
+impl Default for Launch {
+    fn default() -> Self {
+        Self {
+            gravity_losses: 0.15,
+            drag_losses: 0.02,
+            latitude: 0.0,
+            rotation: true,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LaunchEstimate {
+    pub ideal: Velocity,
+    pub gravity_losses: Velocity,
+    pub drag_losses: Velocity,
+    pub rotation_credit: Velocity,
+    pub total: Velocity,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LandingEstimate {
+    pub delta_v: Velocity,
+    // Whether the atmosphere does the braking, only a deorbit burn being needed.
+    pub aerobraking: bool,
+}
+
+impl Launch {
+    // None when the radius of the body is unknown, or its rotation when it is taken into account.
+    pub fn estimate(&self, body: &Parent, orbit: Distance) -> Option<LaunchEstimate> {
+        let ideal = body.launch_delta_v(orbit, self.latitude, false)?;
+        let gravity_losses = ideal * self.gravity_losses;
+        let drag_losses = if body.atmosphere().is_some() {ideal * self.drag_losses} else {Velocity::from_meters_per_second(0.0)};
+        let rotation_credit = if self.rotation {body.surface_speed(self.latitude)?} else {Velocity::from_meters_per_second(0.0)};
+        Some(LaunchEstimate {
+            ideal,
+            gravity_losses,
+            drag_losses,
+            rotation_credit,
+            total: ideal + gravity_losses + drag_losses - rotation_credit,
+        })
+    }
+
+    // Landing from a circular orbit, the reverse of a launch without drag losses. Bodies with a substantial atmosphere
+    // only take a burn lowering the periapsis to the surface, the atmosphere braking the lander.
+    // None when the orbit is below the surface or the radius of the body is unknown.
+    pub fn landing(&self, body: &Parent, orbit: Distance) -> Option<LandingEstimate> {
+        let radius = body.radius().filter(|&radius| radius < orbit)?;
+        if body.atmosphere().is_some_and(|atmosphere| atmosphere.substantial()) {
+            let mu = body.gravitational_parameter().m3ps2();
+            let apoapsis_speed = (2.0 * mu * radius.m() / (orbit.m() * (orbit.m() + radius.m()))).sqrt();
+            return Some(LandingEstimate {
+                delta_v: Velocity::from_meters_per_second((mu / orbit.m()).sqrt() - apoapsis_speed),
+                aerobraking: true,
+            });
+        }
+        let estimate = Launch { drag_losses: 0.0, ..*self }.estimate(body, orbit)?;
+        Some(LandingEstimate {
+            delta_v: estimate.total,
+            aerobraking: false,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use crate::Mass;
+
+// Payload a launch vehicle can inject against the characteristic energy of the departure.
+// The built-in curves are approximate readings of public performance charts and meant for rough sizing only.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LaunchVehicle {
+    pub name: String,
+    // Pairs of C3 in km²/s² and payload in kilograms, sorted by increasing C3.
+    pub curve: Vec<(f64, f64)>,
+}
+
+impl LaunchVehicle {
+    pub fn new(name: &str, curve: Vec<(f64, f64)>) -> Self {
+        Self {
+            name: name.to_string(),
+            curve,
+        }
+    }
+
+    pub fn catalog() -> Vec<LaunchVehicle> {
+        vec![
+            Self::new("Falcon 9", vec![(0.0, 5000.0), (10.0, 3900.0), (20.0, 2900.0), (30.0, 2000.0), (40.0, 1300.0), (60.0, 300.0)]),
+            Self::new("Atlas V 551", vec![(0.0, 6200.0), (10.0, 4900.0), (20.0, 3800.0), (30.0, 2900.0), (40.0, 2100.0), (60.0, 900.0)]),
+            Self::new("Delta IV Heavy", vec![(0.0, 9800.0), (10.0, 8000.0), (20.0, 6500.0), (30.0, 5200.0), (40.0, 4100.0), (60.0, 2300.0), (80.0, 1000.0)]),
+            Self::new("Falcon Heavy", vec![(0.0, 15000.0), (10.0, 12300.0), (20.0, 10000.0), (30.0, 8000.0), (40.0, 6300.0), (60.0, 3600.0), (80.0, 1700.0), (100.0, 500.0)]),
+        ]
+    }
+
+    // Linear interpolation of the curve, none if the C3 lies beyond it.
+    pub fn payload(&self, c3: f64) -> Option<Mass> {
+        let c3 = c3.max(self.curve.first()?.0);
+        self.curve.windows(2)
+            .find(|points| c3 >= points[0].0 && c3 <= points[1].0)
+            .map(|points| {
+                let ((c3_start, payload_start), (c3_end, payload_end)) = (points[0], points[1]);
+                Mass::from_kilograms(payload_start + (payload_end - payload_start) * (c3 - c3_start) / (c3_end - c3_start))
+            })
+    }
+
+    pub fn max_c3(&self) -> Option<f64> {
+        self.curve.last().map(|point| point.0)
+    }
+}
+
+// Launch vehicles file, as a list of `[[vehicle]]` tables each holding a `name` and a `curve` of [C3, payload] pairs.
+#[cfg(feature = "vehicles")]
+pub fn vehicles_from_toml(text: &str) -> Result<Vec<LaunchVehicle>, toml::de::Error> {
+    #[derive(serde::Deserialize)]
+    struct File {
+        vehicle: Vec<LaunchVehicle>,
+    }
+
+    toml::from_str::<File>(text).map(|file| file.vehicle)
+}
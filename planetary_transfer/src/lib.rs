@@ -1,56 +1,492 @@
-use std::f64::consts::{TAU, PI};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::f64::consts::{TAU, PI, SQRT_2};
+use core::ops::RangeInclusive;
+
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("Either the `std` or the `libm` feature is required for the floating point functions.");
 
 pub mod quantities;
 pub mod calculus;
+#[cfg(feature = "std")]
+pub mod catalog;
+#[cfg(feature = "std")]
+pub mod approach;
+#[cfg(feature = "std")]
+pub mod broken_plane;
+#[cfg(feature = "std")]
+pub mod calendar;
+#[cfg(feature = "std")]
+pub mod capture;
+pub mod kepler;
+#[cfg(feature = "std")]
+pub mod geometry;
+#[cfg(feature = "std")]
+pub mod launch_vehicle;
+#[cfg(feature = "std")]
+pub mod missions;
+#[cfg(feature = "std")]
+pub mod resonance;
+#[cfg(feature = "std")]
+pub mod sensitivity;
+pub mod vector;
+pub mod lambert;
+pub mod state;
+pub mod orbit;
+#[cfg(feature = "std")]
+pub mod maneuvers;
+#[cfg(feature = "std")]
+pub mod validation;
+#[cfg(feature = "std")]
+pub mod propagator;
+#[cfg(feature = "std")]
+pub mod parking;
+#[cfg(feature = "std")]
+pub mod perturbation;
+#[cfg(feature = "std")]
+pub mod forces;
+#[cfg(feature = "std")]
+pub mod station_keeping;
+#[cfg(feature = "std")]
+pub mod delta_v_map;
+#[cfg(feature = "std")]
+pub mod moon;
+#[cfg(feature = "std")]
+pub mod summary;
+#[cfg(feature = "std")]
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod derivation;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "files")]
+pub mod scenario;
+#[cfg(feature = "files")]
+pub mod system;
+#[cfg(feature = "files")]
+pub mod recording;
+pub mod binary;
+pub mod launch;
+#[cfg(feature = "std")]
+pub mod node;
+pub mod solvers;
+#[cfg(feature = "std")]
+pub mod optimizer;
+#[cfg(feature = "std")]
+pub mod deadline;
+#[cfg(feature = "std")]
+pub mod porkchop;
+#[cfg(feature = "std")]
+pub mod random;
+pub mod reference;
+mod diagnostics;
+#[cfg(not(feature = "std"))]
+mod math;
+#[cfg(feature = "ksp")]
+pub mod ksp;
+#[cfg(feature = "render")]
+pub mod render;
 pub use calculus::*;
 pub use quantities::*;
+#[cfg(feature = "std")]
+pub use approach::*;
+#[cfg(feature = "std")]
+pub use broken_plane::*;
+#[cfg(feature = "std")]
+pub use calendar::*;
+#[cfg(feature = "std")]
+pub use capture::*;
+#[cfg(feature = "std")]
+pub use launch_vehicle::*;
+#[cfg(feature = "std")]
+pub use missions::*;
+#[cfg(feature = "std")]
+pub use resonance::*;
+#[cfg(feature = "std")]
+pub use sensitivity::*;
+pub use vector::*;
+pub use state::*;
+pub use orbit::*;
+#[cfg(feature = "std")]
+pub use validation::*;
+#[cfg(feature = "std")]
+pub use parking::*;
+#[cfg(feature = "std")]
+pub use perturbation::*;
+#[cfg(feature = "std")]
+pub use forces::*;
+#[cfg(feature = "std")]
+pub use station_keeping::*;
+#[cfg(feature = "std")]
+pub use moon::*;
+#[cfg(feature = "std")]
+pub use summary::*;
+#[cfg(feature = "std")]
+pub use explain::*;
+#[cfg(feature = "std")]
+pub use derivation::*;
+#[cfg(feature = "std")]
+pub use builder::*;
+#[cfg(feature = "files")]
+pub use scenario::*;
+#[cfg(feature = "files")]
+pub use system::*;
+#[cfg(feature = "files")]
+pub use recording::*;
+pub use binary::*;
+pub use launch::*;
+#[cfg(feature = "std")]
+pub use node::*;
+#[cfg(feature = "std")]
+pub use optimizer::*;
+#[cfg(feature = "std")]
+pub use deadline::*;
+#[cfg(feature = "std")]
+pub use porkchop::*;
+#[cfg(feature = "std")]
+pub use random::*;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Parent {
     mass: Mass,
+    gravitational_parameter: GravitationalParameter,
+    radius: Option<Distance>,
+    rotation_period: Option<Duration>,
+    atmosphere: Option<Atmosphere>,
+    j2: Option<f64>,
+    binary: Option<Binary>,
+    luminosity: Option<f64>,
+    star_distance: Option<Distance>,
 }
 
 impl Parent {
     pub fn new(mass: Mass) -> Self {
         Parent {
             mass,
+            gravitational_parameter: mass.gravitational_parameter(),
+            radius: None,
+            rotation_period: None,
+            atmosphere: None,
+            j2: None,
+            binary: None,
+            luminosity: None,
+            star_distance: None,
+        }
+    }
+
+    // Same as `new`, but rejects a mass that isn't finite and positive, the parent then attracting nothing.
+    pub fn try_new(mass: Mass) -> Result<Self, QuantityError> {
+        if Mass::try_from_kilograms(mass.kg())?.kg() == 0.0 {
+            return Err(QuantityError::Zero);
         }
+        Ok(Self::new(mass))
+    }
+
+    // Game universes and ephemerides give the gravitational parameter directly, far more precisely than G×M.
+    pub fn from_gravitational_parameter(gravitational_parameter: GravitationalParameter) -> Self {
+        Self::new(Mass::from_gravitational_parameter(gravitational_parameter))
+    }
+
+    pub fn with_gravitational_constant(mut self, gravitational_constant: Finite) -> Self {
+        self.gravitational_parameter = GravitationalParameter::from_cubic_meters_per_second_squared(self.mass.kg() * gravitational_constant.get());
+        self
+    }
+
+    pub fn with_radius(mut self, radius: Distance) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    // Sidereal rotation period, negative for retrograde rotation.
+    pub fn with_rotation_period(mut self, rotation_period: Duration) -> Self {
+        self.rotation_period = Some(rotation_period);
+        self
+    }
+
+    pub fn with_atmosphere(mut self, atmosphere: Atmosphere) -> Self {
+        self.atmosphere = Some(atmosphere);
+        self
+    }
+
+    // Second zonal harmonic of the gravity field, from the flattening of the parent at its poles.
+    pub fn with_j2(mut self, j2: Finite) -> Self {
+        self.j2 = Some(j2.get());
+        self
+    }
+
+    // Luminosity of a star in solar luminosities, its light pushing spacecraft away from it.
+    pub fn with_luminosity(mut self, luminosity: Finite) -> Self {
+        self.luminosity = Some(luminosity.get());
+        self.star_distance = None;
+        self
+    }
+
+    // Star of a luminosity lighting the parent from a distance, far enough for spacecraft around the parent to all be
+    // lit as it is.
+    pub fn with_star(mut self, luminosity: Finite, distance: Distance) -> Self {
+        self.luminosity = Some(luminosity.get());
+        self.star_distance = Some(distance);
+        self
+    }
+
+    // Turns the parent into the primary of a binary, orbits around it feeling the combined mass of the pair.
+    pub fn with_companion(mut self, companion: Mass, separation: Distance, eccentricity: Finite) -> Self {
+        let binary = Binary::new(self.mass, companion, separation, eccentricity.get());
+        self.mass = binary.mass();
+        self.gravitational_parameter = self.gravitational_parameter + companion.gravitational_parameter();
+        self.binary = Some(binary);
+        self
+    }
+
+    pub fn mass(&self) -> Mass {
+        self.mass
+    }
+
+    pub fn radius(&self) -> Option<Distance> {
+        self.radius
+    }
+
+    pub fn rotation_period(&self) -> Option<Duration> {
+        self.rotation_period
+    }
+
+    pub fn atmosphere(&self) -> Option<Atmosphere> {
+        self.atmosphere
+    }
+
+    pub fn j2(&self) -> Option<f64> {
+        self.j2
+    }
+
+    pub fn binary(&self) -> Option<Binary> {
+        self.binary
+    }
+
+    // Luminosity of the star the parent is or is lit by, None for a dark parent.
+    pub fn luminosity(&self) -> Option<f64> {
+        self.luminosity
+    }
+
+    // None when the parent is the star itself.
+    pub fn star_distance(&self) -> Option<Distance> {
+        self.star_distance
+    }
+
+    // Radius of the circular orbit whose period matches the rotation of the parent, only known when it rotates.
+    pub fn synchronous_orbit(&self) -> Option<Distance> {
+        self.rotation_period.map(|period| Distance::from_meters((self.gravitational_parameter.m3ps2() * (period.s() / TAU).powi(2)).cbrt()))
+    }
+
+    // Eastward speed of the surface at a latitude in radians, negative for retrograde rotation.
+    // Only known when both the radius and the rotation of the parent are.
+    pub fn surface_speed(&self, latitude: f64) -> Option<Velocity> {
+        let (radius, period) = (self.radius?, self.rotation_period?);
+        Some(Velocity::from_meters_per_second(TAU * radius.m() * latitude.cos() / period.s()))
+    }
+
+    // Lower bound of the delta-v from the surface at a latitude in radians to a prograde circular orbit,
+    // ignoring gravity and drag losses. Launching eastward, the rotation of the parent can be taken into account.
+    pub fn launch_delta_v(&self, orbit: Distance, latitude: f64, rotation: bool) -> Option<Velocity> {
+        let radius = self.radius?;
+        let mu = self.gravitational_parameter.m3ps2();
+        let ideal = (2.0 * mu * (1.0 / radius.m() - 1.0 / orbit.m()) + mu / orbit.m()).sqrt();
+        let credit = if rotation {self.surface_speed(latitude)?.mps()} else {0.0};
+        Some(Velocity::from_meters_per_second(ideal - credit))
+    }
+
+    // Burn from a circular orbit onto an escape trajectory with a given excess velocity, the same as for a capture.
+    pub fn escape_delta_v(&self, orbit: Distance, v_infinity: Velocity) -> Velocity {
+        let mu = self.gravitational_parameter.m3ps2();
+        Velocity::from_meters_per_second((v_infinity.mps().powi(2) + 2.0 * mu / orbit.m()).sqrt() - (mu / orbit.m()).sqrt())
+    }
+
+    pub fn gravitational_parameter(&self) -> GravitationalParameter {
+        self.gravitational_parameter
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Planet {
-    sma: Distance,
-    parent: Parent,
+    orbit: Orbit,
+    mass: Option<Mass>,
+    retrograde: bool,
 }
 
 impl Planet {
     pub fn new(sma: Distance, parent: Parent) -> Self {
+        Self::from_orbit(Orbit::circular(sma, parent))
+    }
+
+    // Same as `new`, but rejects a semi-major axis that isn't finite and positive.
+    pub fn try_new(sma: Distance, parent: Parent) -> Result<Self, QuantityError> {
+        if Distance::try_from_meters(sma.m())?.m() == 0.0 {
+            return Err(QuantityError::Zero);
+        }
+        Ok(Self::new(sma, parent))
+    }
+
+    // Transfers only use the semi-major axis of the orbit, treating it as circular and coplanar.
+    pub fn from_orbit(orbit: Orbit) -> Self {
         Planet {
-            sma,
-            parent,
+            orbit,
+            mass: None,
+            retrograde: false,
         }
     }
 
+    pub fn from_period(period: Duration, parent: Parent) -> Self {
+        Self::new(Distance::from_meters((parent.gravitational_parameter.m3ps2() * (period.s() / TAU).powi(2)).cbrt()), parent)
+    }
+
+    // Planet on a circular orbit of given velocity.
+    pub fn from_orbital_velocity(velocity: Velocity, parent: Parent) -> Self {
+        Self::new(Distance::from_meters(parent.gravitational_parameter.m3ps2() / velocity.mps().powi(2)), parent)
+    }
+
+    // Planet orbiting at an altitude above the surface of the parent, only possible when its radius is known.
+    pub fn from_altitude(altitude: Distance, parent: Parent) -> Option<Self> {
+        parent.radius.map(|radius| Self::new(radius + altitude, parent))
+    }
+
+    pub fn altitude(&self) -> Option<Distance> {
+        self.orbit.parent.radius.map(|radius| self.sma() - radius)
+    }
+
+    pub fn with_mass(mut self, mass: Mass) -> Self {
+        self.mass = Some(mass);
+        self
+    }
+
+    // Same as `with_mass`, but rejects a mass that isn't finite or is negative.
+    pub fn try_with_mass(self, mass: Mass) -> Result<Self, QuantityError> {
+        Ok(self.with_mass(Mass::try_from_kilograms(mass.kg())?))
+    }
+
+    // Orbits against the rotation of the other planets, as Triton around Neptune, its orbit staying in the same plane.
+    pub fn with_retrograde(mut self, retrograde: bool) -> Self {
+        self.retrograde = retrograde;
+        self
+    }
+
+    pub fn retrograde(&self) -> bool {
+        self.retrograde
+    }
+
+    pub fn orbit(&self) -> Orbit {
+        self.orbit
+    }
+
+    fn orbiting(mut self, parent: Parent) -> Self {
+        self.orbit.parent = parent;
+        self
+    }
+
     pub fn sma(&self) -> Distance {
-        self.sma
+        self.orbit.sma
+    }
+
+    pub fn mass(&self) -> Option<Mass> {
+        self.mass
+    }
+
+    // Parent of the bodies orbiting this planet, one level down the hierarchy, only known when the planet has a mass.
+    pub fn as_parent(&self) -> Option<Parent> {
+        self.mass.map(Parent::new)
+    }
+
+    // Laplace sphere of influence, only known when the planet has a mass.
+    pub fn sphere_of_influence(&self) -> Option<Distance> {
+        self.mass.map(|mass| Distance::from_meters(self.orbit.sma.m() * (mass.kg() / self.orbit.parent.mass.kg()).powf(0.4)))
+    }
+
+    // Hill sphere at the periapsis of the orbit, only known when the planet has a mass.
+    // Nothing can orbit the planet beyond it, the parent pulling it away.
+    pub fn hill_sphere(&self) -> Option<Distance> {
+        self.mass.map(|mass| self.orbit.sma * ((1.0 - self.orbit.eccentricity) * (mass.gravitational_parameter() / (self.orbit.parent.mass.gravitational_parameter() * 3.0)).cbrt()))
+    }
+
+    // Whether a moon could orbit the planet at a given distance, unknown when the planet has no mass.
+    pub fn can_hold_moon(&self, moon_sma: Distance) -> Option<bool> {
+        self.hill_sphere().map(|hill_sphere| moon_sma < hill_sphere)
     }
 
     pub fn period(&self) -> f64 {
-        2.0 * PI * (self.sma.m.powi(3) / self.parent.mass.gravitational_parameter).sqrt()
+        TAU / self.orbit.mean_motion()
+    }
+
+    pub fn mean_motion(&self) -> f64 {
+        self.orbit.mean_motion()
+    }
+
+    // Mean motion in the direction of the other planets, negative for retrograde orbits.
+    pub fn angular_velocity(&self) -> f64 {
+        if self.retrograde {-self.mean_motion()} else {self.mean_motion()}
+    }
+
+    // Planets orbiting in opposite directions meet far more often, their mean motions adding up.
+    pub fn synodic_period(&self, other: &Planet) -> Duration {
+        Duration::from_seconds(TAU / (self.angular_velocity() - other.angular_velocity()).abs())
     }
 
     pub fn orbital_velocity(&self) -> Velocity {
-        Velocity::from_meters_per_second((self.parent.mass.gravitational_parameter / self.sma.m).sqrt())
+        self.orbit.parent.gravitational_parameter.circular_velocity(self.orbit.sma)
     }
 }
 
+// Relative difference between the semi-major axes of the origin and target below which they share an orbit, the
+// results of a transfer between them then no longer meaning anything.
+pub const DEGENERATE_SMA_TOLERANCE: f64 = 1E-9;
+// Relative difference between a radius and an apsis of the transfer orbit below which the radius sits at the apsis,
+// well below `DEGENERATE_SMA_TOLERANCE` for the origin and target never to both sit at the same one.
+pub const APSIS_TOLERANCE: f64 = 1E-12;
+// Excess of the cosine of a true anomaly beyond one still put down to rounding, the transfer orbit then reaching the
+// radius at an apsis.
+pub const REACH_TOLERANCE: f64 = 1E-9;
+// Headroom of the suggested range of delta-v, outward transfers then being suggested up to escaping the parent.
+pub const DEFAULT_HEADROOM: f64 = 1.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferError {
+    DifferentParents,
+    Quantity(QuantityError),
+    // The origin and target share an orbit, reaching the target being a matter of phasing along it rather than of
+    // transferring to another orbit.
+    DegenerateTransfer,
+    // Index of a body a system does not have, as read from a file.
+    UnknownBody(usize),
+}
+
+impl core::fmt::Display for TransferError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            TransferError::DifferentParents => write!(f, "the origin and target orbit different parents"),
+            TransferError::Quantity(error) => write!(f, "{}", error),
+            TransferError::DegenerateTransfer => write!(f, "the origin and target share an orbit, only phasing along it is needed"),
+            TransferError::UnknownBody(index) => write!(f, "there is no body {} in the system", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransferError {}
+
+impl From<QuantityError> for TransferError {
+    fn from(error: QuantityError) -> Self {
+        TransferError::Quantity(error)
+    }
+}
+
+#[derive(Copy, Clone)]
 pub struct Transfer {
     origin: Planet,
     target: Planet,
     parent: Parent,
     add_delta_v: Velocity,
-
+    revolutions: u32,
+    barycentric: bool,
 }
 
 impl Transfer {
@@ -58,13 +494,38 @@ impl Transfer {
         Transfer {
             origin,
             target,
-            parent: if origin.parent.mass.gravitational_parameter == target.parent.mass.gravitational_parameter {
-                        origin.parent
+            parent: if origin.orbit.parent.gravitational_parameter == target.orbit.parent.gravitational_parameter {
+                        origin.orbit.parent
                     } else {
                         panic!("Different parents!")
                     },
             add_delta_v: Velocity::from_meters_per_second(0.0),
+            revolutions: 0,
+            barycentric: false,
+        }
+    }
+
+    // Configures a transfer fluently, validating it at once rather than through each setter.
+    #[cfg(feature = "std")]
+    pub fn builder(origin: Planet, target: Planet) -> TransferBuilder {
+        TransferBuilder::new(origin, target)
+    }
+
+    // Same as `new`, but rejects planets whose semi-major axis or parent mass would make the transfer degenerate.
+    pub fn try_new(origin: Planet, target: Planet) -> Result<Transfer, TransferError> {
+        for planet in [origin, target] {
+            if Distance::try_from_meters(planet.orbit.sma.m())?.m() == 0.0 || Mass::try_from_kilograms(planet.orbit.parent.mass.kg())?.kg() == 0.0 {
+                return Err(QuantityError::Zero.into());
+            }
+        }
+        if origin.orbit.parent.gravitational_parameter != target.orbit.parent.gravitational_parameter {
+            return Err(TransferError::DifferentParents);
         }
+        let (origin_sma, target_sma) = (origin.orbit.sma.m(), target.orbit.sma.m());
+        if (origin_sma - target_sma).abs() <= DEGENERATE_SMA_TOLERANCE * origin_sma.abs().max(target_sma.abs()) {
+            return Err(TransferError::DegenerateTransfer);
+        }
+        Ok(Transfer::new(origin, target))
     }
 
     pub fn origin(&self) -> Planet {
@@ -75,8 +536,41 @@ impl Transfer {
         self.target
     }
 
+    // Return leg from the target back to the origin with the same number of revolutions, departing with as much
+    // delta-v beyond the Hohmann transfer as this one, within what the return leg allows.
+    pub fn reversed(&self) -> Transfer {
+        let mut transfer = Transfer::new(self.target.orbiting(self.parent), self.origin.orbiting(self.parent));
+        transfer.set_barycentric(self.barycentric);
+        let excess = if transfer.origin.sma() < transfer.target.sma() {self.add_delta_v.mps().abs()} else {-self.add_delta_v.mps().abs()};
+        let (min, max) = (transfer.min_velocity().mps(), transfer.max_velocity().mps());
+        let delta_v = (transfer.delta_v_hohmann().mps() + excess).clamp(min.min(max), min.max(max));
+        transfer.set_delta_v(Velocity::from_meters_per_second(delta_v));
+        transfer.set_revolutions(self.revolutions);
+        transfer
+    }
+
+    // The transfer is worked out with the origin orbiting in the positive direction, its angles being measured in the
+    // direction the origin orbits. The target may orbit the other way, its mean motion and velocity then being negative.
+    fn target_direction(&self) -> f64 {
+        if self.origin.retrograde == self.target.retrograde {1.0} else {-1.0}
+    }
+
+    pub fn target_mean_motion(&self) -> f64 {
+        self.target_direction() * self.target.mean_motion()
+    }
+
+    pub fn target_velocity(&self) -> Velocity {
+        self.target.orbital_velocity() * self.target_direction()
+    }
+
     pub fn velocity_hohmann(&self) -> Velocity {
-        self.origin.orbital_velocity() * ((2.0 * self.target.sma.m) / (self.origin.sma.m + self.target.sma.m)).sqrt()
+        self.circular_velocity(self.origin.sma()) * (self.target.sma() * 2.0 / (self.origin.sma() + self.target.sma())).sqrt()
+    }
+
+    // Speed of the circular orbit around the parent at a radius, which the speeds of the spacecraft scale with. The
+    // planets may orbit faster, around their barycenter with the parent.
+    fn circular_velocity(&self, radius: Distance) -> Velocity {
+        self.parent.gravitational_parameter.circular_velocity(radius)
     }
 
     pub fn delta_v_hohmann(&self) -> Velocity {
@@ -87,24 +581,99 @@ impl Transfer {
         self.add_delta_v = delta_v - self.delta_v_hohmann();
     }
 
+    // Rejects a delta-v that isn't finite or makes the results of the transfer so, leaving the transfer unchanged.
+    pub fn try_set_delta_v(&mut self, delta_v: Velocity) -> Result<(), QuantityError> {
+        let previous = self.add_delta_v;
+        self.set_delta_v(Velocity::try_from_meters_per_second(delta_v.mps())?);
+        self.check().inspect_err(|_| self.add_delta_v = previous)
+    }
+
+    // Whether the results every other one derives from are finite, which a delta-v far outside of the range
+    // of the transfer or degenerate planets can break.
+    pub fn check(&self) -> Result<(), QuantityError> {
+        self.try_launch_velocity()?;
+        self.try_sma()?;
+        self.try_eccentricity()?;
+        self.try_time_of_flight()?;
+        self.try_target_true_anomaly_departure()?;
+        Ok(())
+    }
+
+    // Results every other one derives from, as an error rather than NaN when they aren't finite.
+    pub fn try_launch_velocity(&self) -> Result<Velocity, QuantityError> {
+        Finite::new(self.launch_velocity().mps()).map(|velocity| Velocity::from_meters_per_second(velocity.get()))
+    }
+
+    pub fn try_sma(&self) -> Result<Distance, QuantityError> {
+        Finite::new(self.sma().m()).map(|sma| Distance::from_meters(sma.get()))
+    }
+
+    pub fn try_eccentricity(&self) -> Result<Finite, QuantityError> {
+        Finite::new(self.eccentricity())
+    }
+
+    pub fn try_time_of_flight(&self) -> Result<Duration, QuantityError> {
+        Finite::new(self.time_of_flight().s()).map(|time| Duration::from_seconds(time.get()))
+    }
+
+    pub fn try_target_true_anomaly_departure(&self) -> Result<Finite, QuantityError> {
+        Finite::new(self.target_true_anomaly_departure())
+    }
+
+    // Negative when the departure burn is retrograde enough to reverse the motion of the spacecraft.
     pub fn launch_velocity(&self) -> Velocity {
         self.velocity_hohmann() + self.add_delta_v
     }
 
+    // Whether the spacecraft orbits the parent in the opposite direction to the planets, its angles then decreasing.
+    pub fn retrograde(&self) -> bool {
+        self.launch_velocity().mps() < 0.0
+    }
+
+    fn direction(&self) -> f64 {
+        if self.retrograde() {-1.0} else {1.0}
+    }
+
+    // Vis-viva equation at departure, 1/a = 2/r - v²/μ.
     pub fn sma(&self) -> Distance {
-        Distance::from_meters((self.origin.sma.m * self.parent.mass.gravitational_parameter) / (2.0 * self.parent.mass.gravitational_parameter - self.origin.sma.m * self.launch_velocity().mps.powi(2)))
+        self.origin.sma() / (2.0 - (self.launch_velocity() / self.circular_velocity(self.origin.sma())).powi(2))
     }
 
     pub fn eccentricity(&self) -> f64 {
-        1.0 - self.origin.sma.m / self.sma().m
+        1.0 - self.origin.sma().m() / self.sma().m()
     }
 
+    // True anomaly at which the transfer orbit crosses a radius, NaN when it never gets there. Near an apsis, as the
+    // target of a Hohmann transfer, the inverse cosine would amplify the rounding of the orbit to its square root, so
+    // the apsides are matched by their radius instead. Elsewhere rounding only pushes the cosine out of its domain for
+    // near radial orbits, where it is clamped back.
     pub fn true_anomaly(&self, sma: Distance) -> f64 {
-        round_to((((self.sma().m * (1.0 - self.eccentricity().powi(2))) / sma.m) - 1.0) / (self.eccentricity()), 5).acos()
+        let (eccentricity, radius) = (self.eccentricity(), sma.m());
+        let semi_latus_rectum = self.sma().m() * (1.0 - eccentricity.powi(2));
+        let at_apsis = |true_anomaly_cos: f64| {
+            (semi_latus_rectum / (1.0 + eccentricity * true_anomaly_cos) - radius).abs() <= APSIS_TOLERANCE * radius
+        };
+        let true_anomaly_cos = (semi_latus_rectum / radius - 1.0) / eccentricity;
+        if at_apsis(1.0) {
+            0.0
+        } else if at_apsis(-1.0) {
+            PI
+        } else if true_anomaly_cos.abs() <= 1.0 + REACH_TOLERANCE {
+            true_anomaly_cos.clamp(-1.0, 1.0).acos()
+        } else {
+            f64::NAN
+        }
     }
 
+    // Cosine of the eccentric anomaly, or its hyperbolic cosine for open transfers, kept within the domain of the
+    // inverse functions the mean anomaly is found with.
     pub fn eccentric_anomaly_cos(&self, true_anomaly: f64) -> f64 {
-        (self.eccentricity() + true_anomaly.cos()) / (1.0 + self.eccentricity() * true_anomaly.cos())
+        let eccentric_anomaly_cos = (self.eccentricity() + true_anomaly.cos()) / (1.0 + self.eccentricity() * true_anomaly.cos());
+        if self.eccentricity().abs() < 1.0 {
+            eccentric_anomaly_cos.clamp(-1.0, 1.0)
+        } else {
+            eccentric_anomaly_cos.clamp(1.0, f64::INFINITY)
+        }
     }
 
     pub fn mean_anomaly(&self, eccentric_anomaly_cos: f64) -> f64 {
@@ -116,36 +685,244 @@ impl Transfer {
     }
 
     pub fn origin_true_anomaly_departure(&self) -> f64 {
-        self.true_anomaly(self.origin.sma)
+        self.true_anomaly(self.origin.sma())
     }
 
     pub fn target_true_anomaly_arrival(&self) -> f64 {
-        self.true_anomaly(self.target.sma)
+        self.direction() * self.true_anomaly(self.target.sma())
     }
 
     pub fn time_of_flight(&self) -> Duration {
         let mean_anomaly_departure = self.mean_anomaly(self.eccentric_anomaly_cos(self.origin_true_anomaly_departure()));
         let mean_anomaly_arrival = self.mean_anomaly(self.eccentric_anomaly_cos(self.target_true_anomaly_arrival()));
-        Duration::from_seconds((mean_anomaly_arrival - mean_anomaly_departure) * ((self.sma().m.abs().powi(3)) / self.parent.mass.gravitational_parameter).sqrt())  
+        // The mean motion is the circular velocity at the semi-major axis over the semi-major axis.
+        let sma = self.sma().abs();
+        let time_of_flight = sma / self.circular_velocity(sma) * (mean_anomaly_arrival - mean_anomaly_departure);
+        match self.period() {
+            Some(period) => time_of_flight + period * self.revolutions() as f64,
+            None => time_of_flight,
+        }
+    }
+
+    // Transfer orbit with the origin at departure on the x axis. The signed eccentricity of the
+    // transfer becomes an argument of periapsis of PI when the origin is at apoapsis, and retrograde
+    // transfers an inclination of PI.
+    pub fn orbit(&self) -> Orbit {
+        let (argument_of_periapsis, true_anomaly) = if self.eccentricity() < 0.0 {(PI, PI)} else {(0.0, 0.0)};
+        Orbit::new(self.sma(), self.eccentricity().abs(), self.parent)
+            .with_inclination(if self.retrograde() {PI} else {0.0})
+            .with_argument_of_periapsis(argument_of_periapsis)
+            .with_true_anomaly(true_anomaly)
+    }
+
+    // Period of the transfer orbit, only defined for elliptical transfers.
+    pub fn period(&self) -> Option<Duration> {
+        self.orbit().period()
+    }
+
+    // Number of full revolutions of the transfer orbit before the intercept, always zero for open transfers.
+    pub fn revolutions(&self) -> u32 {
+        if self.period().is_some() {self.revolutions} else {0}
+    }
+
+    pub fn set_revolutions(&mut self, revolutions: u32) {
+        self.revolutions = revolutions;
+    }
+
+    pub fn barycentric(&self) -> bool {
+        self.barycentric
+    }
+
+    // Moves the planets with a mass around their barycenter with the parent, the combined mass speeding them up,
+    // while the spacecraft keeps orbiting the parent alone. Only matters when the planets aren't negligible,
+    // as with Pluto and Charon.
+    pub fn set_barycentric(&mut self, barycentric: bool) {
+        self.barycentric = barycentric;
+        let parent = self.parent;
+        for planet in [&mut self.origin, &mut self.target] {
+            *planet = match planet.mass.filter(|_| barycentric) {
+                Some(mass) => planet.orbiting(Parent {
+                    mass: parent.mass + mass,
+                    gravitational_parameter: parent.gravitational_parameter + mass.gravitational_parameter(),
+                    ..parent
+                }),
+                None => planet.orbiting(parent),
+            };
+        }
+    }
+
+    // Time of flight of every solution with up to `max` revolutions, in increasing order of revolutions.
+    #[cfg(feature = "std")]
+    pub fn revolution_solutions(&self, max: u32) -> Vec<(u32, Duration)> {
+        let mut transfer = *self;
+        (0..=if self.period().is_some() {max} else {0})
+            .map(|revolutions| {
+                transfer.set_revolutions(revolutions);
+                (revolutions, transfer.time_of_flight())
+            })
+            .collect()
+    }
+
+    pub fn mean_motion(&self) -> f64 {
+        self.orbit().mean_motion()
+    }
+
+    // True anomaly of the spacecraft a given time after departure, solving Kepler's equation.
+    pub fn true_anomaly_at(&self, time: Duration) -> f64 {
+        let mean_anomaly_departure = self.mean_anomaly(self.eccentric_anomaly_cos(self.origin_true_anomaly_departure()));
+        self.direction() * kepler::true_anomaly_from_mean(mean_anomaly_departure + self.mean_motion() * time.s(), self.eccentricity())
+    }
+
+    pub fn radius_at(&self, true_anomaly: f64) -> Distance {
+        Distance::from_meters(self.sma().m() * (1.0 - self.eccentricity().powi(2)) / (1.0 + self.eccentricity() * true_anomaly.cos()))
+    }
+
+    // Specific orbital energy of the transfer, positive for open transfers.
+    pub fn specific_orbital_energy(&self) -> SpecificEnergy {
+        self.orbit().specific_orbital_energy()
+    }
+
+    pub fn angular_momentum(&self) -> SpecificAngularMomentum {
+        self.orbit().angular_momentum()
+    }
+
+    // Speed of the transfer at a given distance from the parent.
+    pub fn vis_viva_speed_at(&self, radius: Distance) -> Velocity {
+        self.orbit().vis_viva_speed_at(radius)
+    }
+
+    pub fn velocity_at_true_anomaly(&self, true_anomaly: f64) -> Velocity {
+        self.vis_viva_speed_at(self.radius_at(true_anomaly))
+    }
+
+    pub fn periapsis(&self) -> Distance {
+        self.orbit().periapsis()
+    }
+
+    // Only defined for elliptical transfers.
+    pub fn apoapsis(&self) -> Option<Distance> {
+        self.orbit().apoapsis()
+    }
+
+    // Angle of the periapsis from the origin at departure, on the x axis.
+    pub fn periapsis_true_anomaly(&self) -> f64 {
+        if self.eccentricity() < 0.0 {PI} else {0.0}
+    }
+
+    // Speed at the periapsis of the transfer orbit, which may lie outside of the transfer arc.
+    pub fn periapsis_velocity(&self) -> Velocity {
+        self.velocity_at_true_anomaly(if self.eccentricity() < 0.0 {PI} else {0.0})
+    }
+
+    // Speed at the apoapsis of the transfer orbit, only defined for elliptical transfers.
+    pub fn apoapsis_velocity(&self) -> Option<Velocity> {
+        if self.eccentricity().abs() < 1.0 {
+            Some(self.velocity_at_true_anomaly(if self.eccentricity() < 0.0 {0.0} else {PI}))
+        } else {
+            None
+        }
     }
 
     pub fn target_true_anomaly_departure(&self) -> f64 {
-        (self.target_true_anomaly_arrival() - TAU * self.time_of_flight().s / self.target.period()) % TAU
+        (self.target_true_anomaly_arrival() - self.target_mean_motion() * self.time_of_flight().s()) % TAU
     }
 
     pub fn origin_true_anomaly_arrival(&self) -> f64 {
-        (self.origin_true_anomaly_departure() + TAU * self.time_of_flight().s / self.origin.period()) % TAU
+        (self.origin_true_anomaly_departure() + TAU * self.time_of_flight().s() / self.origin.period()) % TAU
+    }
+
+    // Departure is tangential to the circular orbit of the origin, so the excess speed is the delta-v itself.
+    pub fn departure_v_infinity(&self) -> Velocity {
+        Velocity::from_meters_per_second((self.launch_velocity() - self.origin.orbital_velocity()).mps().abs())
+    }
+
+    // Characteristic energy of the departure, in km²/s².
+    pub fn c3(&self) -> f64 {
+        self.departure_v_infinity().kps().powi(2)
+    }
+
+    pub fn arrival_velocity(&self) -> Velocity {
+        self.vis_viva_speed_at(self.target.sma())
+    }
+
+    pub fn arrival_v_infinity(&self) -> Velocity {
+        let tangential = self.origin.sma().m() * self.launch_velocity().mps() / self.target.sma().m();
+        let radial = (self.arrival_velocity().mps().powi(2) - tangential.powi(2)).max(0.0).sqrt();
+        Velocity::from_meters_per_second((tangential - self.target_velocity().mps()).hypot(radial))
+    }
+
+    pub fn synodic_period(&self) -> Duration {
+        self.origin.synodic_period(&self.target)
+    }
+
+    // Time until the target leads the origin by the phase angle of this transfer, given the current phase angle in radians.
+    pub fn wait_time_until_window(&self, phase_angle: f64) -> Duration {
+        let relative_motion = self.target_mean_motion() - self.origin.mean_motion();
+        Duration::from_seconds(((self.target_true_anomaly_departure() - phase_angle) / relative_motion).rem_euclid(self.synodic_period().s()))
+    }
+
+    // Times from an epoch at which the target leads the origin by the phase angle of this transfer,
+    // given the phase angle at the epoch in radians.
+    #[cfg(feature = "std")]
+    pub fn launch_windows(&self, phase_angle: f64, count: usize) -> Vec<Duration> {
+        let first = self.wait_time_until_window(phase_angle);
+        (0..count)
+            .map(|i| first + self.synodic_period() * i as f64)
+            .collect()
     }
 
     pub fn min_velocity(&self) -> Velocity {
         self.delta_v_hohmann()
     }
 
+    // Furthest delta-v from the Hohmann transfer accepted when solving a transfer, the other end of the range being
+    // `min_velocity`. Inward transfers may burn past a standstill, down to leaving retrograde at the speed of the
+    // Hohmann transfer. Outward transfers can only be reached with more delta-v than the Hohmann transfer, up to the
+    // speed of the Hohmann transfer being raised by 60%, far into hyperbolic departures: frontends offer the narrower
+    // `suggested_delta_v_range` instead.
     pub fn max_velocity(&self) -> Velocity {
-        if self.origin.sma.au < self.target.sma.au {
+        if self.origin.sma() < self.target.sma() {
             self.delta_v_hohmann() + self.velocity_hohmann() * 0.6
         } else {
-            self.delta_v_hohmann() - self.velocity_hohmann() * 0.6
+            (self.origin.orbital_velocity() + self.velocity_hohmann()) * -1.0
+        }
+    }
+
+    // Delta-v of the burns reaching the target without the spacecraft going through the parent. Outward transfers
+    // reach it with any delta-v beyond the Hohmann transfer, the range being unbounded above. Inward ones reach it down
+    // to leaving retrograde at the speed of the Hohmann transfer, unless revolutions bring the spacecraft through a
+    // periapsis below the surface of the parent, which only prograde departures fast enough avoid.
+    pub fn valid_delta_v_range(&self) -> RangeInclusive<Velocity> {
+        let hohmann = self.delta_v_hohmann();
+        if self.origin.sma() < self.target.sma() {
+            return hohmann..=Velocity::from_meters_per_second(f64::INFINITY);
+        }
+        let slowest = match self.parent.radius {
+            // Speed at apoapsis of the orbit grazing the surface of the parent at periapsis.
+            Some(radius) if self.revolutions > 0 => {
+                let (mu, apoapsis) = (self.parent.gravitational_parameter.m3ps2(), self.origin.sma().m());
+                Velocity::from_meters_per_second((2.0 * mu * radius.m() / (apoapsis * (apoapsis + radius.m()))).sqrt())
+            }
+            _ => self.velocity_hohmann() * -1.0,
+        };
+        slowest - self.origin.orbital_velocity()..=hohmann
+    }
+
+    // Delta-v worth offering for this transfer, from the Hohmann transfer to a headroom of extra delta-v, in increasing
+    // order like `valid_delta_v_range`. The headroom is a fraction of the extra delta-v leaving on an escape trajectory
+    // for outward transfers, and of the whole valid range for inward ones, so that it scales with how far the target
+    // is: a headroom of one suggests outward transfers up to escaping the parent and inward ones down to the slowest
+    // reaching the target. Outward transfers go on past escape with more headroom, within `max_velocity`.
+    pub fn suggested_delta_v_range(&self, headroom: f64) -> RangeInclusive<Velocity> {
+        let headroom = headroom.max(0.0);
+        let hohmann = self.delta_v_hohmann();
+        if self.origin.sma() < self.target.sma() {
+            let escape = self.origin.orbital_velocity() * (SQRT_2 - 1.0);
+            let end = (hohmann + (escape - hohmann) * headroom).mps().min(self.max_velocity().mps());
+            hohmann..=Velocity::from_meters_per_second(end)
+        } else {
+            let slowest = *self.valid_delta_v_range().start();
+            hohmann + (slowest - hohmann) * headroom.min(1.0)..=hohmann
         }
     }
 }
\ No newline at end of file
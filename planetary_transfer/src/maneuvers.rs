@@ -0,0 +1,115 @@
+use std::f64::consts::PI;
+
+use crate::{Distance, Orbit, Parent, Velocity};
+
+// Single impulsive burns at the apsides of an elliptical orbit.
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Apsis {
+    Periapsis,
+    Apoapsis,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Maneuver {
+    // Apsis of the initial orbit at which the burn happens.
+    pub location: Apsis,
+    // Negative for retrograde burns.
+    pub delta_v: Velocity,
+    // Orbit right after the burn, its true anomaly at the burn location.
+    pub orbit: Orbit,
+}
+
+impl Orbit {
+    pub fn from_apsides(periapsis: Distance, apoapsis: Distance, parent: Parent) -> Self {
+        let (periapsis, apoapsis) = (periapsis.min(apoapsis), periapsis.max(apoapsis));
+        Orbit::new((periapsis + apoapsis) / 2.0, (apoapsis - periapsis).m() / (apoapsis + periapsis).m(), parent)
+    }
+
+    // Radius of the apsis, none for the apoapsis of open orbits.
+    pub fn apsis(&self, apsis: Apsis) -> Option<Distance> {
+        match apsis {
+            Apsis::Periapsis => Some(self.periapsis()),
+            Apsis::Apoapsis => self.apoapsis(),
+        }
+    }
+}
+
+// Burn at one apsis moving the opposite apsis to a new radius, none if the orbit is open or the radius isn't positive.
+pub fn change_apsis(orbit: &Orbit, location: Apsis, opposite: Distance) -> Option<Maneuver> {
+    let radius = orbit.apsis(location).filter(|_| orbit.eccentricity < 1.0)?;
+    if !(opposite.m() > 0.0 && opposite.m().is_finite()) {
+        return None;
+    }
+
+    // The burn point stays an apsis, but becomes the apoapsis when the opposite radius is lower.
+    let burn_at_periapsis = opposite >= radius;
+    let offset = if (location == Apsis::Periapsis) == burn_at_periapsis {0.0} else {PI};
+    let new_orbit = Orbit {
+        argument_of_periapsis: orbit.argument_of_periapsis + offset,
+        true_anomaly: if burn_at_periapsis {0.0} else {PI},
+        ..Orbit::from_apsides(radius, opposite, orbit.parent)
+            .with_inclination(orbit.inclination)
+            .with_raan(orbit.raan)
+    };
+
+    Some(Maneuver {
+        location,
+        delta_v: new_orbit.vis_viva_speed_at(radius) - orbit.vis_viva_speed_at(radius),
+        orbit: new_orbit,
+    })
+}
+
+// Burn at periapsis.
+pub fn change_apoapsis(orbit: &Orbit, apoapsis: Distance) -> Option<Maneuver> {
+    change_apsis(orbit, Apsis::Periapsis, apoapsis)
+}
+
+// Burn at apoapsis.
+pub fn change_periapsis(orbit: &Orbit, periapsis: Distance) -> Option<Maneuver> {
+    change_apsis(orbit, Apsis::Apoapsis, periapsis)
+}
+
+pub fn circularize(orbit: &Orbit, location: Apsis) -> Option<Maneuver> {
+    change_apsis(orbit, location, orbit.apsis(location)?)
+}
+
+// Velocity change rotating a velocity by an angle in radians without changing its magnitude.
+pub fn plane_change(velocity: Velocity, delta_inclination: f64) -> Velocity {
+    velocity * (2.0 * (delta_inclination / 2.0).sin().abs())
+}
+
+// Velocity change of a single burn going from one speed to another while rotating by an angle in radians,
+// always cheaper than performing both changes in separate burns.
+pub fn combined_burn(before: Velocity, after: Velocity, delta_inclination: f64) -> Velocity {
+    let (before, after) = (before.mps(), after.mps());
+    Velocity::from_meters_per_second((before.powi(2) + after.powi(2) - 2.0 * before * after * delta_inclination.cos()).max(0.0).sqrt())
+}
+
+// Pure plane change at an apsis, cheapest at the apoapsis where the orbit is slowest.
+pub fn change_plane(orbit: &Orbit, location: Apsis, delta_inclination: f64) -> Option<Maneuver> {
+    let radius = orbit.apsis(location).filter(|_| orbit.eccentricity < 1.0)?;
+    let speed = orbit.vis_viva_speed_at(radius);
+    let burn_at_periapsis = location == Apsis::Periapsis || orbit.eccentricity == 0.0;
+
+    Some(Maneuver {
+        location,
+        delta_v: plane_change(speed, delta_inclination),
+        orbit: orbit
+            .with_inclination(orbit.inclination + delta_inclination)
+            .with_true_anomaly(if burn_at_periapsis {0.0} else {PI}),
+    })
+}
+
+// Apsis change combined with a change of inclination in radians in the same burn,
+// the delta-v then being the magnitude of the velocity change.
+pub fn change_apsis_and_plane(orbit: &Orbit, location: Apsis, opposite: Distance, delta_inclination: f64) -> Option<Maneuver> {
+    let maneuver = change_apsis(orbit, location, opposite)?;
+    let radius = orbit.apsis(location)?;
+
+    Some(Maneuver {
+        delta_v: combined_burn(orbit.vis_viva_speed_at(radius), maneuver.orbit.vis_viva_speed_at(radius), delta_inclination),
+        orbit: maneuver.orbit.with_inclination(orbit.inclination + delta_inclination),
+        ..maneuver
+    })
+}
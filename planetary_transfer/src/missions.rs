@@ -0,0 +1,62 @@
+use crate::{Duration, Transfer};
+
+// Interplanetary missions that flew, for a transfer to be compared with.
+// Values are rounded from public mission documents and meant for rough comparison only, the missions flying
+// on eccentric and inclined orbits around launch periods the calculator doesn't model.
+#[derive(Clone, Debug)]
+pub struct Mission {
+    pub name: &'static str,
+    // Names of the bodies of the catalog the mission departed from and arrived at.
+    pub origin: &'static str,
+    pub target: &'static str,
+    // Dates of launch and arrival, as YYYY-MM-DD.
+    pub launch: &'static str,
+    pub arrival: &'static str,
+    // Characteristic energy of the departure, in km²/s².
+    pub c3: f64,
+    pub time_of_flight: Duration,
+}
+
+impl Mission {
+    fn new(name: &'static str, origin: &'static str, target: &'static str, launch: &'static str, arrival: &'static str, c3: f64, days: f64) -> Self {
+        Self {
+            name,
+            origin,
+            target,
+            launch,
+            arrival,
+            c3,
+            time_of_flight: Duration::from_days(days),
+        }
+    }
+
+    pub fn catalog() -> Vec<Mission> {
+        vec![
+            Self::new("Mariner 4", "Earth", "Mars", "1964-11-28", "1965-07-15", 8.7, 229.0),
+            Self::new("MSL", "Earth", "Mars", "2011-11-26", "2012-08-06", 16.0, 254.0),
+            Self::new("MAVEN", "Earth", "Mars", "2013-11-18", "2014-09-22", 12.2, 308.0),
+            Self::new("Perseverance", "Earth", "Mars", "2020-07-30", "2021-02-18", 14.5, 203.0),
+        ]
+    }
+
+    // Missions of the catalog between two bodies, named as in the catalog of bodies.
+    pub fn between(origin: &str, target: &str) -> Vec<Mission> {
+        Self::catalog().into_iter().filter(|mission| mission.origin == origin && mission.target == target).collect()
+    }
+
+    pub fn compare(&self, transfer: &Transfer) -> Comparison {
+        Comparison {
+            c3: transfer.c3() - self.c3,
+            time_of_flight: transfer.time_of_flight().s() - self.time_of_flight.s(),
+        }
+    }
+}
+
+// How much a transfer exceeds a mission by, negative when it falls short.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Comparison {
+    // In km²/s².
+    pub c3: f64,
+    // In seconds.
+    pub time_of_flight: f64,
+}
@@ -0,0 +1,50 @@
+use crate::{Distance, Orbit, Planet, Transfer, Velocity};
+
+// Last leg of a transfer to a moon of the target, inside the sphere of influence of the target.
+// The spacecraft arrives on the hyperbola of the arrival v-infinity whose periapsis lies on the circular orbit
+// of the moon, where it burns to match the velocity of the moon.
+#[derive(Copy, Clone, Debug)]
+pub struct MoonApproach {
+    pub moon: Planet,
+    pub approach: Orbit,
+    pub sphere_of_influence: Distance,
+    pub delta_v: Velocity,
+}
+
+impl Transfer {
+    // None when the target has no mass, when the moon is outside its sphere of influence,
+    // or when the spacecraft arrives without excess velocity.
+    pub fn moon_approach(&self, moon_sma: Distance) -> Option<MoonApproach> {
+        let target = self.target();
+        let host = target.as_parent()?;
+        let sphere_of_influence = target.sphere_of_influence()?;
+        let v_infinity = self.arrival_v_infinity().mps();
+        if moon_sma >= sphere_of_influence || v_infinity <= 0.0 {
+            return None;
+        }
+
+        let mu = host.gravitational_parameter().m3ps2();
+        let moon = Planet::new(moon_sma, host);
+        let approach = Orbit::new(
+            Distance::from_meters(-mu / v_infinity.powi(2)),
+            1.0 + moon_sma.m() * v_infinity.powi(2) / mu,
+            host,
+        );
+        let periapsis_velocity = (v_infinity.powi(2) + 2.0 * mu / moon_sma.m()).sqrt();
+
+        Some(MoonApproach {
+            moon,
+            approach,
+            sphere_of_influence,
+            delta_v: Velocity::from_meters_per_second(periapsis_velocity - moon.orbital_velocity().mps()),
+        })
+    }
+}
+
+impl MoonApproach {
+    // True anomaly on the approach at which the spacecraft enters the sphere of influence, negative as it is inbound.
+    pub fn entry_true_anomaly(&self) -> f64 {
+        let eccentricity = self.approach.eccentricity;
+        -((self.approach.semi_latus_rectum().m() / self.sphere_of_influence.m() - 1.0) / eccentricity).clamp(-1.0, 1.0).acos()
+    }
+}
@@ -0,0 +1,143 @@
+use crate::propagator::{propagate, two_body};
+use crate::solvers::{self, Tolerance};
+use crate::{Distance, Duration, Parent, StateVector, Transfer, Vec3, Velocity};
+
+pub const STANDARD_GRAVITY: f64 = 9.80665;
+
+const BURN_STEPS: usize = 200;
+// Steps of half the impulsive burn time searched for the end of the burn.
+const BURN_BRACKETS: usize = 18;
+// In seconds.
+const BURN_TIME_TOLERANCE: f64 = 1E-6;
+
+// Departure burn of a transfer as a maneuver node of Kerbal Space Program, executed by an engine of constant acceleration
+// from a circular parking orbit around the origin. The escape leaves from the periapsis of the hyperbola, so the burn
+// has no radial component; the normal component tilts the escape into the plane of the target.
+#[derive(Copy, Clone, Debug)]
+pub struct ManeuverNode {
+    // Negative when burning retrograde.
+    pub prograde: Velocity,
+    pub normal: Velocity,
+    pub radial: Velocity,
+    pub burn_time: Duration,
+    // Delta-v lost by spreading the burn around the node while pointing at a fixed direction.
+    pub finite_burn_loss: Velocity,
+}
+
+impl ManeuverNode {
+    // Escape from a parking orbit of a radius around the origin, acceleration of the engine in m/s², the inclination
+    // change in radians. None when the engine cannot escape in a single burn.
+    pub fn departure(transfer: &Transfer, origin: &Parent, parking_orbit: Distance, inclination: f64, acceleration: f64) -> Option<Self> {
+        let before = (origin.gravitational_parameter().m3ps2() / parking_orbit.m()).sqrt();
+        let after = before + origin.escape_delta_v(parking_orbit, transfer.departure_v_infinity()).mps();
+        let (prograde, normal) = (after * inclination.cos() - before, after * inclination.sin());
+        // The burn turns with the parking orbit, the finite burn of the same delta-v giving its length and loss.
+        let burn = FiniteBurn::new(origin, parking_orbit, Velocity::from_meters_per_second(prograde.hypot(normal)), acceleration / STANDARD_GRAVITY)?;
+
+        Some(Self {
+            prograde: Velocity::from_meters_per_second(prograde),
+            normal: Velocity::from_meters_per_second(normal),
+            radial: Velocity::from_meters_per_second(0.0),
+            burn_time: burn.burn_time,
+            finite_burn_loss: burn.loss(),
+        })
+    }
+
+    pub fn delta_v(&self) -> Velocity {
+        Velocity::from_meters_per_second(self.prograde.mps().hypot(self.normal.mps()).hypot(self.radial.mps()))
+    }
+
+    // Time before the node at which to start the burn, centering it on the node.
+    pub fn lead_time(&self) -> Duration {
+        self.burn_time / 2.0
+    }
+
+    pub fn to_text(&self) -> String {
+        format!(
+            "Prograde: {}\nNormal: {}\nRadial: {}\nBurn time: {}, starting {} before the node\nFinite burn loss: {}",
+            self.prograde.formatted(),
+            self.normal.formatted(),
+            self.radial.formatted(),
+            self.burn_time.formatted(),
+            self.lead_time().formatted(),
+            self.finite_burn_loss.formatted(),
+        )
+    }
+}
+
+// Burn of finite duration from a circular orbit compared to the impulsive assumption, integrated numerically
+// with the engine pointing at the prograde direction of the node throughout a burn centered on it.
+#[derive(Copy, Clone, Debug)]
+pub struct FiniteBurn {
+    // Negative when burning retrograde, as is the adjusted delta-v.
+    pub impulsive: Velocity,
+    // Delta-v actually spent reaching the orbital energy of the impulsive burn.
+    pub adjusted: Velocity,
+    pub burn_time: Duration,
+}
+
+impl FiniteBurn {
+    // Thrust-to-weight ratio relative to standard gravity, none when the burn cannot reach the orbital energy
+    // of the impulsive one or the parent has no mass.
+    pub fn new(parent: &Parent, orbit: Distance, delta_v: Velocity, thrust_to_weight: f64) -> Option<Self> {
+        let (mu, r) = (parent.gravitational_parameter().m3ps2(), orbit.m());
+        let acceleration = thrust_to_weight * STANDARD_GRAVITY;
+        if !(mu > 0.0 && r > 0.0 && acceleration > 0.0) {
+            return None;
+        }
+
+        if delta_v.mps() == 0.0 {
+            return Some(Self { impulsive: delta_v, adjusted: delta_v, burn_time: Duration::from_seconds(0.0) });
+        }
+
+        let speed = (mu / r).sqrt();
+        let direction = delta_v.mps().signum();
+        let target = (speed + delta_v.mps()).powi(2) / 2.0 - mu / r;
+        // Signed excess of orbital energy after burning for some time, positive once the target is reached.
+        let excess = |time: f64| {
+            let start = -(mu / r.powi(3)).sqrt() * time / 2.0;
+            let state = StateVector::new(
+                Vec3::new(r * start.cos(), r * start.sin(), 0.0),
+                Vec3::new(-speed * start.sin(), speed * start.cos(), 0.0),
+            );
+            let gravity = two_body(parent.gravitational_parameter());
+            let thrust = Vec3::new(0.0, direction * acceleration, 0.0);
+            let end = propagate(state, Duration::from_seconds(time), BURN_STEPS, |time, position| gravity(time, position) + thrust)
+                .last()
+                .copied()
+                .unwrap_or(state);
+            direction * (end.velocity.norm_squared() / 2.0 - mu / end.position.norm() - target)
+        };
+
+        // A finite burn is never more efficient than the impulsive one, bounding the search from below with some margin
+        // for the error of the integration. Past a revolution the engine points backwards, so the first time the orbital
+        // energy is reached is bracketed by stepping forward before refining it.
+        let impulsive_time = delta_v.mps().abs() / acceleration;
+        let mut low = 0.9 * impulsive_time;
+        let high = (0..=BURN_BRACKETS)
+            .map(|step| impulsive_time * (1.0 + step as f64 / 2.0))
+            .find(|&time| {
+                let reached = excess(time) >= 0.0;
+                if !reached {
+                    low = time;
+                }
+                reached
+            })?;
+        let burn_time = solvers::brent(excess, low, high, Tolerance::new(BURN_TIME_TOLERANCE, 100))?.x;
+
+        Some(Self {
+            impulsive: delta_v,
+            adjusted: Velocity::from_meters_per_second(direction * acceleration * burn_time),
+            burn_time: Duration::from_seconds(burn_time),
+        })
+    }
+
+    pub fn loss(&self) -> Velocity {
+        self.adjusted - self.impulsive
+    }
+
+    // Extra delta-v relative to the impulsive burn, as a fraction of it.
+    pub fn penalty(&self) -> f64 {
+        if self.impulsive.mps() == 0.0 {0.0} else {self.adjusted.mps() / self.impulsive.mps() - 1.0}
+    }
+}
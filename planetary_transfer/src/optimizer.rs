@@ -0,0 +1,57 @@
+use crate::solvers::{self, Tolerance};
+use crate::{Arrival, Transfer, Velocity};
+
+// Departure delta-v scanned before refining around the cheapest sample, inward transfers having
+// a local minimum of the total delta-v on each side of a standstill.
+const SAMPLES: usize = 64;
+// In m/s.
+const TOLERANCE: f64 = 1E-6;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Optimum {
+    // Delta-v as given to `Transfer::set_delta_v`, negative when burning retrograde.
+    pub delta_v: Velocity,
+    pub departure_delta_v: Velocity,
+    pub arrival_delta_v: Velocity,
+}
+
+impl Optimum {
+    pub fn total_delta_v(&self) -> Velocity {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+}
+
+impl Transfer {
+    // Departure minimizing the total delta-v of the departure and arrival burns, the departure delta-v staying within
+    // the bounds and the range of this transfer. The orbits being circular, the departure date only sets when the window
+    // opens and not its cost, leaving the departure delta-v as the only variable. None when capturing around a target
+    // without mass.
+    pub fn cheapest(&self, arrival: Arrival, low: Velocity, high: Velocity) -> Option<Optimum> {
+        let (min, max) = (self.min_velocity().mps(), self.max_velocity().mps());
+        let (min, max) = (min.min(max), min.max(max));
+        let (low, high) = (low.mps().min(high.mps()).clamp(min, max), low.mps().max(high.mps()).clamp(min, max));
+
+        let optimum = |delta_v: f64| {
+            let mut transfer = *self;
+            transfer.set_delta_v(Velocity::from_meters_per_second(delta_v));
+            Some(Optimum {
+                delta_v: Velocity::from_meters_per_second(delta_v),
+                departure_delta_v: Velocity::from_meters_per_second(delta_v.abs()),
+                arrival_delta_v: arrival.delta_v(&transfer.target(), transfer.arrival_v_infinity())?,
+            })
+        };
+        let total = |delta_v: f64| optimum(delta_v).map_or(f64::INFINITY, |optimum| optimum.total_delta_v().mps());
+
+        let step = (high - low) / SAMPLES as f64;
+        let best = (0..=SAMPLES)
+            .map(|i| low + step * i as f64)
+            .min_by(|a, b| total(*a).total_cmp(&total(*b)))?;
+        let minimum = solvers::golden_section(
+            total,
+            (best - step).max(low),
+            (best + step).min(high),
+            Tolerance::new(TOLERANCE, 200),
+        );
+        optimum(minimum.x)
+    }
+}
@@ -0,0 +1,148 @@
+use core::f64::consts::TAU;
+
+use crate::{Distance, Duration, Parent, SpecificAngularMomentum, SpecificEnergy, StateVector, Velocity, Vec3};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Conic defined by its Keplerian elements, angles in radians.
+// The inclination and longitude of the ascending node are measured from the reference plane and x axis
+// of the parent's frame, the argument of periapsis and true anomaly within the plane of the orbit.
+#[derive(Copy, Clone, Debug)]
+pub struct Orbit {
+    pub parent: Parent,
+    // Negative for hyperbolic orbits.
+    pub sma: Distance,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub raan: f64,
+    pub argument_of_periapsis: f64,
+    pub true_anomaly: f64,
+}
+
+impl Orbit {
+    pub fn new(sma: Distance, eccentricity: f64, parent: Parent) -> Self {
+        Orbit {
+            parent,
+            sma,
+            eccentricity,
+            inclination: 0.0,
+            raan: 0.0,
+            argument_of_periapsis: 0.0,
+            true_anomaly: 0.0,
+        }
+    }
+
+    pub fn circular(sma: Distance, parent: Parent) -> Self {
+        Self::new(sma, 0.0, parent)
+    }
+
+    pub fn with_inclination(mut self, inclination: f64) -> Self {
+        self.inclination = inclination;
+        self
+    }
+
+    pub fn with_raan(mut self, raan: f64) -> Self {
+        self.raan = raan;
+        self
+    }
+
+    pub fn with_argument_of_periapsis(mut self, argument_of_periapsis: f64) -> Self {
+        self.argument_of_periapsis = argument_of_periapsis;
+        self
+    }
+
+    pub fn with_true_anomaly(mut self, true_anomaly: f64) -> Self {
+        self.true_anomaly = true_anomaly;
+        self
+    }
+
+    pub fn from_state_vector(state: StateVector, parent: Parent) -> Self {
+        let mu = parent.gravitational_parameter().m3ps2();
+        let (position, velocity) = (state.position, state.velocity);
+
+        let momentum = position.cross(velocity);
+        let eccentricity = (position * (velocity.norm_squared() - mu / position.norm()) - velocity * position.dot(velocity)) / mu;
+        let energy = velocity.norm_squared() / 2.0 - mu / position.norm();
+
+        // Circular and equatorial orbits measure their angles from the x axis and the ascending node respectively.
+        let normal = momentum.normalized().unwrap_or(Vec3::new(0.0, 0.0, 1.0));
+        let node = Vec3::new(0.0, 0.0, 1.0).cross(momentum).normalized().unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+        let periapsis = if eccentricity.norm() > 1E-10 {eccentricity.normalized().unwrap_or(node)} else {node};
+        let in_plane_angle = |from: Vec3, to: Vec3| (from.cross(to).dot(normal)).atan2(from.dot(to)).rem_euclid(TAU);
+
+        Orbit {
+            parent,
+            sma: Distance::from_meters(-mu / (2.0 * energy)),
+            eccentricity: eccentricity.norm(),
+            inclination: (normal.z).clamp(-1.0, 1.0).acos(),
+            raan: node.y.atan2(node.x).rem_euclid(TAU),
+            argument_of_periapsis: in_plane_angle(node, periapsis),
+            true_anomaly: in_plane_angle(periapsis, position),
+        }
+    }
+
+    pub fn to_state_vector(&self) -> StateVector {
+        let (sin_raan, cos_raan) = self.raan.sin_cos();
+        let (sin_inclination, cos_inclination) = self.inclination.sin_cos();
+        let (sin_argument, cos_argument) = self.argument_of_periapsis.sin_cos();
+
+        // Directions of the periapsis and of 90° further along the orbit.
+        let p = Vec3::new(
+            cos_raan * cos_argument - sin_raan * sin_argument * cos_inclination,
+            sin_raan * cos_argument + cos_raan * sin_argument * cos_inclination,
+            sin_argument * sin_inclination,
+        );
+        let q = Vec3::new(
+            -cos_raan * sin_argument - sin_raan * cos_argument * cos_inclination,
+            -sin_raan * sin_argument + cos_raan * cos_argument * cos_inclination,
+            cos_argument * sin_inclination,
+        );
+
+        let (sin_anomaly, cos_anomaly) = self.true_anomaly.sin_cos();
+        let radius = self.radius_at(self.true_anomaly).m();
+        let speed = (self.parent.gravitational_parameter().m3ps2() / self.semi_latus_rectum().m()).sqrt();
+
+        StateVector::new(
+            (p * cos_anomaly + q * sin_anomaly) * radius,
+            (q * (self.eccentricity + cos_anomaly) - p * sin_anomaly) * speed,
+        )
+    }
+
+    pub fn semi_latus_rectum(&self) -> Distance {
+        self.sma * (1.0 - self.eccentricity.powi(2))
+    }
+
+    pub fn radius_at(&self, true_anomaly: f64) -> Distance {
+        self.semi_latus_rectum() / (1.0 + self.eccentricity * true_anomaly.cos())
+    }
+
+    pub fn periapsis(&self) -> Distance {
+        self.sma * (1.0 - self.eccentricity)
+    }
+
+    // Only defined for elliptical orbits.
+    pub fn apoapsis(&self) -> Option<Distance> {
+        (self.eccentricity < 1.0).then(|| self.sma * (1.0 + self.eccentricity))
+    }
+
+    pub fn period(&self) -> Option<Duration> {
+        (self.eccentricity < 1.0).then(|| Duration::from_seconds(TAU / self.mean_motion()))
+    }
+
+    pub fn mean_motion(&self) -> f64 {
+        (self.parent.gravitational_parameter().m3ps2() / self.sma.m().abs().powi(3)).sqrt()
+    }
+
+    pub fn specific_orbital_energy(&self) -> SpecificEnergy {
+        SpecificEnergy::from_joules_per_kilogram(-self.parent.gravitational_parameter().m3ps2() / (2.0 * self.sma.m()))
+    }
+
+    pub fn angular_momentum(&self) -> SpecificAngularMomentum {
+        SpecificAngularMomentum::from_square_meters_per_second((self.parent.gravitational_parameter().m3ps2() * self.semi_latus_rectum().m()).sqrt())
+    }
+
+    // Speed at a given distance from the parent, from the vis-viva equation.
+    pub fn vis_viva_speed_at(&self, radius: Distance) -> Velocity {
+        Velocity::from_meters_per_second((self.parent.gravitational_parameter().m3ps2() * (2.0 / radius.m() - 1.0 / self.sma.m())).sqrt())
+    }
+}
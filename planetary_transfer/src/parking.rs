@@ -0,0 +1,146 @@
+use std::f64::consts::{PI, TAU};
+
+use crate::solvers::{golden_section, Tolerance};
+use crate::{Distance, Duration, Parent, Vec2, Velocity};
+
+// Burn points sampled along the parking orbit before refining the cheapest one.
+const SAMPLES: usize = 360;
+const ANGLE_TOLERANCE: f64 = 1E-9;
+
+// Where on an elliptical parking orbit the escape burn happens.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BurnLocation {
+    Periapsis,
+    Apoapsis,
+    // Cheapest point of the orbit.
+    Optimal,
+}
+
+// Orbit around the origin the spacecraft departs from, possibly eccentric like a geostationary transfer orbit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ParkingOrbit {
+    pub periapsis: Distance,
+    pub apoapsis: Distance,
+    // Angle in radians from the periapsis to the direction the spacecraft must leave the origin along, in the direction
+    // of motion. None when the orbit can be lined up with it, by waiting or launching at the right time, the burns
+    // then being tangential.
+    pub asymptote_angle: Option<f64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EscapeBurn {
+    pub location: BurnLocation,
+    // True anomaly of the burn on the parking orbit.
+    pub true_anomaly: f64,
+    pub delta_v: Velocity,
+}
+
+impl ParkingOrbit {
+    pub fn new(periapsis: Distance, apoapsis: Distance) -> Self {
+        Self {
+            periapsis: periapsis.min(apoapsis),
+            apoapsis: periapsis.max(apoapsis),
+            asymptote_angle: None,
+        }
+    }
+
+    pub fn circular(radius: Distance) -> Self {
+        Self::new(radius, radius)
+    }
+
+    pub fn with_asymptote_angle(mut self, asymptote_angle: f64) -> Self {
+        self.asymptote_angle = Some(asymptote_angle);
+        self
+    }
+
+    pub fn sma(&self) -> Distance {
+        (self.periapsis + self.apoapsis) / 2.0
+    }
+
+    pub fn eccentricity(&self) -> f64 {
+        (self.apoapsis - self.periapsis).m() / (self.apoapsis + self.periapsis).m()
+    }
+
+    // Rate in rad/s at which the ascending node of the orbit turns from the oblateness of the parent, given the
+    // inclination of the orbit to its equator in radians. Negative for prograde orbits, their plane drifting westward
+    // away from the one the departure was lined up with. None when the J2 or the radius of the parent is unknown.
+    pub fn nodal_precession_rate(&self, parent: &Parent, inclination: f64) -> Option<f64> {
+        let (j2, radius) = (parent.j2()?, parent.radius()?);
+        let sma = self.sma().m();
+        let mean_motion = (parent.gravitational_parameter().m3ps2() / sma.powi(3)).sqrt();
+        let semi_latus_rectum = sma * (1.0 - self.eccentricity().powi(2));
+        Some(-1.5 * mean_motion * j2 * (radius.m() / semi_latus_rectum).powi(2) * inclination.cos())
+    }
+
+    // Angle in radians the ascending node turns by over a duration, as while waiting in orbit for a launch window.
+    pub fn nodal_drift(&self, parent: &Parent, inclination: f64, duration: Duration) -> Option<f64> {
+        Some(self.nodal_precession_rate(parent, inclination)? * duration.s())
+    }
+
+    // Burn from the orbit onto an escape trajectory with a given excess velocity around the parent.
+    pub fn escape(&self, parent: &Parent, v_infinity: Velocity, location: BurnLocation) -> EscapeBurn {
+        let burn = |true_anomaly: f64| EscapeBurn {
+            location,
+            true_anomaly,
+            delta_v: Velocity::from_meters_per_second(self.escape_delta_v(parent, v_infinity, true_anomaly)),
+        };
+        match (location, self.asymptote_angle) {
+            (BurnLocation::Periapsis, _) => burn(0.0),
+            (BurnLocation::Apoapsis, _) => burn(PI),
+            // With the asymptote free, burning deeper in the gravity well is always cheaper.
+            (BurnLocation::Optimal, None) => burn(0.0),
+            (BurnLocation::Optimal, Some(_)) => {
+                let step = TAU / SAMPLES as f64;
+                let best = (0..SAMPLES)
+                    .map(|i| i as f64 * step)
+                    .min_by(|&a, &b| self.escape_delta_v(parent, v_infinity, a).total_cmp(&self.escape_delta_v(parent, v_infinity, b)))
+                    .unwrap_or(0.0);
+                let refined = golden_section(
+                    |true_anomaly| self.escape_delta_v(parent, v_infinity, true_anomaly),
+                    best - step,
+                    best + step,
+                    Tolerance::new(ANGLE_TOLERANCE, 100),
+                );
+                burn(refined.x.rem_euclid(TAU))
+            }
+        }
+    }
+
+    // Escape burns at the periapsis, at the apoapsis and at the cheapest point, in this order.
+    pub fn escapes(&self, parent: &Parent, v_infinity: Velocity) -> [EscapeBurn; 3] {
+        [BurnLocation::Periapsis, BurnLocation::Apoapsis, BurnLocation::Optimal].map(|location| self.escape(parent, v_infinity, location))
+    }
+
+    // Delta-v in m/s of a burn at a true anomaly onto the escape hyperbola, tangential when the asymptote is free and
+    // otherwise onto the hyperbola through the burn point leaving along the asymptote.
+    fn escape_delta_v(&self, parent: &Parent, v_infinity: Velocity, true_anomaly: f64) -> f64 {
+        let mu = parent.gravitational_parameter().m3ps2();
+        let (sma, eccentricity) = (self.sma().m(), self.eccentricity());
+        let radius = sma * (1.0 - eccentricity.powi(2)) / (1.0 + eccentricity * true_anomaly.cos());
+
+        let asymptote_angle = match self.asymptote_angle {
+            Some(angle) if v_infinity.mps() > 0.0 => angle,
+            _ => {
+                let escape = (v_infinity.mps().powi(2) + 2.0 * mu / radius).sqrt();
+                return escape - (mu * (2.0 / radius - 1.0 / sma)).sqrt();
+            }
+        };
+
+        // Radial and tangential components of the velocity on the parking orbit.
+        let momentum = (mu * sma * (1.0 - eccentricity.powi(2))).sqrt();
+        let parking = Vec2::new(mu / momentum * eccentricity * true_anomaly.sin(), momentum / radius);
+
+        // The hyperbola through the burn point reaching the asymptote after turning by an angle in ]0, TAU[, of
+        // semi-major axis mu / v², solves r (1 - cos θ + s sin θ) = |a| s² for s = sqrt(e² - 1).
+        let turn = (asymptote_angle - true_anomaly).rem_euclid(TAU).max(f64::EPSILON);
+        let sma_hyperbola = mu / v_infinity.mps().powi(2);
+        let s = (radius * turn.sin() + (radius.powi(2) * turn.sin().powi(2) + 4.0 * sma_hyperbola * radius * (1.0 - turn.cos())).sqrt()) / (2.0 * sma_hyperbola);
+        let eccentricity_hyperbola = s.hypot(1.0);
+        let true_anomaly_hyperbola = (-1.0 / eccentricity_hyperbola).acos() - turn;
+        let momentum_hyperbola = (mu * sma_hyperbola * s.powi(2)).sqrt();
+        let escape = Vec2::new(mu / momentum_hyperbola * eccentricity_hyperbola * true_anomaly_hyperbola.sin(), momentum_hyperbola / radius);
+
+        (escape - parking).norm()
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::{self, Display};
+
+use crate::propagator::{propagate, two_body, PROPAGATION_STEPS};
+use crate::{Distance, Planet, Transfer, Vec2, Vec3};
+
+// Displacement at arrival, relative to the semi-major axis of the target, above which the perturbation matters.
+const PERTURBATION_THRESHOLD: f64 = 1E-3;
+
+// Effect of a third body on a transfer, estimated by integrating the trajectory with and without it.
+// Without ephemerides the third body is placed on its circular orbit by its longitude at departure,
+// measured like the true anomalies of the transfer from the origin at departure.
+#[derive(Copy, Clone, Debug)]
+pub struct Perturbation {
+    // Distance between the perturbed and unperturbed arrival positions.
+    pub displacement: Distance,
+    pub closest_approach: Distance,
+    pub significant: bool,
+}
+
+impl Display for Perturbation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The third body shifts the arrival by {} km, passing as close as {} km.", self.displacement.km().round(), self.closest_approach.km().round())
+    }
+}
+
+impl Transfer {
+    // None if the third body has no mass.
+    pub fn perturbation(&self, third_body: &Planet, longitude: f64) -> Option<Perturbation> {
+        let gravitational_parameter = self.parent.gravitational_parameter();
+        let third_body_mu = gravitational_parameter.m3ps2() * third_body.mass()?.kg() / self.parent.mass().kg();
+        let position = |time: f64| Vec2::from_polar(third_body.sma().m(), longitude + third_body.angular_velocity() * time).extend(0.0);
+
+        // The indirect term accounts for the acceleration of the parent by the third body.
+        let perturbed = move |time: f64, spacecraft: Vec3| {
+            let third_body = position(time);
+            let relative = third_body - spacecraft;
+            two_body(gravitational_parameter)(time, spacecraft)
+                + (relative / relative.norm().powi(3) - third_body / third_body.norm().powi(3)) * third_body_mu
+        };
+
+        let departure = self.orbit().to_state_vector();
+        let reference = propagate(departure, self.time_of_flight(), PROPAGATION_STEPS, two_body(gravitational_parameter));
+        let states = propagate(departure, self.time_of_flight(), PROPAGATION_STEPS, perturbed);
+
+        let step = self.time_of_flight().s() / PROPAGATION_STEPS as f64;
+        let closest_approach = states.iter()
+            .enumerate()
+            .map(|(i, state)| state.position.distance(position(i as f64 * step)))
+            .fold(f64::INFINITY, f64::min);
+        let displacement = states.last()?.position.distance(reference.last()?.position);
+
+        Some(Perturbation {
+            displacement: Distance::from_meters(displacement),
+            closest_approach: Distance::from_meters(closest_approach),
+            significant: displacement > self.target().sma().m() * PERTURBATION_THRESHOLD,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use std::f64::consts::FRAC_PI_2;
+use std::fmt::Write;
+
+use crate::lambert::lambert;
+use crate::{Duration, Planet, Vec2, Velocity};
+
+// Delta-v of the transfers between two planets over a grid of departure times and times of flight, solving Lambert's
+// problem between the positions of the planets on their circular orbits for each of them.
+#[derive(Clone, Debug)]
+pub struct Porkchop {
+    // Departure times from the epoch and times of flight along the axes of the grid.
+    pub departures: Vec<Duration>,
+    pub times_of_flight: Vec<Duration>,
+    // One row per departure time, None where Lambert's problem has no prograde solution of less than one revolution,
+    // as for transfers of exactly half a revolution whose plane is undefined.
+    pub points: Vec<Vec<Option<PorkchopPoint>>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PorkchopPoint {
+    pub departure_v_infinity: Velocity,
+    pub arrival_v_infinity: Velocity,
+}
+
+impl PorkchopPoint {
+    pub fn total(&self) -> Velocity {
+        self.departure_v_infinity + self.arrival_v_infinity
+    }
+}
+
+impl Porkchop {
+    // Grid of evenly spaced departure times and times of flight, both ends included, the target leading the origin by
+    // a phase angle in radians at the epoch.
+    pub fn new(origin: &Planet, target: &Planet, phase_angle: f64, departures: (Duration, Duration), times_of_flight: (Duration, Duration), steps: (usize, usize)) -> Self {
+        let departures = evenly_spaced(departures, steps.0);
+        let times_of_flight = evenly_spaced(times_of_flight, steps.1);
+        let gravitational_parameter = origin.orbit().parent.gravitational_parameter();
+
+        // Position and velocity of a planet on its circular orbit at a time from the epoch.
+        let state = |planet: &Planet, start: f64, time: Duration| {
+            let angle = start + planet.angular_velocity() * time.s();
+            let speed = planet.orbital_velocity().mps() * planet.angular_velocity().signum();
+            (Vec2::from_polar(planet.sma().m(), angle).extend(0.0), Vec2::from_polar(speed, angle).rotated(FRAC_PI_2).extend(0.0))
+        };
+
+        let points = departures.iter().map(|&departure| {
+            let (r1, v1) = state(origin, 0.0, departure);
+            times_of_flight.iter().map(|&time_of_flight| {
+                let (r2, v2) = state(target, phase_angle, departure + time_of_flight);
+                let (departure_velocity, arrival_velocity) = lambert(r1, r2, time_of_flight.s(), gravitational_parameter)?;
+                Some(PorkchopPoint {
+                    departure_v_infinity: Velocity::from_meters_per_second((departure_velocity - v1).norm()),
+                    arrival_v_infinity: Velocity::from_meters_per_second((arrival_velocity - v2).norm()),
+                })
+            }).collect()
+        }).collect();
+
+        Self { departures, times_of_flight, points }
+    }
+
+    // Cheapest transfer of the grid in total delta-v, with its departure time and time of flight.
+    pub fn cheapest(&self) -> Option<(Duration, Duration, PorkchopPoint)> {
+        self.departures.iter().zip(&self.points)
+            .flat_map(|(&departure, row)| self.times_of_flight.iter().zip(row).filter_map(move |(&time_of_flight, point)| point.map(|point| (departure, time_of_flight, point))))
+            .min_by(|(_, _, a), (_, _, b)| a.total().mps().total_cmp(&b.total().mps()))
+    }
+
+    // One line per point of the grid with its departure time and time of flight in days and its delta-v in m/s,
+    // the delta-v of points without a solution left empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("departure_days,time_of_flight_days,departure_v_infinity_mps,arrival_v_infinity_mps,total_mps\n");
+        for (departure, row) in self.departures.iter().zip(&self.points) {
+            for (time_of_flight, point) in self.times_of_flight.iter().zip(row) {
+                let _ = write!(csv, "{},{},", departure.d(), time_of_flight.d());
+                match point {
+                    Some(point) => {let _ = writeln!(csv, "{},{},{}", point.departure_v_infinity.mps(), point.arrival_v_infinity.mps(), point.total().mps());}
+                    None => csv.push_str(",,\n"),
+                }
+            }
+        }
+        csv
+    }
+
+    // Total delta-v in m/s as a matrix, one row per departure time and one column per time of flight, the first row
+    // and column holding the times in days. Points without a solution are NaN, as read by NumPy and Matlab.
+    pub fn to_grid_csv(&self) -> String {
+        let mut csv = String::from("departure_days\\time_of_flight_days");
+        for time_of_flight in &self.times_of_flight {
+            let _ = write!(csv, ",{}", time_of_flight.d());
+        }
+        csv.push('\n');
+        for (departure, row) in self.departures.iter().zip(&self.points) {
+            let _ = write!(csv, "{}", departure.d());
+            for point in row {
+                let _ = write!(csv, ",{}", point.map_or(f64::NAN, |point| point.total().mps()));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn evenly_spaced((start, end): (Duration, Duration), steps: usize) -> Vec<Duration> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![start],
+        _ => (0..steps).map(|i| start + (end - start) * (i as f64 / (steps - 1) as f64)).collect(),
+    }
+}
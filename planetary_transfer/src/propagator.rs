@@ -0,0 +1,116 @@
+use crate::{Distance, Duration, GravitationalParameter, StateVector, Transfer, Vec2, Vec3};
+
+// Fixed step fourth order Runge-Kutta integration, independent of the analytic Kepler solution.
+
+pub const PROPAGATION_STEPS: usize = 10_000;
+
+// Acceleration of a body at a position around a point mass with the given gravitational parameter.
+pub fn two_body(gravitational_parameter: GravitationalParameter) -> impl Fn(f64, Vec3) -> Vec3 {
+    let mu = gravitational_parameter.m3ps2();
+    move |_, position| position * (-mu / position.norm().powi(3))
+}
+
+// States at every step from the initial state over the duration, the acceleration being given
+// as a function of the time since the start in seconds and of the position.
+pub fn propagate(state: StateVector, duration: Duration, steps: usize, acceleration: impl Fn(f64, Vec3) -> Vec3) -> Vec<StateVector> {
+    propagate_state(state, duration, steps, |time, state| acceleration(time, state.position))
+}
+
+// Same as `propagate` for accelerations also depending on the velocity, as drag.
+pub fn propagate_state(state: StateVector, duration: Duration, steps: usize, acceleration: impl Fn(f64, StateVector) -> Vec3) -> Vec<StateVector> {
+    let h = duration.s() / steps.max(1) as f64;
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(state);
+    let mut state = state;
+    for i in 0..steps.max(1) {
+        state = runge_kutta_step(state, i as f64 * h, h, &acceleration);
+        states.push(state);
+    }
+    states
+}
+
+// State a step of duration h after the state at a time.
+fn runge_kutta_step(state: StateVector, time: f64, h: f64, acceleration: &impl Fn(f64, StateVector) -> Vec3) -> StateVector {
+    let derivative = |time: f64, state: StateVector| StateVector::new(state.velocity, acceleration(time, state));
+    let step = |state: StateVector, derivative: StateVector, h: f64| StateVector::new(
+        state.position + derivative.position * h,
+        state.velocity + derivative.velocity * h,
+    );
+    let k1 = derivative(time, state);
+    let k2 = derivative(time + h / 2.0, step(state, k1, h / 2.0));
+    let k3 = derivative(time + h / 2.0, step(state, k2, h / 2.0));
+    let k4 = derivative(time + h, step(state, k3, h));
+    StateVector::new(
+        state.position + (k1.position + k2.position * 2.0 + k3.position * 2.0 + k4.position) * (h / 6.0),
+        state.velocity + (k1.velocity + k2.velocity * 2.0 + k3.velocity * 2.0 + k4.velocity) * (h / 6.0),
+    )
+}
+
+pub struct Verification {
+    // Positions of the numerically integrated trajectory, in meters.
+    pub path: Vec<Vec3>,
+    pub analytic: Vec3,
+    pub numerical: Vec3,
+    // Distance between both arrival positions.
+    pub error: Distance,
+}
+
+// Numerical integration of a transfer done a number of steps at a time, so that it can be spread over several frames.
+pub struct Verifying {
+    transfer: Transfer,
+    steps: usize,
+    h: f64,
+    state: StateVector,
+    path: Vec<Vec3>,
+}
+
+impl Verifying {
+    // Does up to a number of steps more, returning the verification once all of them are done.
+    pub fn advance(&mut self, steps: usize) -> Option<Verification> {
+        let acceleration = two_body(self.transfer.parent.gravitational_parameter());
+        let done = self.path.len() - 1;
+        for i in done..(done + steps).min(self.steps) {
+            self.state = runge_kutta_step(self.state, i as f64 * self.h, self.h, &|time, state: StateVector| acceleration(time, state.position));
+            self.path.push(self.state.position);
+        }
+        if self.path.len() - 1 < self.steps {
+            return None;
+        }
+
+        let analytic = Vec2::from_polar(self.transfer.target().sma().m(), self.transfer.target_true_anomaly_arrival()).extend(0.0);
+        let numerical = *self.path.last().unwrap_or(&analytic);
+
+        Some(Verification {
+            path: self.path.clone(),
+            analytic,
+            numerical,
+            error: Distance::from_meters(analytic.distance(numerical)),
+        })
+    }
+}
+
+impl Transfer {
+    // Integrates the transfer numerically from departure and compares the arrival position to the analytic one.
+    pub fn verify(&self, steps: usize) -> Verification {
+        let mut verifying = self.verifying(steps);
+        loop {
+            if let Some(verification) = verifying.advance(steps) {
+                return verification;
+            }
+        }
+    }
+
+    // Same as `verify`, advanced by the caller.
+    pub fn verifying(&self, steps: usize) -> Verifying {
+        let state = self.orbit().to_state_vector();
+        let mut path = Vec::with_capacity(steps.max(1) + 1);
+        path.push(state.position);
+        Verifying {
+            transfer: *self,
+            steps: steps.max(1),
+            h: self.time_of_flight().s() / steps.max(1) as f64,
+            state,
+            path,
+        }
+    }
+}
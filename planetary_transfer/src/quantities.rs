@@ -1,9 +1,16 @@
-use std::ops::{Add, Sub, Mul, Div};
+use core::ops::{Add, Sub, Mul, Div};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
 use std::iter::once;
-use std::fmt::Debug;
 
-use crate::round_to;
-use crate::{Calculus, calculus};
+#[cfg(feature = "std")]
+use crate::{significant, Calendar, SIGNIFICANT_FIGURES};
+use crate::{Calculus, calculus, comparison};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
 
 pub const GRAVITATIONAL_CONSTANT: f64 = 6.67430E-11;
 pub const KILOGRAMS_LUNAR: f64 = 7.34767309E22;
@@ -16,17 +23,139 @@ pub const SECONDS_HOUR: f64 = 3600.0;
 pub const SECONDS_DAY: f64 = 86400.0;
 pub const SECONDS_MONTH: f64 = 2629746.0;
 pub const SECONDS_YEAR: f64 = 31556952.0;
+pub const SECONDS_SOL: f64 = 88775.244;
+pub const SECONDS_KERBIN_DAY: f64 = 21600.0;
+pub const SECONDS_KERBIN_YEAR: f64 = 426.0 * SECONDS_KERBIN_DAY;
 
 pub const METERS_AU: f64 = 149598023E3;
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum QuantityError {
+    NonFinite,
+    Negative,
+    Zero,
+}
+
+impl Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuantityError::NonFinite => write!(f, "quantity is not a finite number"),
+            QuantityError::Negative => write!(f, "quantity is negative"),
+            QuantityError::Zero => write!(f, "quantity is zero"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QuantityError {}
+
+// Number that is neither NaN nor infinite, checked once where it enters the library
+// so that a single bad value can't spread through every result computed from it.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Finite(f64);
+
+impl Finite {
+    pub fn new(value: f64) -> Result<Self, QuantityError> {
+        if value.is_finite() {
+            Ok(Self(value))
+        } else {
+            Err(QuantityError::NonFinite)
+        }
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Finite {
+    type Error = QuantityError;
+
+    fn try_from(value: f64) -> Result<Self, QuantityError> {
+        Self::new(value)
+    }
+}
+
+impl From<Finite> for f64 {
+    fn from(value: Finite) -> f64 {
+        value.0
+    }
+}
+
+fn finite(value: f64) -> Result<f64, QuantityError> {
+    Finite::new(value).map(Finite::get)
+}
+
+fn non_negative(value: f64) -> Result<f64, QuantityError> {
+    if finite(value)? < 0.0 {
+        Err(QuantityError::Negative)
+    } else {
+        Ok(value)
+    }
+}
+
+// Quantity readable and settable in any of its units, its value in every other unit staying consistent with it.
+pub trait Quantity: Copy {
+    type Unit: Copy;
+
+    fn in_unit(&self, unit: Self::Unit) -> f64;
+
+    fn from_unit(value: f64, unit: Self::Unit) -> Self;
+
+    fn set_in_unit(&mut self, value: f64, unit: Self::Unit) {
+        *self = Self::from_unit(value, unit);
+    }
+}
+
+// Getters of the values of a quantity in each of its units, which are only set together by its constructors, along
+// with the enum of its units. Getters are named after their field unless given another name.
+macro_rules! units {
+    ($t:ty, $unit:ident, $($field:ident $(as $getter:ident)?: $variant:ident => $constructor:ident),*) => (
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub enum $unit {
+            $($variant),*
+        }
+
+        impl $t {
+            $(getter!{$field $(, $getter)?})*
+        }
+
+        impl Quantity for $t {
+            type Unit = $unit;
+
+            fn in_unit(&self, unit: $unit) -> f64 {
+                match unit {
+                    $($unit::$variant => self.$field),*
+                }
+            }
+
+            fn from_unit(value: f64, unit: $unit) -> Self {
+                match unit {
+                    $($unit::$variant => Self::$constructor(value)),*
+                }
+            }
+        }
+    )
+}
+
+macro_rules! getter {
+    ($field:ident) => (getter!{$field, $field});
+    ($field:ident, $getter:ident) => (
+        pub fn $getter(&self) -> f64 {
+            self.$field
+        }
+    );
+}
+
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Duration {
-    pub s: f64,
-    pub min: f64,
-    pub h: f64,
-    pub d: f64,
-    pub m: f64,
-    pub y: f64,
+    s: f64,
+    min: f64,
+    h: f64,
+    d: f64,
+    m: f64,
+    y: f64,
 }
 
 impl Duration {
@@ -70,19 +199,23 @@ impl Duration {
             ..Self::from_seconds(duration * SECONDS_YEAR)
         }
     }
-}
 
-impl Duration {
-    pub fn round_to(mut self, decimal: usize) -> Duration {
-        self.s = round_to(self.s, decimal);
-        self.min = round_to(self.min, decimal);
-        self.h = round_to(self.h, decimal);
-        self.d = round_to(self.d, decimal);
-        self.m = round_to(self.m, decimal);
-        self.y = round_to(self.y, decimal);
-        self
+    pub fn try_from_seconds(duration: f64) -> Result<Self, QuantityError> {
+        non_negative(duration).map(Self::from_seconds)
+    }
+
+    pub fn try_from_days(duration: f64) -> Result<Self, QuantityError> {
+        non_negative(duration).map(Self::from_days)
+    }
+
+    pub fn try_from_years(duration: f64) -> Result<Self, QuantityError> {
+        non_negative(duration).map(Self::from_years)
     }
+}
 
+// Formatting needs the standard library.
+#[cfg(feature = "std")]
+impl Duration {
     fn iter_with_string(&self) -> Box<dyn Iterator<Item = (f64, String)> + '_> {
         Box::new(once((self.y, "years".to_string()))
         .chain(once((self.m, "months".to_string())))
@@ -92,7 +225,7 @@ impl Duration {
         .chain(once((self.s, "seconds".to_string()))))
     }
 
-    pub fn smallest_duration(&mut self) -> (f64, String) {
+    pub fn smallest_duration(&self) -> (f64, String) {
         let duration = self.iter_with_string().find(|item| item.0 >= 1.0);
         if let Some(value) = duration {
             value
@@ -101,20 +234,42 @@ impl Duration {
         }
     }
 
-    pub fn smallest_duration_formatted(&mut self) -> String {
-        let duration = self.smallest_duration();
-        format!("{} {}", duration.0, duration.1)
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
+    }
+
+    // In the largest unit the duration is at least one of, with a number of significant figures.
+    pub fn formatted_to(&self, figures: usize) -> String {
+        let (duration, unit) = self.smallest_duration();
+        format!("{} {}", significant(duration, figures), unit)
+    }
+
+    // In the days and years of a calendar, hours and smaller units staying those of Earth.
+    pub fn formatted_in(&self, calendar: &Calendar) -> String {
+        if *calendar == Calendar::EARTH {
+            return self.formatted();
+        }
+        let (duration, unit) = [(calendar.year, calendar.year_name), (Some(calendar.day), calendar.day_name)]
+            .into_iter()
+            .chain([(SECONDS_HOUR, "hours"), (SECONDS_MINUTE, "minutes")].map(|(seconds, unit)| (Some(seconds), unit)))
+            .filter_map(|(seconds, unit)| Some((self.s / seconds?, unit)))
+            .find(|(duration, _)| *duration >= 1.0)
+            .unwrap_or((self.s, "seconds"));
+        format!("{} {}", significant(duration, SIGNIFICANT_FIGURES), unit)
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Mass {
-    pub kg: f64,
-    pub lunar: f64,
-    pub earth: f64,
-    pub jovian: f64,
-    pub solar: f64,
-    pub gravitational_parameter: f64,
+    kg: f64,
+    lunar: f64,
+    earth: f64,
+    jovian: f64,
+    solar: f64,
+    // Only set along with the other fields, bodies being known by their gravitational parameter far more precisely
+    // than by their mass.
+    gravitational_parameter: GravitationalParameter,
 }
 
 impl Mass {
@@ -125,7 +280,7 @@ impl Mass {
             earth: mass / KILOGRAMS_EARTH,
             jovian: mass / KILOGRAMS_JOVIAN,
             solar: mass / KILOGRAMS_SOLAR,
-            gravitational_parameter: mass * GRAVITATIONAL_CONSTANT,
+            gravitational_parameter: GravitationalParameter::from_cubic_meters_per_second_squared(mass * GRAVITATIONAL_CONSTANT),
         }
     }
 
@@ -151,35 +306,185 @@ impl Mass {
             ..Self::from_kilograms(mass * KILOGRAMS_SOLAR)
         }
     }
+
+    // Mass of a body of given gravitational parameter, which is kept exact.
+    pub fn from_gravitational_parameter(gravitational_parameter: GravitationalParameter) -> Mass {
+        Self {
+            gravitational_parameter,
+            ..Self::from_kilograms(gravitational_parameter.m3ps2 / GRAVITATIONAL_CONSTANT)
+        }
+    }
+
+    // From a gravitational parameter in m³/s².
+    pub fn from_mu(mu: f64) -> Mass {
+        Self::from_gravitational_parameter(GravitationalParameter::from_cubic_meters_per_second_squared(mu))
+    }
+
+    pub fn gravitational_parameter(&self) -> GravitationalParameter {
+        self.gravitational_parameter
+    }
+
+    // Gravitational parameter in m³/s².
+    pub fn mu(&self) -> f64 {
+        self.gravitational_parameter.m3ps2
+    }
+
+    pub fn try_from_kilograms(mass: f64) -> Result<Mass, QuantityError> {
+        non_negative(mass).map(Self::from_kilograms)
+    }
+
+    pub fn try_from_mu(mu: f64) -> Result<Mass, QuantityError> {
+        non_negative(mu).map(Self::from_mu)
+    }
+
+    pub fn try_from_earth(mass: f64) -> Result<Mass, QuantityError> {
+        non_negative(mass).map(Self::from_earth)
+    }
+
+    pub fn try_from_solar(mass: f64) -> Result<Mass, QuantityError> {
+        non_negative(mass).map(Self::from_solar)
+    }
 }
 
-impl Mass {
-    pub fn kg_updated(&mut self) {
-        *self = Self::from_kilograms(self.kg);
+// Product of the gravitational constant and of the mass of a body, which orbits around it depend on.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GravitationalParameter {
+    m3ps2: f64,
+    km3ps2: f64,
+}
+
+// Shorter name of the gravitational parameter, μ in formulas.
+pub type GravParam = GravitationalParameter;
+
+impl GravitationalParameter {
+    pub fn from_cubic_meters_per_second_squared(gravitational_parameter: f64) -> GravitationalParameter {
+        Self {
+            m3ps2: gravitational_parameter,
+            km3ps2: gravitational_parameter / 1E9,
+        }
     }
 
-    pub fn lunar_updated(&mut self) {
-        *self = Self::from_lunar(self.lunar);
+    pub fn from_cubic_kilometers_per_second_squared(gravitational_parameter: f64) -> GravitationalParameter {
+        Self {
+            ..Self::from_cubic_meters_per_second_squared(gravitational_parameter * 1E9)
+        }
     }
 
-    pub fn earth_updated(&mut self) {
-        *self = Self::from_earth(self.earth);
+    pub fn try_from_cubic_meters_per_second_squared(gravitational_parameter: f64) -> Result<GravitationalParameter, QuantityError> {
+        non_negative(gravitational_parameter).map(Self::from_cubic_meters_per_second_squared)
     }
 
-    pub fn jovian_updated(&mut self) {
-        *self = Self::from_jovian(self.jovian);
+    pub fn try_from_cubic_kilometers_per_second_squared(gravitational_parameter: f64) -> Result<GravitationalParameter, QuantityError> {
+        non_negative(gravitational_parameter).map(Self::from_cubic_kilometers_per_second_squared)
     }
 
-    pub fn solar_updated(&mut self) {
-        *self = Self::from_solar(self.solar);
+    // Speed of a circular orbit of a given radius.
+    pub fn circular_velocity(&self, radius: Distance) -> Velocity {
+        Velocity::from_meters_per_second((self.m3ps2 / radius.m).sqrt())
     }
 }
 
-#[derive(Copy, Clone)]
+impl GravitationalParameter {
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn formatted_to(&self, figures: usize) -> String {
+        if self.km3ps2.abs() >= 1.0 {
+            format!("{} km³/s²", significant(self.km3ps2, figures))
+        } else {
+            format!("{} m³/s²", significant(self.m3ps2, figures))
+        }
+    }
+}
+
+// Energy of an orbit per unit of mass of the orbiting body, negative for closed orbits.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SpecificEnergy {
+    jpkg: f64,
+    km2ps2: f64,
+}
+
+impl SpecificEnergy {
+    pub fn from_joules_per_kilogram(energy: f64) -> SpecificEnergy {
+        Self {
+            jpkg: energy,
+            km2ps2: energy / 1E6,
+        }
+    }
+
+    pub fn from_square_kilometers_per_second_squared(energy: f64) -> SpecificEnergy {
+        Self {
+            ..Self::from_joules_per_kilogram(energy * 1E6)
+        }
+    }
+}
+
+impl SpecificEnergy {
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn formatted_to(&self, figures: usize) -> String {
+        if self.km2ps2.abs() >= 1.0 {
+            format!("{} km²/s²", significant(self.km2ps2, figures))
+        } else {
+            format!("{} J/kg", significant(self.jpkg, figures))
+        }
+    }
+}
+
+// Angular momentum of an orbit per unit of mass of the orbiting body.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SpecificAngularMomentum {
+    m2ps: f64,
+    km2ps: f64,
+}
+
+impl SpecificAngularMomentum {
+    pub fn from_square_meters_per_second(angular_momentum: f64) -> SpecificAngularMomentum {
+        Self {
+            m2ps: angular_momentum,
+            km2ps: angular_momentum / 1E6,
+        }
+    }
+
+    pub fn from_square_kilometers_per_second(angular_momentum: f64) -> SpecificAngularMomentum {
+        Self {
+            ..Self::from_square_meters_per_second(angular_momentum * 1E6)
+        }
+    }
+}
+
+impl SpecificAngularMomentum {
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn formatted_to(&self, figures: usize) -> String {
+        if self.km2ps.abs() >= 1.0 {
+            format!("{} km²/s", significant(self.km2ps, figures))
+        } else {
+            format!("{} m²/s", significant(self.m2ps, figures))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Distance {
-    pub m: f64,
-    pub km: f64,
-    pub au: f64,
+    m: f64,
+    km: f64,
+    au: f64,
 }
 
 impl Distance {
@@ -202,27 +507,44 @@ impl Distance {
             ..Self::from_meters(sma * METERS_AU)
         }
     }
-}
 
-impl Distance {
-    pub fn m_updated(&mut self) {
-        *self = Self::from_meters(self.m);
+    pub fn try_from_meters(sma: f64) -> Result<Distance, QuantityError> {
+        non_negative(sma).map(Self::from_meters)
     }
 
-    pub fn km_updated(&mut self) {
-        *self = Self::from_kilometers(self.km);
+    pub fn try_from_kilometers(sma: f64) -> Result<Distance, QuantityError> {
+        non_negative(sma).map(Self::from_kilometers)
     }
 
-    pub fn au_updated(&mut self) {
-        *self = Self::from_astronomical_unit(self.au);
+    pub fn try_from_astronomical_unit(sma: f64) -> Result<Distance, QuantityError> {
+        non_negative(sma).map(Self::from_astronomical_unit)
+    }
+}
+
+impl Distance {
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn formatted_to(&self, figures: usize) -> String {
+        if self.km.abs() > 7_500_000.0 {
+            format!("{} au", significant(self.au, figures))
+        } else if self.m.abs() > 100_000.0 {
+            format!("{} km", significant(self.km, figures))
+        } else {
+            format!("{} m", significant(self.m, figures))
+        }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Velocity {
-    pub mmps: f64,
-    pub mps: f64,
-    pub kps: f64,
+    mmps: f64,
+    mps: f64,
+    kps: f64,
 }
 
 impl Velocity {
@@ -245,19 +567,32 @@ impl Velocity {
             ..Self::from_meters_per_second(velocity * 1E3)
         }
     }
-}
 
-impl Velocity {
-    pub fn mmps_updated(&mut self) {
-        *self = Self::from_millimeters_per_second(self.mmps);
+    // Velocities may be negative, only non-finite values are rejected.
+    pub fn try_from_meters_per_second(velocity: f64) -> Result<Velocity, QuantityError> {
+        finite(velocity).map(Self::from_meters_per_second)
     }
 
-    pub fn mps_updated(&mut self) {
-        *self = Self::from_meters_per_second(self.mps);
+    pub fn try_from_kilometers_per_second(velocity: f64) -> Result<Velocity, QuantityError> {
+        finite(velocity).map(Self::from_kilometers_per_second)
+    }
+}
+
+impl Velocity {
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        self.formatted_to(SIGNIFICANT_FIGURES)
     }
 
-    pub fn kps_updated(&mut self) {
-        *self = Self::from_kilometers_per_second(self.kps);
+    #[cfg(feature = "std")]
+    pub fn formatted_to(&self, figures: usize) -> String {
+        if self.mps.abs() >= 1000.0 {
+            format!("{} km/s", significant(self.kps, figures))
+        } else if self.mmps.abs() >= 1000.0 {
+            format!("{} m/s", significant(self.mps, figures))
+        } else {
+            format!("{} mm/s", significant(self.mmps, figures))
+        }
     }
 }
 
@@ -296,4 +631,96 @@ impl Calculus for Velocity {
     }
 }
 
-calculus!{Duration, Distance, Velocity}
\ No newline at end of file
+// Masses are handled through their gravitational parameter, which sums exactly where their masses in kilograms
+// only come from dividing it by the gravitational constant.
+impl Calculus for Mass {
+    type Output = Mass;
+
+    fn base_quantity(&self) -> f64 {
+        self.gravitational_parameter.m3ps2
+    }
+
+    fn new(quantity: f64) -> Self {
+        Self::from_mu(quantity)
+    }
+}
+
+impl Calculus for GravitationalParameter {
+    type Output = GravitationalParameter;
+
+    fn base_quantity(&self) -> f64 {
+        self.m3ps2
+    }
+
+    fn new(quantity: f64) -> Self {
+        Self::from_cubic_meters_per_second_squared(quantity)
+    }
+}
+
+impl Calculus for SpecificEnergy {
+    type Output = SpecificEnergy;
+
+    fn base_quantity(&self) -> f64 {
+        self.jpkg
+    }
+
+    fn new(quantity: f64) -> Self {
+        Self::from_joules_per_kilogram(quantity)
+    }
+}
+
+impl Calculus for SpecificAngularMomentum {
+    type Output = SpecificAngularMomentum;
+
+    fn base_quantity(&self) -> f64 {
+        self.m2ps
+    }
+
+    fn new(quantity: f64) -> Self {
+        Self::from_square_meters_per_second(quantity)
+    }
+}
+
+calculus!{Duration, Distance, Velocity, Mass, GravitationalParameter, SpecificEnergy, SpecificAngularMomentum}
+comparison!{Duration, Distance, Velocity, Mass, GravitationalParameter, SpecificEnergy, SpecificAngularMomentum}
+
+// Quantities of different kinds combine into the one their units make, keeping formulas from mixing raw units.
+impl Div<Duration> for Distance {
+    type Output = Velocity;
+
+    fn div(self, rhs: Duration) -> Velocity {
+        Velocity::from_meters_per_second(self.m / rhs.s)
+    }
+}
+
+impl Div<Velocity> for Distance {
+    type Output = Duration;
+
+    fn div(self, rhs: Velocity) -> Duration {
+        Duration::from_seconds(self.m / rhs.mps)
+    }
+}
+
+impl Mul<Duration> for Velocity {
+    type Output = Distance;
+
+    fn mul(self, rhs: Duration) -> Distance {
+        Distance::from_meters(self.mps * rhs.s)
+    }
+}
+
+impl Mul<Velocity> for Duration {
+    type Output = Distance;
+
+    fn mul(self, rhs: Velocity) -> Distance {
+        rhs * self
+    }
+}
+
+units!{Duration, DurationUnit, s: Seconds => from_seconds, min as minutes: Minutes => from_minutes, h: Hours => from_hours, d: Days => from_days, m: Months => from_months, y: Years => from_years}
+units!{Mass, MassUnit, kg: Kilograms => from_kilograms, lunar: Lunar => from_lunar, earth: Earth => from_earth, jovian: Jovian => from_jovian, solar: Solar => from_solar}
+units!{GravitationalParameter, GravitationalParameterUnit, m3ps2: CubicMetersPerSecondSquared => from_cubic_meters_per_second_squared, km3ps2: CubicKilometersPerSecondSquared => from_cubic_kilometers_per_second_squared}
+units!{SpecificEnergy, SpecificEnergyUnit, jpkg: JoulesPerKilogram => from_joules_per_kilogram, km2ps2: SquareKilometersPerSecondSquared => from_square_kilometers_per_second_squared}
+units!{SpecificAngularMomentum, SpecificAngularMomentumUnit, m2ps: SquareMetersPerSecond => from_square_meters_per_second, km2ps: SquareKilometersPerSecond => from_square_kilometers_per_second}
+units!{Distance, DistanceUnit, m: Meters => from_meters, km: Kilometers => from_kilometers, au: AstronomicalUnits => from_astronomical_unit}
+units!{Velocity, VelocityUnit, mmps: MillimetersPerSecond => from_millimeters_per_second, mps: MetersPerSecond => from_meters_per_second, kps: KilometersPerSecond => from_kilometers_per_second}
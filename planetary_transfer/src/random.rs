@@ -0,0 +1,105 @@
+use crate::{Distance, Mass, Parent, Planet, Transfer, Velocity};
+
+// Kinds of parents systems are drawn around, each with its range of masses in its own unit and of orbits in units of
+// the scale of the system, the distance at which an orbit lasts a year around a parent of its mass.
+const KINDS: [Kind; 3] = [
+    Kind { name: "star", unit: Mass::from_solar, mass: (0.1, 10.0), sma: (0.2, 40.0) },
+    Kind { name: "gas giant", unit: Mass::from_jovian, mass: (0.1, 10.0), sma: (0.01, 0.5) },
+    Kind { name: "rocky planet", unit: Mass::from_earth, mass: (0.1, 10.0), sma: (0.01, 0.5) },
+];
+// Smallest ratio between the semi-major axes of the origin and of the target.
+const MIN_SMA_RATIO: f64 = 1.1;
+// Chance of a transfer faster than the Hohmann transfer, and the largest extra delta-v as a fraction of its own.
+const FAST_CHANCE: f64 = 0.5;
+const MAX_EXTRA_DELTA_V: f64 = 0.5;
+
+// Random but plausible system of a parent and two orbits around it, reproducible from its seed so that a surprising
+// case can be shared by its seed alone.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RandomSystem {
+    pub seed: u64,
+    pub kind: &'static str,
+    pub mass: Mass,
+    pub origin_sma: Distance,
+    pub target_sma: Distance,
+    // Delta-v added to the one of the Hohmann transfer, as a fraction of it.
+    pub extra_delta_v: f64,
+}
+
+impl RandomSystem {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = SplitMix64(seed);
+        let kind = &KINDS[(rng.next_f64() * KINDS.len() as f64) as usize];
+        let mass = (kind.unit)(rng.log_uniform(kind.mass.0, kind.mass.1));
+        let scale = Distance::from_astronomical_unit(mass.solar().cbrt());
+
+        let origin_sma = scale * rng.log_uniform(kind.sma.0, kind.sma.1);
+        let mut target_sma = scale * rng.log_uniform(kind.sma.0, kind.sma.1);
+        // Orbits too close to each other make for transfers taking ages to line up.
+        let ratio = target_sma.m() / origin_sma.m();
+        if ratio < MIN_SMA_RATIO && ratio > 1.0 / MIN_SMA_RATIO {
+            target_sma = origin_sma * if ratio >= 1.0 {MIN_SMA_RATIO} else {1.0 / MIN_SMA_RATIO};
+        }
+        let extra_delta_v = if rng.next_f64() < FAST_CHANCE {rng.next_f64() * MAX_EXTRA_DELTA_V} else {0.0};
+
+        Self { seed, kind: kind.name, mass, origin_sma, target_sma, extra_delta_v }
+    }
+
+    pub fn parent(&self) -> Parent {
+        Parent::new(self.mass)
+    }
+
+    // Delta-v of the transfer of the system, its extra delta-v kept within the range reaching the target.
+    pub fn delta_v(&self) -> Velocity {
+        let transfer = self.hohmann();
+        let range = transfer.valid_delta_v_range();
+        let delta_v = transfer.delta_v_hohmann().mps() * (1.0 + self.extra_delta_v);
+        Velocity::from_meters_per_second(delta_v.clamp(range.start().mps(), range.end().mps()))
+    }
+
+    pub fn transfer(&self) -> Transfer {
+        let mut transfer = self.hohmann();
+        transfer.set_delta_v(self.delta_v());
+        transfer
+    }
+
+    fn hohmann(&self) -> Transfer {
+        let parent = self.parent();
+        Transfer::new(Planet::new(self.origin_sma, parent), Planet::new(self.target_sma, parent))
+    }
+
+    // Seed following this one, for drawing systems one after the other.
+    pub fn next_seed(seed: u64) -> u64 {
+        SplitMix64(seed).next_u64()
+    }
+}
+
+struct Kind {
+    name: &'static str,
+    unit: fn(f64) -> Mass,
+    mass: (f64, f64),
+    sma: (f64, f64),
+}
+
+// SplitMix64 generator, small enough to keep the systems drawn from a seed the same across versions and platforms.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1[.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in logarithm between both bounds, spreading values evenly across scales.
+    fn log_uniform(&mut self, low: f64, high: f64) -> f64 {
+        (low.ln() + self.next_f64() * (high.ln() - low.ln())).exp()
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Duration, Leg, ParentEntry, ScenarioFile};
+
+// Parameters of a scenario at some point of a recorded session.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Keyframe {
+    // In seconds from the start of the recording.
+    pub time: f64,
+    pub parent: ParentEntry,
+    pub leg: Leg,
+}
+
+// Records how the parameters of a scenario are explored over a session, for tutorials or to reproduce a bug report.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recorder {
+    keyframes: Vec<Keyframe>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Records the parameters at a time from the start of the recording, only when they changed since the last
+    // keyframe so that it can be called every frame. Returns whether they did.
+    pub fn record(&mut self, time: Duration, parent: ParentEntry, leg: Leg) -> bool {
+        if matches!(self.keyframes.last(), Some(last) if last.parent == parent && last.leg == leg) {
+            return false;
+        }
+        self.keyframes.push(Keyframe { time: time.s(), parent, leg });
+        true
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    // Scenario of the parameters at the start of the recording, replaying the changes that followed. None when nothing
+    // was recorded.
+    pub fn finish(self, name: &str) -> Option<ScenarioFile> {
+        let first = self.keyframes.first()?;
+        let mut file = ScenarioFile::new(name, first.parent.clone(), first.leg.clone());
+        file.recording = self.keyframes;
+        Some(file)
+    }
+}
+
+// Plays back the recording of a scenario, handing out its keyframes as their time comes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    keyframes: Vec<Keyframe>,
+    next: usize,
+}
+
+impl Replay {
+    // None for scenarios without a recording.
+    pub fn new(file: &ScenarioFile) -> Option<Self> {
+        if file.recording.is_empty() {
+            return None;
+        }
+        let mut keyframes = file.recording.clone();
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Some(Self { keyframes, next: 0 })
+    }
+
+    // Latest keyframe due by a time from the start of the replay not handed out yet, those passed over between two
+    // calls being skipped since only the latest one is shown.
+    pub fn advance(&mut self, time: Duration) -> Option<&Keyframe> {
+        let due = self.keyframes[self.next..].iter().take_while(|keyframe| keyframe.time <= time.s()).count();
+        if due == 0 {
+            return None;
+        }
+        self.next += due;
+        Some(&self.keyframes[self.next - 1])
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next == self.keyframes.len()
+    }
+
+    // Time of the last keyframe from the start of the replay.
+    pub fn duration(&self) -> Duration {
+        Duration::from_seconds(self.keyframes[self.keyframes.len() - 1].time)
+    }
+}
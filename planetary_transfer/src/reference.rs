@@ -0,0 +1,128 @@
+use crate::{Distance, Mass, Parent, Planet, Transfer, Velocity};
+
+// Canonical transfers with their expected results, which both the test suite and downstream users can assert against.
+// A result moving beyond its tolerance is a breaking change, even when it comes from an improvement of the solvers.
+
+// Relative tolerance of the results in closed form, only limited by rounding.
+pub const ANALYTIC_TOLERANCE: f64 = 1E-12;
+// Relative tolerance of the results going through Kepler's equation, solved to 1E-12 radians.
+pub const ITERATIVE_TOLERANCE: f64 = 1E-9;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Expected {
+    // Delta-v of the departure burn in m/s, negative when burning retrograde, within `ANALYTIC_TOLERANCE`.
+    pub delta_v: f64,
+    // In seconds, within `ANALYTIC_TOLERANCE`.
+    pub time_of_flight: f64,
+    // Excess velocity on reaching the target in m/s, within `ANALYTIC_TOLERANCE`.
+    pub arrival_v_infinity: f64,
+    // Angle in radians by which the target leads the origin at departure, within `ANALYTIC_TOLERANCE`.
+    pub phase_angle: f64,
+    // Distance from the parent in meters halfway through the flight, within `ITERATIVE_TOLERANCE`.
+    pub halfway_radius: f64,
+}
+
+impl Expected {
+    // Results of any transfer, to be compared to the expected ones.
+    pub fn of(transfer: &Transfer) -> Self {
+        Self {
+            delta_v: (transfer.launch_velocity() - transfer.origin().orbital_velocity()).mps(),
+            time_of_flight: transfer.time_of_flight().s(),
+            arrival_v_infinity: transfer.arrival_v_infinity().mps(),
+            phase_angle: transfer.target_true_anomaly_departure(),
+            halfway_radius: transfer.radius_at(transfer.true_anomaly_at(transfer.time_of_flight() / 2.0)).m(),
+        }
+    }
+
+    // Whether every result is within its tolerance of the expected one.
+    pub fn matches(&self, actual: &Expected) -> bool {
+        let close = |actual: f64, expected: f64, tolerance: f64| (actual - expected).abs() <= tolerance * expected.abs();
+        close(actual.delta_v, self.delta_v, ANALYTIC_TOLERANCE)
+            && close(actual.time_of_flight, self.time_of_flight, ANALYTIC_TOLERANCE)
+            && close(actual.arrival_v_infinity, self.arrival_v_infinity, ANALYTIC_TOLERANCE)
+            && close(actual.phase_angle, self.phase_angle, ANALYTIC_TOLERANCE)
+            && close(actual.halfway_radius, self.halfway_radius, ITERATIVE_TOLERANCE)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ReferenceTransfer {
+    pub name: &'static str,
+    // Semi-major axes in astronomical units around a star of one solar mass.
+    pub origin: f64,
+    pub target: f64,
+    // Delta-v added to the Hohmann transfer in m/s, away from it for inward transfers.
+    pub extra_delta_v: f64,
+    pub expected: Expected,
+}
+
+impl ReferenceTransfer {
+    pub fn transfer(&self) -> Transfer {
+        let parent = Parent::new(Mass::from_solar(1.0));
+        let mut transfer = Transfer::new(
+            Planet::new(Distance::from_astronomical_unit(self.origin), parent),
+            Planet::new(Distance::from_astronomical_unit(self.target), parent),
+        );
+        transfer.set_delta_v(transfer.delta_v_hohmann() + Velocity::from_meters_per_second(self.extra_delta_v));
+        transfer
+    }
+
+    pub fn matches(&self) -> bool {
+        self.expected.matches(&Expected::of(&self.transfer()))
+    }
+}
+
+pub const REFERENCE_TRANSFERS: [ReferenceTransfer; 4] = [
+    ReferenceTransfer {
+        name: "Hohmann transfer from Earth to Mars",
+        origin: 1.0,
+        target: 1.52366,
+        extra_delta_v: 0.0,
+        expected: Expected {
+            delta_v: 2944.651418269441,
+            time_of_flight: 22365444.985242255,
+            arrival_v_infinity: 2648.8695851301854,
+            phase_angle: 0.7739342407007266,
+            halfway_radius: 196672949252.28534,
+        },
+    },
+    ReferenceTransfer {
+        name: "Hohmann transfer from Mars to Earth",
+        origin: 1.52366,
+        target: 1.0,
+        extra_delta_v: 0.0,
+        expected: Expected {
+            delta_v: -2648.8695851301854,
+            time_of_flight: 22365444.98524226,
+            arrival_v_infinity: 2944.6514182694445,
+            phase_angle: -1.3113914574925651,
+            halfway_radius: 196672949252.28537,
+        },
+    },
+    ReferenceTransfer {
+        name: "Fast transfer from Earth to Mars",
+        origin: 1.0,
+        target: 1.52366,
+        extra_delta_v: 3000.0,
+        expected: Expected {
+            delta_v: 5944.651418269437,
+            time_of_flight: 9793750.773587534,
+            arrival_v_infinity: 10833.946516195501,
+            phase_angle: 0.6609102272208391,
+            halfway_radius: 176277372787.94647,
+        },
+    },
+    ReferenceTransfer {
+        name: "Retrograde transfer from Earth to Venus",
+        origin: 1.0,
+        target: 0.723,
+        extra_delta_v: -45000.0,
+        expected: Expected {
+            delta_v: -47499.058569520515,
+            time_of_flight: 4492993.239264227,
+            arrival_v_infinity: 62746.19367005151,
+            phase_angle: -2.114579181495969,
+            halfway_radius: 139770445166.20187,
+        },
+    },
+];
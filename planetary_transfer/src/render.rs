@@ -0,0 +1,88 @@
+use std::fmt::Write;
+use std::{fs, io, path::Path};
+
+use crate::Transfer;
+use crate::geometry::{Conic, Diagram};
+
+// Headless rendering of the transfer diagram, using the same geometry as the app.
+pub struct SvgRenderer {
+    size: u32,
+    background: String,
+    foreground: String,
+    transfer_color: String,
+}
+
+impl SvgRenderer {
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            background: "white".to_string(),
+            foreground: "black".to_string(),
+            transfer_color: "rgb(255,115,0)".to_string(),
+        }
+    }
+
+    pub fn background(mut self, color: &str) -> Self {
+        self.background = color.to_string();
+        self
+    }
+
+    pub fn foreground(mut self, color: &str) -> Self {
+        self.foreground = color.to_string();
+        self
+    }
+
+    pub fn transfer_color(mut self, color: &str) -> Self {
+        self.transfer_color = color.to_string();
+        self
+    }
+
+    pub fn render(&self, transfer: &Transfer) -> String {
+        let diagram = Diagram::new(transfer);
+        let (origin, target, scale) = (transfer.origin(), transfer.target(), diagram.scale);
+
+        let mut svg = String::new();
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#, self.size);
+        let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, self.background);
+
+        self.polyline(&mut svg, &diagram.origin, &self.foreground);
+        self.polyline(&mut svg, &diagram.target, &self.foreground);
+        self.polyline(&mut svg, &diagram.transfer, &self.transfer_color);
+
+        self.marker(&mut svg, origin.position(transfer.origin_true_anomaly_departure(), scale), 6.0);
+        self.marker(&mut svg, origin.position(transfer.origin_true_anomaly_arrival(), scale), 6.0);
+        self.marker(&mut svg, target.position(transfer.target_true_anomaly_departure(), scale), 6.0);
+        self.marker(&mut svg, target.position(transfer.target_true_anomaly_arrival(), scale), 6.0);
+        self.marker(&mut svg, (0.0, 0.0), 8.0);
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn save(&self, transfer: &Transfer, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.render(transfer))
+    }
+
+    // Maps plot coordinates, the largest orbit being of unit radius, to pixels with a small margin.
+    fn to_pixels(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let half = self.size as f64 / 2.0;
+        (half + x * half / 1.1, half - y * half / 1.1)
+    }
+
+    fn polyline(&self, svg: &mut String, points: &[(f64, f64)], color: &str) {
+        let points = points.iter()
+            .map(|&point| self.to_pixels(point))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1"/>"#, points, color);
+    }
+
+    fn marker(&self, svg: &mut String, point: (f64, f64), radius: f64) {
+        let (x, y) = self.to_pixels(point);
+        if x.is_finite() && y.is_finite() {
+            let _ = writeln!(svg, r#"<circle cx="{:.2}" cy="{:.2}" r="{}" fill="{}"/>"#, x, y, radius, self.foreground);
+        }
+    }
+}
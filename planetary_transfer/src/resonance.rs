@@ -0,0 +1,57 @@
+use std::f64::consts::TAU;
+
+use crate::{Distance, Duration, Planet, Velocity};
+
+// Orbit completing `resonance` revolutions while the reference orbit completes `revolutions`,
+// sharing one apsis with it, as used to deploy evenly spaced satellites from a carrier.
+#[derive(Copy, Clone, Debug)]
+pub struct ResonantOrbit {
+    pub resonance: u32,
+    pub revolutions: u32,
+    pub sma: Distance,
+    pub periapsis: Distance,
+    pub apoapsis: Distance,
+    pub period: Duration,
+    pub delta_v: Velocity,
+    // Angle between consecutive passes of the resonant orbit at the shared apsis, along the reference orbit.
+    pub separation: f64,
+    radius: Distance,
+}
+
+impl ResonantOrbit {
+    pub fn new(planet: &Planet, resonance: u32, revolutions: u32) -> Option<Self> {
+        if resonance == 0 || revolutions == 0 {
+            return None;
+        }
+
+        let mu = planet.orbit.parent.gravitational_parameter.m3ps2();
+        let period = planet.period() * revolutions as f64 / resonance as f64;
+        let sma = (mu * (period / TAU).powi(2)).cbrt();
+        let radius = planet.sma().m();
+
+        // The resonant orbit must still reach the reference orbit with its other apsis above the parent's center.
+        let other_apsis = 2.0 * sma - radius;
+        if other_apsis <= 0.0 {
+            return None;
+        }
+
+        let velocity = (mu * (2.0 / radius - 1.0 / sma)).sqrt();
+
+        Some(Self {
+            resonance,
+            revolutions,
+            sma: Distance::from_meters(sma),
+            periapsis: Distance::from_meters(radius.min(other_apsis)),
+            apoapsis: Distance::from_meters(radius.max(other_apsis)),
+            period: Duration::from_seconds(period),
+            delta_v: Velocity::from_meters_per_second(velocity - planet.orbital_velocity().mps()),
+            separation: TAU * (revolutions as f64 / resonance as f64).fract(),
+            radius: planet.sma(),
+        })
+    }
+
+    // Signed so that a true anomaly of zero lies on the reference orbit, as with transfers.
+    pub fn eccentricity(&self) -> f64 {
+        1.0 - self.radius.m() / self.sma.m()
+    }
+}
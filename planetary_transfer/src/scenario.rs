@@ -0,0 +1,175 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Arrival, BuildError, Departure, Distance, Keyframe, Mass, Parent, Planet, QuantityError, SolvedTransfer, Transfer, TransferBuilder, Velocity};
+
+// Version of the scenario files written by this crate. Fields added later are optional, so that files of a newer
+// version still load as long as their structure is unchanged, their unknown fields being ignored.
+pub const SCENARIO_VERSION: u32 = 1;
+pub const SCENARIO_EXTENSION: &str = "transfer";
+// Most full revolutions of the transfer orbit a leg can make before the intercept.
+pub const SCENARIO_MAX_REVOLUTIONS: u32 = 5;
+
+// Scenario shared as a `.transfer` file, in JSON: a transfer or an itinerary of consecutive transfers around a parent.
+// Quantities are in SI units, bodies of the catalog being referred to by name along with their values.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ScenarioFile {
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub parent: ParentEntry,
+    pub legs: Vec<Leg>,
+    // Changes of the first leg over a recorded session, replayed from the leg as it was at the start.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recording: Vec<Keyframe>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ParentEntry {
+    #[serde(default)]
+    pub body: Option<String>,
+    // In m³/s².
+    pub gravitational_parameter: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct OrbitEntry {
+    #[serde(default)]
+    pub body: Option<String>,
+    // In meters.
+    pub sma: f64,
+    #[serde(default)]
+    pub retrograde: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Leg {
+    pub origin: OrbitEntry,
+    pub target: OrbitEntry,
+    // Delta-v of the departure burn in m/s, negative when burning retrograde, None for the Hohmann transfer.
+    #[serde(default)]
+    pub delta_v: Option<f64>,
+    #[serde(default)]
+    pub revolutions: u32,
+    #[serde(default)]
+    pub arrival: ArrivalEntry,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ArrivalEntry {
+    #[default]
+    Flyby,
+    Rendezvous,
+    // Radius of the circular orbit around the target in meters.
+    Capture { radius: f64 },
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Json(serde_json::Error),
+    // File of a newer version whose structure this version does not understand.
+    UnsupportedVersion(u32),
+    Empty,
+    // Leg, or keyframe of the recording, making more revolutions than `SCENARIO_MAX_REVOLUTIONS`.
+    Revolutions(u32),
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::Json(error) => write!(f, "invalid scenario file: {}", error),
+            ScenarioError::UnsupportedVersion(version) => write!(f, "scenario file of version {}, newer than the supported version {}", version, SCENARIO_VERSION),
+            ScenarioError::Empty => write!(f, "the scenario file has no transfer"),
+            ScenarioError::Revolutions(revolutions) => write!(f, "transfer of {} revolutions, more than the {} supported", revolutions, SCENARIO_MAX_REVOLUTIONS),
+        }
+    }
+}
+
+impl Error for ScenarioError {}
+
+impl ScenarioFile {
+    // Scenario of a single transfer around a parent of given gravitational parameter.
+    pub fn new(name: &str, parent: ParentEntry, leg: Leg) -> Self {
+        Self {
+            version: SCENARIO_VERSION,
+            name: name.to_string(),
+            description: String::new(),
+            parent,
+            legs: vec![leg],
+            recording: Vec::new(),
+        }
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ScenarioError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        let file = serde_json::from_str::<Self>(text).map_err(|error| {
+            // A newer version is the likelier reason for a file of this format not to load.
+            match serde_json::from_str::<Versioned>(text) {
+                Ok(Versioned { version }) if version > SCENARIO_VERSION => ScenarioError::UnsupportedVersion(version),
+                _ => ScenarioError::Json(error),
+            }
+        })?;
+        if file.legs.is_empty() {
+            return Err(ScenarioError::Empty);
+        }
+        let mut legs = file.legs.iter().chain(file.recording.iter().map(|keyframe| &keyframe.leg));
+        if let Some(leg) = legs.find(|leg| leg.revolutions > SCENARIO_MAX_REVOLUTIONS) {
+            return Err(ScenarioError::Revolutions(leg.revolutions));
+        }
+        Ok(file)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("scenarios only hold serializable values")
+    }
+
+    pub fn parent(&self) -> Result<Parent, QuantityError> {
+        Mass::try_from_mu(self.parent.gravitational_parameter).map(Parent::new)
+    }
+
+    // Solves every leg of the scenario in order.
+    pub fn transfers(&self) -> Result<Vec<SolvedTransfer>, BuildError> {
+        let parent = self.parent()?;
+        self.legs.iter().map(|leg| leg.transfer(parent)).collect()
+    }
+}
+
+impl Leg {
+    // Leg flying a transfer as it is, its departure delta-v being kept even when it is the one of the Hohmann transfer.
+    pub fn from_transfer(transfer: &Transfer, origin: Option<&str>, target: Option<&str>) -> Self {
+        Self {
+            origin: OrbitEntry { body: origin.map(str::to_string), sma: transfer.origin().sma().m(), retrograde: transfer.origin().retrograde() },
+            target: OrbitEntry { body: target.map(str::to_string), sma: transfer.target().sma().m(), retrograde: transfer.target().retrograde() },
+            delta_v: Some((transfer.launch_velocity() - transfer.origin().orbital_velocity()).mps()),
+            revolutions: transfer.revolutions(),
+            arrival: ArrivalEntry::Flyby,
+        }
+    }
+
+    pub fn transfer(&self, parent: Parent) -> Result<SolvedTransfer, BuildError> {
+        let planet = |orbit: &OrbitEntry| Distance::try_from_meters(orbit.sma).map(|sma| Planet::new(sma, parent).with_retrograde(orbit.retrograde));
+        let departure = match self.delta_v {
+            Some(delta_v) => Departure::DeltaV(Velocity::from_meters_per_second(delta_v)),
+            None => Departure::Hohmann,
+        };
+        let arrival = match self.arrival {
+            ArrivalEntry::Flyby => Arrival::Flyby,
+            ArrivalEntry::Rendezvous => Arrival::Rendezvous,
+            ArrivalEntry::Capture { radius } => Arrival::Capture(Distance::from_meters(radius)),
+        };
+        TransferBuilder::new(planet(&self.origin)?, planet(&self.target)?)
+            .with_departure(departure)
+            .with_arrival(arrival)
+            .with_revolutions(self.revolutions)
+            .build()
+    }
+}
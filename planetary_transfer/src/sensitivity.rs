@@ -0,0 +1,110 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{Distance, Duration, Parent, Transfer, Vec2, Velocity};
+
+// Step of the departure delta-v in m/s around which the miss distance is linearized.
+const DELTA_V_STEP: f64 = 1E-3;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Parameter {
+    OriginSma,
+    TargetSma,
+    Mass,
+}
+
+// Change of the Hohmann delta-v and of the time of flight for a 1% error on a parameter,
+// estimated with a central difference.
+#[derive(Copy, Clone, Debug)]
+pub struct Sensitivity {
+    pub parameter: Parameter,
+    pub delta_v: Velocity,
+    pub time_of_flight: Duration,
+}
+
+impl Sensitivity {
+    pub fn new(transfer: &Transfer, parameter: Parameter) -> Self {
+        let above = transfer.perturbed(parameter, 1.01);
+        let below = transfer.perturbed(parameter, 0.99);
+
+        Self {
+            parameter,
+            delta_v: (above.delta_v_hohmann() - below.delta_v_hohmann()) / 2.0,
+            time_of_flight: (above.time_of_flight() - below.time_of_flight()) / 2.0,
+        }
+    }
+
+    pub fn all(transfer: &Transfer) -> Vec<Sensitivity> {
+        [Parameter::OriginSma, Parameter::TargetSma, Parameter::Mass]
+            .into_iter()
+            .map(|parameter| Self::new(transfer, parameter))
+            .collect()
+    }
+}
+
+// Displacement of the spacecraft from the target at arrival per error of the departure burn, linearized around the
+// nominal burn. The arrival is the one of the nominal transfer, delayed by as much as the burn for timing errors.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MissSensitivity {
+    // Miss in meters per m/s of extra delta-v, on the axes of the transfer diagram.
+    pub per_delta_v: Vec2,
+    // Miss in meters per second of delay of the burn.
+    pub per_second: Vec2,
+}
+
+impl MissSensitivity {
+    pub fn new(transfer: &Transfer) -> Self {
+        let arrival_position = |delta_v: f64| {
+            let mut transfer = *transfer;
+            transfer.add_delta_v = transfer.add_delta_v + Velocity::from_meters_per_second(delta_v);
+            let true_anomaly = transfer.true_anomaly_at(transfer.time_of_flight());
+            Vec2::from_polar(transfer.radius_at(true_anomaly).m(), true_anomaly)
+        };
+        let per_delta_v = (arrival_position(DELTA_V_STEP) - arrival_position(-DELTA_V_STEP)) / (2.0 * DELTA_V_STEP);
+
+        // A late burn rotates the whole transfer along the orbit of the origin while the target moves on along its own.
+        let arrival = Vec2::from_polar(transfer.target.sma().m(), transfer.target_true_anomaly_arrival());
+        let per_second = arrival.rotated(FRAC_PI_2) * (transfer.origin.mean_motion() - transfer.target_mean_motion());
+
+        Self { per_delta_v, per_second }
+    }
+
+    // Miss distance of a burn off by some delta-v, negative when short, and delayed by some time, negative when early.
+    pub fn miss(&self, delta_v_error: Velocity, timing_error: Duration) -> Distance {
+        Distance::from_meters((self.per_delta_v * delta_v_error.mps() + self.per_second * timing_error.s()).norm())
+    }
+
+    // Largest delta-v error keeping the spacecraft within a distance of the target, the burn being on time.
+    pub fn delta_v_tolerance(&self, miss: Distance) -> Velocity {
+        Velocity::from_meters_per_second(miss.m() / self.per_delta_v.norm())
+    }
+
+    // Largest timing error keeping the spacecraft within a distance of the target, the delta-v being exact.
+    pub fn timing_tolerance(&self, miss: Distance) -> Duration {
+        Duration::from_seconds(miss.m() / self.per_second.norm())
+    }
+}
+
+impl Transfer {
+    pub fn miss_sensitivity(&self) -> MissSensitivity {
+        MissSensitivity::new(self)
+    }
+
+    // Copy of the transfer with one parameter scaled, keeping the delta-v added to the Hohmann transfer.
+    pub fn perturbed(&self, parameter: Parameter, factor: f64) -> Transfer {
+        let mut transfer = *self;
+        match parameter {
+            Parameter::OriginSma => transfer.origin.orbit.sma = transfer.origin.orbit.sma * factor,
+            Parameter::TargetSma => transfer.target.orbit.sma = transfer.target.orbit.sma * factor,
+            Parameter::Mass => {
+                let parent = Parent {
+                    mass: transfer.parent.mass * factor,
+                    gravitational_parameter: transfer.parent.gravitational_parameter * factor,
+                    ..transfer.parent
+                };
+                transfer.parent = parent;
+                transfer.set_barycentric(transfer.barycentric);
+            }
+        }
+        transfer
+    }
+}
@@ -0,0 +1,198 @@
+// Root finding of scalar equations, shared by the iterative parts of the crate.
+
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tolerance {
+    // Width of the bracket, or size of the last step for Newton-Raphson, under which the root is found.
+    pub absolute: f64,
+    pub max_iterations: usize,
+}
+
+impl Tolerance {
+    pub fn new(absolute: f64, max_iterations: usize) -> Self {
+        Self { absolute, max_iterations }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::new(1E-12, 100)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Root {
+    pub x: f64,
+    // Value of the function at the root, in its own unit.
+    pub residual: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+impl Root {
+    fn new(x: f64, residual: f64, iterations: usize, converged: bool) -> Self {
+        Self { x, residual, iterations, converged }
+    }
+}
+
+// Halves the bracket until it is narrow enough, none if the function has the same sign at both ends.
+pub fn bisection(f: impl Fn(f64) -> f64, low: f64, high: f64, tolerance: Tolerance) -> Option<Root> {
+    let (mut low, mut high) = (low, high);
+    let (f_low, f_high) = (f(low), f(high));
+    if f_low == 0.0 {
+        return Some(Root::new(low, f_low, 0, true));
+    }
+    if f_high == 0.0 {
+        return Some(Root::new(high, f_high, 0, true));
+    }
+    if f_low.signum() == f_high.signum() {
+        return None;
+    }
+
+    let low_sign = f_low.signum();
+    for iteration in 1..=tolerance.max_iterations {
+        let middle = (low + high) / 2.0;
+        let f_middle = f(middle);
+        // Past the resolution of floating point numbers, the bracket cannot shrink any further.
+        if f_middle == 0.0 || (high - low).abs() / 2.0 < tolerance.absolute || middle == low || middle == high {
+            return Some(Root::new(middle, f_middle, iteration, true));
+        }
+        if f_middle.signum() == low_sign {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    let middle = (low + high) / 2.0;
+    Some(Root::new(middle, f(middle), tolerance.max_iterations, false))
+}
+
+// Follows the tangent of the function from a first guess, fast near the root but without any guarantee of converging.
+pub fn newton(f: impl Fn(f64) -> f64, derivative: impl Fn(f64) -> f64, guess: f64, tolerance: Tolerance) -> Root {
+    let mut x = guess;
+    for iteration in 1..=tolerance.max_iterations {
+        let step = f(x) / derivative(x);
+        x -= step;
+        if step.abs() < tolerance.absolute {
+            return Root::new(x, f(x), iteration, true);
+        }
+    }
+    Root::new(x, f(x), tolerance.max_iterations, false)
+}
+
+// Brent's method, combining inverse quadratic interpolation with bisection, none if the function has the same sign
+// at both ends of the bracket. Converges much faster than bisection on smooth functions with the same guarantees.
+pub fn brent(f: impl Fn(f64) -> f64, low: f64, high: f64, tolerance: Tolerance) -> Option<Root> {
+    let (mut a, mut b) = (low, high);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(Root::new(a, fa, 0, true));
+    }
+    if fb == 0.0 {
+        return Some(Root::new(b, fb, 0, true));
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let (mut d, mut e) = (b - a, b - a);
+    for iteration in 1..=tolerance.max_iterations {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        // The best estimate is kept in b, the root staying bracketed between b and c.
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let bound = 2.0 * f64::EPSILON * b.abs() + tolerance.absolute / 2.0;
+        let middle = (c - b) / 2.0;
+        if middle.abs() <= bound || fb == 0.0 {
+            return Some(Root::new(b, fb, iteration, true));
+        }
+
+        if e.abs() >= bound && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * middle * s, 1.0 - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                (
+                    s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            // Interpolates only when the step stays well within the bracket, bisecting otherwise.
+            if 2.0 * p < (3.0 * middle * q - (bound * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = middle;
+                e = d;
+            }
+        } else {
+            d = middle;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > bound {d} else {bound.copysign(middle)};
+        fb = f(b);
+    }
+    Some(Root::new(b, fb, tolerance.max_iterations, false))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Minimum {
+    pub x: f64,
+    // Value of the function at the minimum.
+    pub value: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// Golden-section search of the minimum of a function within an interval, only finding the global minimum
+// when the function has no other local minimum there.
+pub fn golden_section(f: impl Fn(f64) -> f64, low: f64, high: f64, tolerance: Tolerance) -> Minimum {
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let (mut low, mut high) = (low.min(high), low.max(high));
+    let (mut x1, mut x2) = (high - ratio * (high - low), low + ratio * (high - low));
+    let (mut f1, mut f2) = (f(x1), f(x2));
+    for iteration in 1..=tolerance.max_iterations {
+        if high - low < tolerance.absolute {
+            let x = (low + high) / 2.0;
+            return Minimum { x, value: f(x), iterations: iteration, converged: true };
+        }
+        if f1 < f2 {
+            high = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = high - ratio * (high - low);
+            f1 = f(x1);
+        } else {
+            low = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = low + ratio * (high - low);
+            f2 = f(x2);
+        }
+    }
+    let x = (low + high) / 2.0;
+    Minimum { x, value: f(x), iterations: tolerance.max_iterations, converged: false }
+}
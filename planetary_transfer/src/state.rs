@@ -0,0 +1,50 @@
+use crate::{lambert::lambert, vector::Vec3, Duration, Parent, Transfer, Velocity};
+
+// Position in meters and velocity in m/s relative to the parent.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct StateVector {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+impl StateVector {
+    pub fn new(position: Vec3, velocity: Vec3) -> Self {
+        Self {
+            position,
+            velocity,
+        }
+    }
+}
+
+// Transfer between arbitrary positions, which unlike `Transfer` need not lie on coplanar circular orbits.
+#[derive(Copy, Clone, Debug)]
+pub struct StateTransfer {
+    pub parent: Parent,
+    pub departure: StateVector,
+    pub arrival: StateVector,
+    pub time_of_flight: Duration,
+    // Velocity change needed at departure from the initial velocity.
+    pub delta_v: Vec3,
+}
+
+impl StateTransfer {
+    pub fn delta_v_norm(&self) -> Velocity {
+        Velocity::from_meters_per_second(self.delta_v.norm())
+    }
+}
+
+impl Transfer {
+    // Transfer from a position and velocity to a position reached after the given time of flight,
+    // none if Lambert's problem has no prograde solution of less than one revolution.
+    pub fn from_states(r1: Vec3, v1: Vec3, r2: Vec3, parent: Parent, time_of_flight: Duration) -> Option<StateTransfer> {
+        let (departure, arrival) = lambert(r1, r2, time_of_flight.s(), parent.gravitational_parameter())?;
+
+        Some(StateTransfer {
+            parent,
+            departure: StateVector::new(r1, departure),
+            arrival: StateVector::new(r2, arrival),
+            time_of_flight,
+            delta_v: departure - v1,
+        })
+    }
+}
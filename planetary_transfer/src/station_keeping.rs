@@ -0,0 +1,43 @@
+use crate::{atmospheric_density, Distance, Duration, Parent, Planet, SurfaceForces, Velocity};
+
+// Fraction of the Hill sphere within which prograde orbits around a body stay bound to it over many revolutions.
+pub const STABLE_HILL_FRACTION: f64 = 0.5;
+
+// Rough yearly delta-v keeping a spacecraft on a circular orbit around a body, as the orbit it was captured into.
+// Low orbits lose energy to the atmosphere, made up for at the rate drag slows the spacecraft down. Distant ones are
+// pulled around by the parent of the body, its tidal acceleration relative to the gravity of the body growing as the
+// cube of the radius over the Hill sphere: that share of it is taken as the part that does not average out over the
+// revolutions and has to be countered.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StationKeeping {
+    pub drag: Velocity,
+    pub tidal: Velocity,
+    // Whether the orbit lies beyond the stable part of the Hill sphere, no budget keeping the spacecraft there for long.
+    pub unstable: bool,
+}
+
+impl StationKeeping {
+    // Budget of an orbit of a radius around a body, given its orbit around the parent and the body itself for its
+    // atmosphere, with the area to mass and drag coefficient of the spacecraft. None when the body has no mass.
+    pub fn new(orbit: &Planet, body: &Parent, radius: Distance, forces: &SurfaceForces) -> Option<Self> {
+        let hill_sphere = orbit.hill_sphere()?;
+        let year = Duration::from_years(1.0).s();
+
+        let speed = body.gravitational_parameter().circular_velocity(radius).mps();
+        let density = atmospheric_density(body, radius);
+        let drag = 0.5 * density * speed.powi(2) * forces.drag_coefficient * forces.area_to_mass * year;
+
+        let ratio = radius.m() / hill_sphere.m();
+        let tidal = 3.0 * orbit.mean_motion().powi(2) * radius.m() * ratio.powi(3) * year;
+
+        Some(Self {
+            drag: Velocity::from_meters_per_second(drag),
+            tidal: Velocity::from_meters_per_second(tidal),
+            unstable: ratio > STABLE_HILL_FRACTION,
+        })
+    }
+
+    pub fn annual(&self) -> Velocity {
+        self.drag + self.tidal
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{geometry, Distance, Duration, Transfer, Velocity, significant, SIGNIFICANT_FIGURES};
+
+// Plain text description of a transfer, to be pasted where the calculator is not at hand.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TransferSummary {
+    pub parent: Option<String>,
+    pub origin: Distance,
+    pub target: Distance,
+    pub delta_v: Velocity,
+    pub time_of_flight: Duration,
+    // In radians within ]-PI, PI], the target leading the origin at departure.
+    pub phase_angle: f64,
+    pub next_window: Option<Duration>,
+}
+
+impl TransferSummary {
+    pub fn new(transfer: &Transfer) -> Self {
+        Self {
+            parent: None,
+            origin: transfer.origin().sma(),
+            target: transfer.target().sma(),
+            delta_v: transfer.launch_velocity() - transfer.origin().orbital_velocity(),
+            time_of_flight: transfer.time_of_flight(),
+            phase_angle: geometry::Protractor::new(transfer.target_true_anomaly_departure(), 1.0).angle(),
+            next_window: None,
+        }
+    }
+
+    pub fn with_parent(mut self, name: &str) -> Self {
+        self.parent = Some(name.to_string());
+        self
+    }
+
+    pub fn with_next_window(mut self, wait_time: Duration) -> Self {
+        self.next_window = Some(wait_time);
+        self
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Transfer from {} to {}", self.origin.formatted(), self.target.formatted());
+        if let Some(parent) = &self.parent {
+            text.push_str(&format!(" around {}", parent));
+        }
+        text.push_str(&format!("\nDelta-v: {}", self.delta_v.formatted()));
+        text.push_str(&format!("\nTime of flight: {}", self.time_of_flight.formatted()));
+        text.push_str(&format!("\nPhase angle: {} °", significant(self.phase_angle.to_degrees(), SIGNIFICANT_FIGURES)));
+        if let Some(next_window) = self.next_window {
+            text.push_str(&format!("\nNext window in: {}", next_window.formatted()));
+        }
+        text
+    }
+}
@@ -0,0 +1,121 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Distance, Mass, Parent, ParentEntry, Planet, QuantityError, Transfer, TransferError};
+
+// Version of the system files written by this crate, newer files loading as long as their structure is unchanged
+// like scenario files.
+pub const SYSTEM_VERSION: u32 = 1;
+pub const SYSTEM_EXTENSION: &str = "system";
+
+// Custom system shared as a `.system` file, in JSON: bodies on circular orbits around a parent, any two of which can
+// be picked as the origin and target of a transfer. Quantities are in SI units.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SystemFile {
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    pub parent: ParentEntry,
+    #[serde(default)]
+    pub bodies: Vec<SystemBody>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SystemBody {
+    pub name: String,
+    // In meters.
+    pub sma: f64,
+    // In kilograms, None for bodies of negligible mass.
+    #[serde(default)]
+    pub mass: Option<f64>,
+    // Whether the body orbits against the others.
+    #[serde(default)]
+    pub retrograde: bool,
+    // Display color as RGB.
+    #[serde(default = "SystemBody::default_color")]
+    pub color: [u8; 3],
+}
+
+#[derive(Debug)]
+pub enum SystemError {
+    Json(serde_json::Error),
+    // File of a newer version whose structure this version does not understand.
+    UnsupportedVersion(u32),
+}
+
+impl Display for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SystemError::Json(error) => write!(f, "invalid system file: {}", error),
+            SystemError::UnsupportedVersion(version) => write!(f, "system file of version {}, newer than the supported version {}", version, SYSTEM_VERSION),
+        }
+    }
+}
+
+impl Error for SystemError {}
+
+impl SystemFile {
+    pub fn new(name: &str, parent: ParentEntry, bodies: Vec<SystemBody>) -> Self {
+        Self {
+            version: SYSTEM_VERSION,
+            name: name.to_string(),
+            parent,
+            bodies,
+        }
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, SystemError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        serde_json::from_str::<Self>(text).map_err(|error| {
+            match serde_json::from_str::<Versioned>(text) {
+                Ok(Versioned { version }) if version > SYSTEM_VERSION => SystemError::UnsupportedVersion(version),
+                _ => SystemError::Json(error),
+            }
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("systems only hold serializable values")
+    }
+
+    pub fn parent(&self) -> Result<Parent, QuantityError> {
+        Mass::try_from_mu(self.parent.gravitational_parameter).map(Parent::new)
+    }
+
+    // Transfer between two bodies of the system, given their indices.
+    pub fn transfer(&self, origin: usize, target: usize) -> Result<Transfer, TransferError> {
+        let parent = self.parent()?;
+        let body = |index: usize| self.bodies.get(index).ok_or(TransferError::UnknownBody(index));
+        Transfer::try_new(body(origin)?.planet(parent)?, body(target)?.planet(parent)?)
+    }
+}
+
+impl SystemBody {
+    pub fn new(name: &str, sma: Distance) -> Self {
+        Self {
+            name: name.to_string(),
+            sma: sma.m(),
+            mass: None,
+            retrograde: false,
+            color: Self::default_color(),
+        }
+    }
+
+    fn default_color() -> [u8; 3] {
+        [255, 255, 255]
+    }
+
+    pub fn planet(&self, parent: Parent) -> Result<Planet, QuantityError> {
+        let planet = Planet::new(Distance::try_from_meters(self.sma)?, parent).with_retrograde(self.retrograde);
+        match self.mass {
+            Some(mass) => Ok(planet.with_mass(Mass::try_from_kilograms(mass)?)),
+            None => Ok(planet),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::{self, Display};
+
+use crate::{Distance, Transfer};
+
+// Relative difference between the semi-major axes below which the orbits are considered the same.
+const CLOSE_ORBITS: f64 = 1E-3;
+
+// Situations in which the patched conics approximation behind the transfer no longer holds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferWarning {
+    CloseOrbits,
+    // Distance between the orbits and the sum of the spheres of influence of the bodies.
+    OverlappingSpheresOfInfluence {
+        gap: Distance,
+        spheres_of_influence: Distance,
+    },
+    // Orbit closer to a binary parent than the smallest stable circumbinary orbit.
+    UnstableCircumbinaryOrbit {
+        sma: Distance,
+        critical_sma: Distance,
+    },
+}
+
+impl Display for TransferWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferWarning::CloseOrbits => write!(f, "The orbits of the origin and target are almost identical, a transfer between them is meaningless."),
+            TransferWarning::OverlappingSpheresOfInfluence { gap, spheres_of_influence } => write!(
+                f,
+                "The orbits are {} km apart, within the {} km spanned by the spheres of influence of the bodies: the bodies themselves dominate the trajectory.",
+                gap.km().round(),
+                spheres_of_influence.km().round(),
+            ),
+            TransferWarning::UnstableCircumbinaryOrbit { sma, critical_sma } => write!(
+                f,
+                "An orbit of {} lies within the {} around the binary parent where the pull of both stars makes orbits unstable.",
+                sma.formatted(),
+                critical_sma.formatted(),
+            ),
+        }
+    }
+}
+
+impl Transfer {
+    // Spheres of influence are only accounted for when the planets have a mass.
+    pub fn warnings(&self) -> Vec<TransferWarning> {
+        let (origin, target) = (self.origin(), self.target());
+        let gap = Distance::from_meters((target.sma() - origin.sma()).m().abs());
+
+        if gap.m() < origin.sma().max(target.sma()).m() * CLOSE_ORBITS {
+            return vec![TransferWarning::CloseOrbits];
+        }
+
+        let spheres_of_influence = [origin, target].iter()
+            .filter_map(|planet| planet.sphere_of_influence())
+            .fold(Distance::from_meters(0.0), |sum, sphere_of_influence| sum + sphere_of_influence);
+
+        let mut warnings = Vec::new();
+        if gap < spheres_of_influence {
+            warnings.push(TransferWarning::OverlappingSpheresOfInfluence { gap, spheres_of_influence });
+        }
+        if let Some(binary) = self.parent.binary() {
+            let critical_sma = binary.critical_sma();
+            for sma in [origin.sma(), target.sma()].into_iter().filter(|&sma| sma < critical_sma) {
+                warnings.push(TransferWarning::UnstableCircumbinaryOrbit { sma, critical_sma });
+            }
+        }
+        warnings
+    }
+}
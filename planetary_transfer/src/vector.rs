@@ -0,0 +1,189 @@
+use core::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Cartesian vectors in the frame of the parent, x and y spanning the reference plane and the
+// x axis pointing to the zero of longitude. Components are in SI units.
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec2 {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x,
+            y,
+        }
+    }
+
+    pub fn from_polar(radius: f64, angle: f64) -> Self {
+        Self::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    pub fn dot(self, rhs: Vec2) -> f64 {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    // Z component of the cross product, positive when rhs is counterclockwise from self.
+    pub fn cross(self, rhs: Vec2) -> f64 {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    // Counterclockwise angle from the x axis in ]-PI, PI].
+    pub fn angle(self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    // Signed angle from self to rhs in ]-PI, PI], positive counterclockwise.
+    pub fn angle_to(self, rhs: Vec2) -> f64 {
+        self.cross(rhs).atan2(self.dot(rhs))
+    }
+
+    // Rotated counterclockwise by an angle in radians.
+    pub fn rotated(self, angle: f64) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    pub fn extend(self, z: f64) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+}
+
+impl Vec3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            x,
+            y,
+            z,
+        }
+    }
+
+    pub fn dot(self, rhs: Vec3) -> f64 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    // Unsigned angle between both vectors in [0, PI].
+    pub fn angle_to(self, rhs: Vec3) -> f64 {
+        self.cross(rhs).norm().atan2(self.dot(rhs))
+    }
+
+    // Projection on the reference plane.
+    pub fn truncate(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl From<(f64, f64)> for Vec2 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Vec2> for (f64, f64) {
+    fn from(vector: Vec2) -> Self {
+        (vector.x, vector.y)
+    }
+}
+
+impl From<Vec2> for Vec3 {
+    fn from(vector: Vec2) -> Self {
+        vector.extend(0.0)
+    }
+}
+
+macro_rules! vector {
+    ($($t:ident { $($field:ident),* }),*) => ($(
+        impl $t {
+            pub fn norm(self) -> f64 {
+                self.dot(self).sqrt()
+            }
+
+            pub fn norm_squared(self) -> f64 {
+                self.dot(self)
+            }
+
+            // Unit vector in the same direction, none for the zero vector.
+            pub fn normalized(self) -> Option<$t> {
+                let norm = self.norm();
+                (norm > 0.0 && norm.is_finite()).then(|| self / norm)
+            }
+
+            pub fn distance(self, rhs: $t) -> f64 {
+                (self - rhs).norm()
+            }
+        }
+        impl Add for $t {
+            type Output = $t;
+
+            fn add(self, rhs: $t) -> $t {
+                $t { $($field: self.$field + rhs.$field),* }
+            }
+        }
+        impl Sub for $t {
+            type Output = $t;
+
+            fn sub(self, rhs: $t) -> $t {
+                $t { $($field: self.$field - rhs.$field),* }
+            }
+        }
+        impl Mul<f64> for $t {
+            type Output = $t;
+
+            fn mul(self, rhs: f64) -> $t {
+                $t { $($field: self.$field * rhs),* }
+            }
+        }
+        impl Mul<$t> for f64 {
+            type Output = $t;
+
+            fn mul(self, rhs: $t) -> $t {
+                rhs * self
+            }
+        }
+        impl Div<f64> for $t {
+            type Output = $t;
+
+            fn div(self, rhs: f64) -> $t {
+                $t { $($field: self.$field / rhs),* }
+            }
+        }
+        impl Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                $t { $($field: -self.$field),* }
+            }
+        }
+        impl AddAssign for $t {
+            fn add_assign(&mut self, rhs: $t) {
+                *self = *self + rhs;
+            }
+        }
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, rhs: $t) {
+                *self = *self - rhs;
+            }
+        }
+    )*)
+}
+
+vector!{Vec2 { x, y }, Vec3 { x, y, z }}
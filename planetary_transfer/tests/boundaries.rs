@@ -0,0 +1,68 @@
+// Transfers at the ends of their range of delta-v, where the target sits at an apsis of the transfer orbit and the
+// anomalies land on the edges of the domains of the inverse functions they are found with.
+
+use std::f64::consts::PI;
+
+use planetary_transfer::{Transfer, Velocity};
+
+mod common;
+use common::transfer;
+
+const TOLERANCE: f64 = 1E-9;
+
+// Half the period of the Hohmann transfer, which any transfer with the target at its apsis takes.
+fn half_period(transfer: &Transfer) -> f64 {
+    let mut hohmann = *transfer;
+    hohmann.set_delta_v(transfer.delta_v_hohmann());
+    hohmann.period().unwrap().s() / 2.0
+}
+
+fn assert_apsis(transfer: &Transfer) {
+    assert_eq!(transfer.check(), Ok(()));
+    let arrival = transfer.target_true_anomaly_arrival();
+    assert!((arrival.abs() - PI).abs() <= TOLERANCE, "target at {}", arrival);
+    let radius = transfer.radius_at(arrival).m();
+    assert!((radius - transfer.target().sma().m()).abs() <= TOLERANCE * radius, "target reached at {} m", radius);
+    let time_of_flight = transfer.time_of_flight().s();
+    assert!((time_of_flight - half_period(transfer)).abs() <= TOLERANCE * time_of_flight, "time of flight of {} s", time_of_flight);
+}
+
+#[test]
+fn outward_range() {
+    let mut transfer = transfer(1.0, 1.52366);
+    let range = transfer.valid_delta_v_range();
+    transfer.set_delta_v(*range.start());
+    assert_apsis(&transfer);
+
+    transfer.set_delta_v(transfer.max_velocity());
+    assert_eq!(transfer.check(), Ok(()));
+
+    // Short of the Hohmann transfer, the apoapsis falls short of the target.
+    transfer.set_delta_v(*range.start() - Velocity::from_meters_per_second(1.0));
+    assert!(transfer.check().is_err());
+}
+
+#[test]
+fn inward_range() {
+    let mut transfer = transfer(1.52366, 1.0);
+    let range = transfer.valid_delta_v_range();
+    for delta_v in [*range.end(), *range.start()] {
+        transfer.set_delta_v(delta_v);
+        assert_apsis(&transfer);
+    }
+    // Leaving retrograde at the speed of the Hohmann transfer.
+    assert!(transfer.retrograde());
+
+    // Past the Hohmann transfer, the periapsis stays above the target.
+    transfer.set_delta_v(*range.end() + Velocity::from_meters_per_second(1.0));
+    assert!(transfer.check().is_err());
+}
+
+#[test]
+fn close_orbits() {
+    for target in [1.0 + 1E-8, 1.0 - 1E-8] {
+        let mut transfer = transfer(1.0, target);
+        transfer.set_delta_v(transfer.delta_v_hohmann());
+        assert_apsis(&transfer);
+    }
+}
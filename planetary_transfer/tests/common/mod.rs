@@ -0,0 +1,20 @@
+// Fixtures shared by the integration tests, each test only using some of them.
+#![allow(dead_code)]
+
+use planetary_transfer::{Distance, Mass, Parent, Planet, Transfer, TransferError};
+
+// Planets on circular orbits of semi-major axes in au around a parent of a solar mass.
+pub fn planets(origin: f64, target: f64) -> (Planet, Planet) {
+    let parent = Parent::new(Mass::from_solar(1.0));
+    (Planet::new(Distance::from_astronomical_unit(origin), parent), Planet::new(Distance::from_astronomical_unit(target), parent))
+}
+
+pub fn transfer(origin: f64, target: f64) -> Transfer {
+    let (origin, target) = planets(origin, target);
+    Transfer::new(origin, target)
+}
+
+pub fn try_transfer(origin: f64, target: f64) -> Result<Transfer, TransferError> {
+    let (origin, target) = planets(origin, target);
+    Transfer::try_new(origin, target)
+}
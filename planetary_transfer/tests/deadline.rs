@@ -0,0 +1,53 @@
+// Transfers solved backwards from the date they must reach the target by.
+
+use planetary_transfer::{Duration, Transfer, Velocity};
+
+mod common;
+
+fn earth_mars() -> Transfer {
+    let mut transfer = common::transfer(1.0, 1.52366);
+    transfer.set_delta_v(transfer.delta_v_hohmann());
+    transfer
+}
+
+// Arrival of the first window of a transfer with the given delta-v.
+fn first_arrival(transfer: &Transfer, delta_v: Velocity, phase_angle: f64) -> Duration {
+    let mut transfer = *transfer;
+    transfer.set_delta_v(delta_v);
+    transfer.wait_time_until_window(phase_angle) + transfer.time_of_flight()
+}
+
+#[test]
+fn latest_hohmann_window() {
+    let transfer = earth_mars();
+    let deadline = Duration::from_years(5.0);
+    let solution = transfer.solve_for_arrival(0.0, deadline).unwrap();
+
+    assert_eq!(solution.delta_v, transfer.delta_v_hohmann());
+    assert!(solution.arrival <= deadline);
+    // The next window arrives too late.
+    assert!(solution.arrival + transfer.synodic_period() > deadline);
+    assert!((solution.arrival - solution.departure - transfer.time_of_flight()).s().abs() < 1E-6);
+}
+
+#[test]
+fn faster_than_hohmann() {
+    let transfer = earth_mars();
+    let phase_angle = transfer.target_true_anomaly_departure();
+    let deadline = transfer.time_of_flight() * 0.8;
+    let solution = transfer.solve_for_arrival(phase_angle, deadline).unwrap();
+
+    assert!(solution.delta_v > transfer.delta_v_hohmann());
+    assert!(solution.arrival <= deadline);
+    // Faster transfers first need a larger phase angle than the Hohmann transfer, the cheapest one making it departing
+    // as the phase angle it needs comes back round to the one at the epoch.
+    assert!(solution.departure < Duration::from_days(1.0));
+    // Any less delta-v arrives too late.
+    let cheaper = solution.delta_v - Velocity::from_meters_per_second(1E-3);
+    assert!(first_arrival(&transfer, cheaper, phase_angle) > deadline);
+}
+
+#[test]
+fn unreachable() {
+    assert!(earth_mars().solve_for_arrival(0.0, Duration::from_days(1.0)).is_none());
+}
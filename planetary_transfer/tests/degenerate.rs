@@ -0,0 +1,20 @@
+// Transfers between orbits too close to each other to leave anything to transfer between.
+
+use planetary_transfer::{TransferError, DEGENERATE_SMA_TOLERANCE};
+
+mod common;
+use common::try_transfer;
+
+#[test]
+fn shared_orbit() {
+    assert_eq!(try_transfer(1.0, 1.0).err(), Some(TransferError::DegenerateTransfer));
+    assert_eq!(try_transfer(1.0, 1.0 + DEGENERATE_SMA_TOLERANCE / 2.0).err(), Some(TransferError::DegenerateTransfer));
+}
+
+#[test]
+fn closest_orbits() {
+    for target in [1.0 + DEGENERATE_SMA_TOLERANCE * 2.0, 1.0 - DEGENERATE_SMA_TOLERANCE * 2.0] {
+        let transfer = try_transfer(1.0, target).unwrap();
+        assert_eq!(transfer.check(), Ok(()), "target at {} au", target);
+    }
+}
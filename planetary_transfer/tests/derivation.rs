@@ -0,0 +1,30 @@
+// Derivations must end on the same results as the transfer, for every kind of reference transfer.
+
+use planetary_transfer::reference::REFERENCE_TRANSFERS;
+
+#[test]
+fn derivation_results() {
+    for reference in REFERENCE_TRANSFERS {
+        let transfer = reference.transfer();
+        let derivation = transfer.derivation();
+        let value = |symbol| derivation.steps.iter().find(|step| step.symbol == symbol).unwrap().value;
+
+        assert_eq!(value("Δvₕ"), transfer.delta_v_hohmann().mps(), "{}", reference.name);
+        assert_eq!(value("t"), transfer.time_of_flight().s(), "{}", reference.name);
+        assert_eq!(value("C3"), transfer.c3(), "{}", reference.name);
+        assert_eq!(value("v∞₂"), transfer.arrival_v_infinity().mps(), "{}", reference.name);
+    }
+}
+
+#[test]
+fn latex_braces_balance() {
+    for reference in REFERENCE_TRANSFERS {
+        let latex = reference.transfer().derivation().to_latex();
+        let depth = latex.chars().try_fold(0i32, |depth, c| match c {
+            '{' => Some(depth + 1),
+            '}' => (depth > 0).then(|| depth - 1),
+            _ => Some(depth),
+        });
+        assert_eq!(depth, Some(0), "{}", reference.name);
+    }
+}
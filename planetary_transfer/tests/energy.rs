@@ -0,0 +1,33 @@
+// Energy and angular momentum of transfers, consistent with the speeds the vis-viva equation gives.
+
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, Planet, Transfer, Velocity};
+
+fn transfer(delta_v: Velocity) -> Transfer {
+    let sun = catalog::bodies()[0].parent();
+    let mut transfer = Transfer::new(
+        Planet::new(Distance::from_astronomical_unit(1.0), sun),
+        Planet::new(Distance::from_astronomical_unit(5.2), sun),
+    );
+    transfer.set_delta_v(delta_v);
+    transfer
+}
+
+#[test]
+fn vis_viva() {
+    let transfer = transfer(Velocity::from_kilometers_per_second(9.0));
+    let mu = catalog::bodies()[0].parent().gravitational_parameter().m3ps2();
+    let radius = transfer.origin().sma();
+    let speed = transfer.vis_viva_speed_at(radius);
+    let energy = speed.mps().powi(2) / 2.0 - mu / radius.m();
+    assert!((transfer.specific_orbital_energy().jpkg() - energy).abs() < energy.abs() * 1E-9);
+    // Departing at the periapsis, perpendicular to the radius.
+    assert!((transfer.angular_momentum().m2ps() - radius.m() * speed.mps()).abs() < transfer.angular_momentum().m2ps() * 1E-9);
+    assert!(transfer.specific_orbital_energy().jpkg() < 0.0);
+}
+
+#[test]
+fn open() {
+    // Past escape, the transfer leaves the parent for good.
+    assert!(transfer(Velocity::from_kilometers_per_second(15.0)).specific_orbital_energy().km2ps2() > 0.0);
+}
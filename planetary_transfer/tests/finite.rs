@@ -0,0 +1,35 @@
+// Values that aren't finite rejected where they enter the library rather than spreading through the results.
+
+use planetary_transfer::{Distance, Finite, Mass, Parent, Planet, QuantityError, Transfer, TransferError, Velocity};
+
+#[test]
+fn boundaries() {
+    assert_eq!(Finite::new(f64::NAN), Err(QuantityError::NonFinite));
+    assert_eq!(Finite::try_from(f64::INFINITY), Err(QuantityError::NonFinite));
+    assert_eq!(Parent::try_new(Mass::from_kilograms(f64::NAN)).err(), Some(QuantityError::NonFinite));
+    assert_eq!(Parent::try_new(Mass::from_kilograms(0.0)).err(), Some(QuantityError::Zero));
+
+    let parent = Parent::try_new(Mass::from_solar(1.0)).unwrap();
+    assert_eq!(Planet::try_new(Distance::from_meters(f64::INFINITY), parent).err(), Some(QuantityError::NonFinite));
+    assert_eq!(Planet::try_new(Distance::from_meters(-1.0), parent).err(), Some(QuantityError::Negative));
+    let origin = Planet::try_new(Distance::from_astronomical_unit(1.0), parent).unwrap();
+    assert_eq!(origin.try_with_mass(Mass::from_kilograms(f64::NAN)).err(), Some(QuantityError::NonFinite));
+
+    // Planets around different parents are rejected rather than panicking.
+    let target = Planet::new(Distance::from_astronomical_unit(1.52366), Parent::new(Mass::from_solar(2.0)));
+    assert_eq!(Transfer::try_new(origin, target).err(), Some(TransferError::DifferentParents));
+    assert!(Transfer::builder(origin, target).build().is_err());
+}
+
+#[test]
+fn results() {
+    let parent = Parent::new(Mass::from_solar(1.0));
+    let mut transfer = Transfer::new(Planet::new(Distance::from_astronomical_unit(1.0), parent), Planet::new(Distance::from_astronomical_unit(1.52366), parent));
+    assert_eq!(transfer.try_sma().unwrap().m(), transfer.sma().m());
+    assert_eq!(transfer.try_time_of_flight().unwrap().s(), transfer.time_of_flight().s());
+
+    // A delta-v that isn't finite leaves the transfer as it was.
+    let delta_v = transfer.try_launch_velocity().unwrap();
+    assert_eq!(transfer.try_set_delta_v(Velocity::from_meters_per_second(f64::NAN)), Err(QuantityError::NonFinite));
+    assert_eq!(transfer.try_launch_velocity().unwrap().mps(), delta_v.mps());
+}
@@ -0,0 +1,75 @@
+// Radiation pressure and drag, pushing the spacecraft off the trajectory gravity alone gives it.
+
+use planetary_transfer::catalog;
+use planetary_transfer::{atmospheric_density, Distance, Parent, Planet, SurfaceForces, Transfer};
+
+fn parent(name: &str) -> Parent {
+    catalog::bodies().iter().find(|body| body.name == name).unwrap().parent()
+}
+
+#[test]
+fn density() {
+    let earth = parent("Earth");
+    let radius = earth.radius().unwrap();
+    let surface = atmospheric_density(&earth, radius);
+    assert!(surface > 1.1 && surface < 1.35, "{} kg/m³", surface);
+    assert!(atmospheric_density(&earth, radius + Distance::from_kilometers(50.0)) < surface * 1E-2);
+    assert_eq!(atmospheric_density(&parent("Mun"), Distance::from_kilometers(210.0)), 0.0);
+}
+
+#[test]
+fn orbit_lifetime() {
+    let kerbin = parent("Kerbin");
+    let forces = SurfaceForces { drag: true, ..SurfaceForces::default() };
+    let low = forces.orbit_lifetime(&kerbin, Distance::from_kilometers(650.0)).unwrap();
+    let high = forces.orbit_lifetime(&kerbin, Distance::from_kilometers(665.0)).unwrap();
+    assert!(low.s() > 0.0 && high.s() > low.s());
+    // Lighter spacecraft for their area come down sooner.
+    let light = SurfaceForces { area_to_mass: forces.area_to_mass * 10.0, ..forces };
+    assert!((light.orbit_lifetime(&kerbin, Distance::from_kilometers(650.0)).unwrap().s() - low.s() / 10.0).abs() < low.s() * 1E-9);
+
+    // Above the atmosphere orbits don't decay.
+    assert_eq!(forces.orbit_lifetime(&kerbin, Distance::from_kilometers(700.0)), None);
+    assert_eq!(SurfaceForces::default().orbit_lifetime(&kerbin, Distance::from_kilometers(650.0)), None);
+    assert_eq!(forces.orbit_lifetime(&parent("Mun"), Distance::from_kilometers(210.0)), None);
+}
+
+#[test]
+fn radiation_pressure() {
+    let sun = parent("Sun");
+    let transfer = Transfer::new(
+        Planet::new(Distance::from_astronomical_unit(1.0), sun),
+        Planet::new(Distance::from_astronomical_unit(1.52366), sun),
+    );
+    assert!(transfer.surface_effect(&SurfaceForces::default()).is_none());
+
+    let forces = SurfaceForces { radiation_pressure: true, ..SurfaceForces::default() };
+    let effect = transfer.surface_effect(&forces).unwrap();
+    assert!(effect.displacement.km() > 1.0, "{} km", effect.displacement.km());
+    // The push grows with the area of the spacecraft for its mass.
+    let sail = SurfaceForces { area_to_mass: 10.0, ..forces };
+    let sailing = transfer.surface_effect(&sail).unwrap();
+    assert!(sailing.displacement.m() > effect.displacement.m() * 100.0);
+    assert!(sailing.significant && !effect.significant);
+}
+
+#[test]
+fn starlight() {
+    let forces = SurfaceForces { radiation_pressure: true, ..SurfaceForces::default() };
+    let transfer = |parent: Parent| Transfer::new(
+        Planet::new(Distance::from_kilometers(7_000.0), parent),
+        Planet::new(Distance::from_kilometers(42_164.0), parent),
+    );
+    // Planets are lit by their star from its distance, the Moon as the Earth.
+    let (earth, moon) = (parent("Earth"), parent("Moon"));
+    assert_eq!(earth.star_distance().unwrap().m(), moon.star_distance().unwrap().m());
+    assert_eq!(parent("Sun").star_distance(), None);
+    let lit = transfer(earth).surface_effect(&forces).unwrap();
+    assert!(lit.displacement.m() > 1.0, "{} m", lit.displacement.m());
+    // Kerbol lights Kerbin as the Sun does the Earth.
+    assert!((parent("Kerbin").luminosity().unwrap() * (1.0 / 0.0909f64).powi(2) - 1.0).abs() < 1E-2);
+
+    // Without a star there is no light to push the spacecraft.
+    let dark = transfer(Parent::new(earth.mass())).surface_effect(&forces).unwrap();
+    assert_eq!(dark.displacement.m(), 0.0);
+}
@@ -0,0 +1,95 @@
+// Golden-file tests of the transfer diagram, comparing its polylines to the ones stored in `tests/golden`.
+// Run with `UPDATE_GOLDEN=1` to overwrite the stored files after an intended change of the geometry.
+
+use std::{env, fs, path::PathBuf};
+
+use serde_json::{json, Value};
+
+use planetary_transfer::geometry::{self, Diagram, MAX_TIME_TICKS};
+use planetary_transfer::{Duration, Transfer, Velocity};
+
+mod common;
+
+const TOLERANCE: f64 = 1E-9;
+
+fn transfer(origin: f64, target: f64, extra_delta_v: f64) -> Transfer {
+    let mut transfer = common::transfer(origin, target);
+    transfer.set_delta_v(transfer.delta_v_hohmann() + Velocity::from_meters_per_second(extra_delta_v));
+    transfer
+}
+
+fn to_json(diagram: &Diagram) -> Value {
+    json!({
+        "origin": diagram.origin,
+        "target": diagram.target,
+        "transfer": diagram.transfer,
+        "protractor": diagram.protractor,
+    })
+}
+
+// Numbers are compared with an absolute tolerance, the coordinates being of the order of unity.
+fn assert_close(actual: &Value, expected: &Value, path: &str) {
+    match (actual, expected) {
+        (Value::Array(actual), Value::Array(expected)) => {
+            assert_eq!(actual.len(), expected.len(), "Lengths differ at {}", path);
+            for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+                assert_close(actual, expected, &format!("{}[{}]", path, i));
+            }
+        }
+        (Value::Object(actual), Value::Object(expected)) => {
+            assert_eq!(actual.len(), expected.len(), "Keys differ at {}", path);
+            for (key, expected) in expected {
+                let actual = actual.get(key).unwrap_or_else(|| panic!("Missing {}.{}", path, key));
+                assert_close(actual, expected, &format!("{}.{}", path, key));
+            }
+        }
+        (Value::Number(actual), Value::Number(expected)) => {
+            let (actual, expected) = (actual.as_f64().unwrap(), expected.as_f64().unwrap());
+            assert!((actual - expected).abs() <= TOLERANCE, "{} is {} instead of {}", path, actual, expected);
+        }
+        _ => assert_eq!(actual, expected, "Values differ at {}", path),
+    }
+}
+
+fn check(name: &str, transfer: Transfer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{}.json", name));
+    let actual = to_json(&Diagram::new(&transfer));
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, serde_json::to_string(&actual).unwrap()).unwrap();
+        return;
+    }
+    let expected: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_close(&actual, &expected, name);
+}
+
+#[test]
+fn hohmann_outward() {
+    check("hohmann_outward", transfer(1.0, 1.52366, 0.0));
+}
+
+#[test]
+fn hohmann_inward() {
+    check("hohmann_inward", transfer(1.52366, 1.0, 0.0));
+}
+
+#[test]
+fn fast_outward() {
+    check("fast_outward", transfer(1.0, 1.52366, 3000.0));
+}
+
+#[test]
+fn retrograde_inward() {
+    check("retrograde_inward", transfer(1.0, 0.723, -45000.0));
+}
+
+#[test]
+fn time_ticks() {
+    let transfer = transfer(1.0, 1.52366, 0.0);
+    assert_eq!(geometry::time_ticks(&transfer, Duration::from_days(30.0), 1.0).len(), 8);
+    // Intervals that would never reach the arrival place no marker, nor too short ones more than the most there can be.
+    assert!(geometry::time_ticks(&transfer, Duration::from_seconds(0.0), 1.0).is_empty());
+    assert!(geometry::time_ticks(&transfer, Duration::from_seconds(-1.0), 1.0).is_empty());
+    assert!(geometry::time_ticks(&transfer, Duration::from_seconds(f64::NAN), 1.0).is_empty());
+    assert_eq!(geometry::time_ticks(&transfer, Duration::from_seconds(1.0), 1.0).len(), MAX_TIME_TICKS);
+}
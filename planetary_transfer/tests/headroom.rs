@@ -0,0 +1,67 @@
+// Range of delta-v offered by frontends, scaling with how far the target is rather than with the Hohmann transfer.
+
+use std::f64::consts::SQRT_2;
+
+use planetary_transfer::{Transfer, DEFAULT_HEADROOM};
+
+mod common;
+use common::transfer;
+
+const TOLERANCE: f64 = 1E-9;
+
+fn escape(transfer: &Transfer) -> f64 {
+    transfer.origin().orbital_velocity().mps() * (SQRT_2 - 1.0)
+}
+
+#[test]
+fn max_velocity() {
+    // Outward transfers take more delta-v than the Hohmann transfer, inward ones less, down to burning retrograde.
+    let outward = transfer(1.0, 1.52366);
+    assert!(outward.max_velocity().mps() > escape(&outward));
+    let inward = transfer(1.52366, 1.0);
+    assert!(inward.max_velocity().mps() < 0.0);
+    assert!((inward.max_velocity().mps() - inward.valid_delta_v_range().start().mps()).abs() <= TOLERANCE);
+}
+
+#[test]
+fn outward() {
+    for target in [1.01, 1.52366, 5.2, 30.0] {
+        let transfer = transfer(1.0, target);
+        let hohmann = transfer.delta_v_hohmann().mps();
+
+        let none = transfer.suggested_delta_v_range(0.0);
+        assert_eq!((none.start().mps(), none.end().mps()), (hohmann, hohmann));
+
+        // Up to escaping the parent, however close or far the target is.
+        let suggested = transfer.suggested_delta_v_range(DEFAULT_HEADROOM);
+        assert_eq!(suggested.start().mps(), hohmann);
+        assert!((suggested.end().mps() - escape(&transfer)).abs() <= TOLERANCE * escape(&transfer), "target at {} au", target);
+
+        // Past escape with more headroom, within the range accepted when solving transfers.
+        let wider = transfer.suggested_delta_v_range(2.0);
+        assert!(wider.end().mps() > suggested.end().mps());
+        assert!(transfer.suggested_delta_v_range(100.0).end().mps() <= transfer.max_velocity().mps());
+        for delta_v in [*suggested.end(), *wider.end()] {
+            let mut transfer = transfer;
+            transfer.set_delta_v(delta_v);
+            assert_eq!(transfer.check(), Ok(()), "target at {} au", target);
+        }
+    }
+}
+
+#[test]
+fn inward() {
+    let transfer = transfer(5.2, 1.0);
+    let (hohmann, valid) = (transfer.delta_v_hohmann().mps(), transfer.valid_delta_v_range());
+
+    let suggested = transfer.suggested_delta_v_range(0.5);
+    assert_eq!(suggested.end().mps(), hohmann);
+    assert!((suggested.start().mps() - (hohmann + valid.start().mps()) / 2.0).abs() <= TOLERANCE * hohmann.abs());
+
+    // The whole valid range, never more.
+    for headroom in [DEFAULT_HEADROOM, 3.0] {
+        let suggested = transfer.suggested_delta_v_range(headroom);
+        assert_eq!(suggested.start().mps(), valid.start().mps());
+        assert_eq!(suggested.end().mps(), valid.end().mps());
+    }
+}
@@ -0,0 +1,30 @@
+// Departure burns from a parking orbit as maneuver nodes, their length and loss following the finite burn.
+
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, FiniteBurn, ManeuverNode, Parent, Planet, Transfer, STANDARD_GRAVITY};
+
+#[test]
+fn departure() {
+    let bodies = catalog::bodies();
+    let (kerbol, kerbin) = (bodies[10].parent(), bodies[11].parent());
+    let transfer = Transfer::new(Planet::new(Distance::from_meters(13_599_840_256.0), kerbol), Planet::new(Distance::from_meters(20_726_155_264.0), kerbol));
+    let parking_orbit = Distance::from_kilometers(700.0);
+    let node = ManeuverNode::departure(&transfer, &kerbin, parking_orbit, 0.0, 10.0).unwrap();
+
+    // The escape from the parking orbit, about a kilometer per second for Duna, rather than the excess velocity.
+    let escape = kerbin.escape_delta_v(parking_orbit, transfer.departure_v_infinity());
+    assert_eq!(node.prograde.mps(), escape.mps());
+    assert!(node.prograde.mps() > 1000.0 && node.prograde.mps() < 1100.0, "{} m/s", node.prograde.mps());
+    assert_eq!(node.normal.mps(), 0.0);
+
+    let burn = FiniteBurn::new(&kerbin, parking_orbit, escape, 10.0 / STANDARD_GRAVITY).unwrap();
+    assert_eq!(node.burn_time.s(), burn.burn_time.s());
+    assert_eq!(node.finite_burn_loss.mps(), burn.loss().mps());
+    assert!(node.finite_burn_loss.mps() > 0.0);
+
+    // Tilting the escape costs more than escaping in the plane.
+    let tilted = ManeuverNode::departure(&transfer, &kerbin, parking_orbit, 0.1, 10.0).unwrap();
+    assert!(tilted.normal.mps() > 0.0 && tilted.delta_v().mps() > node.delta_v().mps());
+    // An engine too weak never reaches the escape within the revolutions searched.
+    assert!(ManeuverNode::departure(&transfer, &Parent::new(kerbin.mass()), parking_orbit, 0.0, 1E-4).is_none());
+}
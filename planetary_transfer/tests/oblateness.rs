@@ -0,0 +1,45 @@
+// Regression of the nodes of parking orbits around oblate parents.
+
+use std::f64::consts::TAU;
+
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, Duration, Parent, ParkingOrbit};
+
+fn parent(name: &str) -> Parent {
+    catalog::bodies().iter().find(|body| body.name == name).unwrap().parent()
+}
+
+#[test]
+fn sun_synchronous() {
+    // A sun-synchronous orbit turns eastward once a year, following the Sun.
+    let earth = parent("Earth");
+    let orbit = ParkingOrbit::circular(Distance::from_kilometers(6_378.137 + 800.0));
+    let rate = orbit.nodal_precession_rate(&earth, 98.6_f64.to_radians()).unwrap();
+    let yearly = TAU / Duration::from_days(365.2422).s();
+    assert!((rate - yearly).abs() < yearly * 0.01, "{} rad/s", rate);
+}
+
+#[test]
+fn low_earth_orbit() {
+    let earth = parent("Earth");
+    let orbit = ParkingOrbit::circular(Distance::from_kilometers(6_571.0));
+    let inclination = 28.5_f64.to_radians();
+    let daily = orbit.nodal_drift(&earth, inclination, Duration::from_days(1.0)).unwrap().to_degrees();
+    assert!(daily < -7.7 && daily > -8.0, "{}° a day", daily);
+
+    // Polar orbits keep their plane, retrograde ones turning the other way.
+    assert!(orbit.nodal_precession_rate(&earth, TAU / 4.0).unwrap().abs() < 1E-20);
+    assert!(orbit.nodal_precession_rate(&earth, TAU / 2.0 - inclination).unwrap() > 0.0);
+    // Eccentric orbits of the same semi-major axis turn faster.
+    let eccentric = ParkingOrbit::new(Distance::from_kilometers(6_571.0), Distance::from_kilometers(8_000.0));
+    let circular = ParkingOrbit::circular(eccentric.sma());
+    assert!(eccentric.nodal_precession_rate(&earth, inclination) < circular.nodal_precession_rate(&earth, inclination));
+}
+
+#[test]
+fn point_masses() {
+    // The bodies of KSP and parents without a known J2 leave the plane of the orbit where it is.
+    let orbit = ParkingOrbit::circular(Distance::from_kilometers(700.0));
+    assert_eq!(orbit.nodal_precession_rate(&parent("Kerbin"), 0.5), None);
+    assert_eq!(orbit.nodal_drift(&Parent::from_gravitational_parameter(parent("Earth").gravitational_parameter()), 0.5, Duration::from_days(1.0)), None);
+}
@@ -0,0 +1,25 @@
+// Transfers of the porkchop grid around the timing of the Hohmann transfer must cost about as much, on either side
+// of half a revolution.
+
+use planetary_transfer::reference::REFERENCE_TRANSFERS;
+use planetary_transfer::{Duration, Porkchop};
+
+#[test]
+fn hohmann_neighbourhood() {
+    let hohmann = REFERENCE_TRANSFERS[0].transfer();
+    let time_of_flight = hohmann.time_of_flight();
+    let porkchop = Porkchop::new(
+        &hohmann.origin(),
+        &hohmann.target(),
+        hohmann.target_true_anomaly_departure(),
+        (Duration::from_seconds(0.0), Duration::from_seconds(0.0)),
+        (time_of_flight * 0.99, time_of_flight * 1.01),
+        (1, 2),
+    );
+
+    let expected = (hohmann.departure_v_infinity() + hohmann.arrival_v_infinity()).mps();
+    for point in &porkchop.points[0] {
+        let total = point.expect("transfers close to half a revolution are solved").total().mps();
+        assert!((total - expected).abs() < 0.05 * expected, "{} m/s instead of about {} m/s", total, expected);
+    }
+}
@@ -0,0 +1,22 @@
+// Numerical integration of a transfer checking the analytic arrival, whole or a few steps at a time.
+
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, Planet, Transfer};
+
+#[test]
+fn verification() {
+    let sun = catalog::bodies()[0].parent();
+    let transfer = Transfer::new(Planet::new(Distance::from_astronomical_unit(1.0), sun), Planet::new(Distance::from_astronomical_unit(1.52366), sun));
+    let verification = transfer.verify(1_000);
+    assert_eq!(verification.path.len(), 1_001);
+    assert!(verification.error.km() < 1_000.0, "{} km", verification.error.km());
+
+    // Spread over several calls, the integration ends up at the same place.
+    let mut verifying = transfer.verifying(1_000);
+    let calls = (1..).find(|_| verifying.advance(300).is_some()).unwrap();
+    assert_eq!(calls, 4);
+    let mut verifying = transfer.verifying(1_000);
+    let chunked = std::iter::repeat_with(|| verifying.advance(7)).flatten().next().unwrap();
+    assert_eq!(chunked.numerical, verification.numerical);
+    assert_eq!(chunked.path, verification.path);
+}
@@ -0,0 +1,32 @@
+// Random systems span every scale the calculator is used at, from moons of rocky planets to planets of giant stars:
+// the transfers across them must keep finite results, reaching the target, and a seed must always give the same one.
+
+use planetary_transfer::RandomSystem;
+
+const SYSTEMS: u64 = 2000;
+
+#[test]
+fn finite_across_scales() {
+    let mut seed = 0;
+    for _ in 0..SYSTEMS {
+        let system = RandomSystem::new(seed);
+        let transfer = system.transfer();
+        let results = [
+            transfer.launch_velocity().mps(),
+            transfer.time_of_flight().s(),
+            transfer.target_true_anomaly_departure(),
+            transfer.departure_v_infinity().mps(),
+            transfer.arrival_v_infinity().mps(),
+        ];
+        assert!(results.iter().all(|x| x.is_finite()), "seed {}: {:?} gives {:?}", seed, system, results);
+        assert!(transfer.time_of_flight().s() > 0.0, "seed {}: {:?} arrives before departing", seed, system);
+        seed = RandomSystem::next_seed(seed);
+    }
+}
+
+#[test]
+fn reproducible() {
+    for seed in [0, 1, 42, u64::MAX] {
+        assert_eq!(RandomSystem::new(seed), RandomSystem::new(seed));
+    }
+}
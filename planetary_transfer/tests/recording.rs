@@ -0,0 +1,50 @@
+// Recorded sessions must replay the changes they were recorded with, at their time, through scenario files.
+
+use planetary_transfer::reference::REFERENCE_TRANSFERS;
+use planetary_transfer::{Duration, Leg, ParentEntry, Recorder, Replay, ScenarioFile};
+
+fn recorded() -> Recorder {
+    let mut recorder = Recorder::new();
+    for (i, reference) in REFERENCE_TRANSFERS.iter().enumerate() {
+        let transfer = reference.transfer();
+        let parent = ParentEntry { body: None, gravitational_parameter: transfer.origin().orbit().parent.gravitational_parameter().m3ps2() };
+        let leg = Leg::from_transfer(&transfer, None, None);
+        assert!(recorder.record(Duration::from_seconds(i as f64), parent.clone(), leg.clone()));
+        // Unchanged parameters, as from the next frame, are not recorded again.
+        assert!(!recorder.record(Duration::from_seconds(i as f64 + 0.5), parent, leg));
+    }
+    recorder
+}
+
+#[test]
+fn round_trip() {
+    let recorder = recorded();
+    let keyframes = recorder.keyframes().to_vec();
+    assert_eq!(keyframes.len(), REFERENCE_TRANSFERS.len());
+
+    let file = recorder.finish("session").unwrap();
+    assert_eq!(file.legs[0], keyframes[0].leg);
+    let loaded = ScenarioFile::from_json(&file.to_json()).unwrap();
+    assert_eq!(loaded.recording, keyframes);
+
+    // Scenarios without a recording are written as before.
+    let plain = ScenarioFile::new("plain", file.parent.clone(), file.legs[0].clone());
+    assert!(!plain.to_json().contains("recording"));
+    assert_eq!(Replay::new(&plain), None);
+    assert_eq!(Recorder::new().finish("empty"), None);
+}
+
+#[test]
+fn replay() {
+    let file = recorded().finish("session").unwrap();
+    let mut replay = Replay::new(&file).unwrap();
+    let last = file.recording.len() - 1;
+    assert_eq!(replay.duration().s(), last as f64);
+
+    assert_eq!(replay.advance(Duration::from_seconds(0.0)), Some(&file.recording[0]));
+    assert_eq!(replay.advance(Duration::from_seconds(0.5)), None);
+    // Keyframes passed over between two frames are skipped for the latest one.
+    assert_eq!(replay.advance(Duration::from_seconds(last as f64)), Some(&file.recording[last]));
+    assert!(replay.finished());
+    assert_eq!(replay.advance(Duration::from_seconds(last as f64 + 1.0)), None);
+}
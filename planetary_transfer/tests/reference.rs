@@ -0,0 +1,11 @@
+// Results of the reference transfers, which must stay within their documented tolerances.
+
+use planetary_transfer::reference::{Expected, REFERENCE_TRANSFERS};
+
+#[test]
+fn reference_transfers() {
+    for reference in REFERENCE_TRANSFERS {
+        let actual = Expected::of(&reference.transfer());
+        assert!(reference.expected.matches(&actual), "{}: {:?} instead of {:?}", reference.name, actual, reference.expected);
+    }
+}
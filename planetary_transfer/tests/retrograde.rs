@@ -0,0 +1,52 @@
+// Transfers to targets orbiting against the origin, as from a low orbit of Neptune to Triton.
+
+use std::f64::consts::TAU;
+
+use planetary_transfer::catalog;
+use planetary_transfer::{Distance, Duration, Planet, Transfer};
+
+fn transfer(retrograde: bool) -> Transfer {
+    let bodies = catalog::bodies();
+    let neptune = bodies.iter().find(|body| body.name == "Neptune").unwrap();
+    let triton = bodies.iter().find(|body| body.name == "Triton").unwrap();
+    assert!(triton.retrograde);
+    let (_, sma) = triton.orbit.unwrap();
+    Transfer::new(
+        Planet::new(Distance::from_kilometers(30_000.0), neptune.parent()),
+        Planet::new(sma, neptune.parent()).with_retrograde(retrograde),
+    )
+}
+
+#[test]
+fn arrival_v_infinity() {
+    let (prograde, retrograde) = (transfer(false), transfer(true));
+    let speed = retrograde.target().orbital_velocity().mps();
+    assert_eq!(retrograde.target_velocity().mps(), -speed);
+
+    // A Hohmann transfer arrives tangentially, against the target when it orbits the other way.
+    let arrival = retrograde.arrival_velocity().mps();
+    assert!((prograde.arrival_v_infinity().mps() - (speed - arrival)).abs() < 1E-6);
+    assert!((retrograde.arrival_v_infinity().mps() - (speed + arrival)).abs() < 1E-6);
+}
+
+#[test]
+fn phase_geometry() {
+    let (prograde, retrograde) = (transfer(false), transfer(true));
+    let origin = prograde.origin().mean_motion();
+    let target = prograde.target().mean_motion();
+    assert_eq!(retrograde.target_mean_motion(), -target);
+
+    let synodic = |transfer: &Transfer| transfer.synodic_period().s();
+    assert!((synodic(&prograde) - TAU / (origin - target)).abs() < 1E-6);
+    assert!((synodic(&retrograde) - TAU / (origin + target)).abs() < 1E-6);
+
+    // The target moves back towards the arrival point during the flight instead of ahead of it.
+    let travelled = target * retrograde.time_of_flight().s();
+    let lead = |transfer: &Transfer| (transfer.target_true_anomaly_departure() - transfer.target_true_anomaly_arrival()).rem_euclid(TAU);
+    assert!((lead(&prograde) - (TAU - travelled).rem_euclid(TAU)).abs() < 1E-9);
+    assert!((lead(&retrograde) - travelled.rem_euclid(TAU)).abs() < 1E-9);
+
+    // The window comes back once the target has lapped the origin.
+    let wait = retrograde.wait_time_until_window(retrograde.target_true_anomaly_departure());
+    assert!(wait < Duration::from_seconds(1.0) || (synodic(&retrograde) - wait.s()).abs() < 1.0);
+}
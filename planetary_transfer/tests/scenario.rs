@@ -0,0 +1,42 @@
+// Scenario files must load back to the transfers they were written from, including those written by newer versions.
+
+use planetary_transfer::reference::REFERENCE_TRANSFERS;
+use planetary_transfer::{Leg, ParentEntry, ScenarioError, ScenarioFile, SCENARIO_MAX_REVOLUTIONS, SCENARIO_VERSION};
+
+#[test]
+fn round_trip() {
+    for reference in REFERENCE_TRANSFERS {
+        let transfer = reference.transfer();
+        let parent = ParentEntry { body: None, gravitational_parameter: transfer.origin().orbit().parent.gravitational_parameter().m3ps2() };
+        let file = ScenarioFile::new(reference.name, parent, Leg::from_transfer(&transfer, None, None));
+
+        let loaded = ScenarioFile::from_json(&file.to_json()).unwrap();
+        assert_eq!(loaded, file, "{}", reference.name);
+        let solved = loaded.transfers().unwrap()[0];
+        assert_eq!(solved.time_of_flight().s(), transfer.time_of_flight().s(), "{}", reference.name);
+    }
+}
+
+#[test]
+fn newer_versions() {
+    let newer = format!(
+        r#"{{"version": {}, "parent": {{"gravitational_parameter": 1.327e20, "rings": true}},
+            "legs": [{{"origin": {{"sma": 1.496e11}}, "target": {{"sma": 2.279e11}}, "window": 3}}]}}"#,
+        SCENARIO_VERSION + 1,
+    );
+    assert_eq!(ScenarioFile::from_json(&newer).unwrap().legs[0].delta_v, None);
+
+    let incompatible = format!(r#"{{"version": {}, "itinerary": []}}"#, SCENARIO_VERSION + 1);
+    assert!(matches!(ScenarioFile::from_json(&incompatible), Err(ScenarioError::UnsupportedVersion(_))));
+}
+
+#[test]
+fn revolutions() {
+    let file = |revolutions: u32| format!(
+        r#"{{"version": {}, "parent": {{"gravitational_parameter": 1.327e20}},
+            "legs": [{{"origin": {{"sma": 1.496e11}}, "target": {{"sma": 2.279e11}}, "delta_v": 3500.0, "revolutions": {}}}]}}"#,
+        SCENARIO_VERSION, revolutions,
+    );
+    assert_eq!(ScenarioFile::from_json(&file(SCENARIO_MAX_REVOLUTIONS)).unwrap().legs[0].revolutions, SCENARIO_MAX_REVOLUTIONS);
+    assert!(matches!(ScenarioFile::from_json(&file(SCENARIO_MAX_REVOLUTIONS + 1)), Err(ScenarioError::Revolutions(6))));
+}
@@ -0,0 +1,50 @@
+// Yearly delta-v keeping capture orbits, from drag low down to the pull of the parent far out.
+
+use planetary_transfer::catalog::{self, Body};
+use planetary_transfer::{Distance, Planet, StationKeeping, SurfaceForces, STABLE_HILL_FRACTION};
+
+fn body(name: &str) -> Body {
+    catalog::bodies().into_iter().find(|body| body.name == name).unwrap()
+}
+
+fn orbit(body: &Body) -> Planet {
+    let bodies = catalog::bodies();
+    let primary = &bodies[body.primary(&bodies).unwrap()];
+    let (_, sma) = body.orbit.unwrap();
+    Planet::new(sma, primary.parent()).with_mass(body.mass)
+}
+
+fn station_keeping(name: &str, altitude: Distance) -> StationKeeping {
+    let body = body(name);
+    StationKeeping::new(&orbit(&body), &body.parent(), body.radius + altitude, &SurfaceForces::default()).unwrap()
+}
+
+#[test]
+fn drag() {
+    // Drag dominates low around bodies with an atmosphere, and falls off quickly with altitude.
+    let low = station_keeping("Earth", Distance::from_kilometers(150.0));
+    assert!(low.drag.mps() > low.tidal.mps());
+    assert!(station_keeping("Earth", Distance::from_kilometers(250.0)).drag.mps() < low.drag.mps() * 1E-3);
+    assert_eq!(station_keeping("Moon", Distance::from_kilometers(100.0)).drag.mps(), 0.0);
+}
+
+#[test]
+fn tidal() {
+    // Growing as the fourth power of the radius, up to the edge of the stable part of the Hill sphere.
+    let mars = body("Mars");
+    let hill_sphere = orbit(&mars).hill_sphere().unwrap();
+    let budget = |fraction: f64| StationKeeping::new(&orbit(&mars), &mars.parent(), hill_sphere * fraction, &SurfaceForces::default()).unwrap();
+    let (near, far) = (budget(0.1), budget(0.2));
+    assert!((far.tidal.mps() / near.tidal.mps() - 16.0).abs() < 1E-9);
+    assert!(!far.unstable);
+    assert!(budget(STABLE_HILL_FRACTION * 1.01).unstable);
+    assert_eq!(far.annual().mps(), far.drag.mps() + far.tidal.mps());
+}
+
+#[test]
+fn massless() {
+    let mars = body("Mars");
+    let (_, sma) = mars.orbit.unwrap();
+    let massless = Planet::new(sma, body("Sun").parent());
+    assert_eq!(StationKeeping::new(&massless, &mars.parent(), mars.radius * 2.0, &SurfaceForces::default()), None);
+}
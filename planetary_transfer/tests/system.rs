@@ -0,0 +1,34 @@
+// System files must load back to the bodies they were written from, any two of which make a transfer.
+
+use planetary_transfer::{Distance, Mass, ParentEntry, SystemBody, SystemError, SystemFile, TransferError, SYSTEM_VERSION};
+
+fn parent() -> ParentEntry {
+    ParentEntry { body: None, gravitational_parameter: Mass::from_solar(1.0).mu() }
+}
+
+#[test]
+fn round_trip() {
+    let mut inner = SystemBody::new("Inner", Distance::from_astronomical_unit(0.8));
+    inner.mass = Some(Mass::from_earth(1.0).kg());
+    inner.color = [40, 122, 184];
+    let outer = SystemBody::new("Outer", Distance::from_astronomical_unit(2.5));
+    let file = SystemFile::new("Binary", parent(), vec![inner, outer]);
+
+    let loaded = SystemFile::from_json(&file.to_json()).unwrap();
+    assert_eq!(loaded, file);
+    let transfer = loaded.transfer(0, 1).unwrap();
+    assert_eq!(transfer.origin().sma().au(), 0.8);
+    assert_eq!(transfer.target().sma().au(), 2.5);
+    assert!(loaded.transfer(1, 1).is_err());
+    assert_eq!(loaded.transfer(0, 2).err(), Some(TransferError::UnknownBody(2)));
+}
+
+#[test]
+fn optional_fields() {
+    let minimal = r#"{"version": 1, "parent": {"gravitational_parameter": 1.327e20}, "bodies": [{"name": "Rock", "sma": 1.5e11}]}"#;
+    let body = &SystemFile::from_json(minimal).unwrap().bodies[0];
+    assert_eq!((body.mass, body.color), (None, [255, 255, 255]));
+
+    let incompatible = format!(r#"{{"version": {}, "stars": []}}"#, SYSTEM_VERSION + 1);
+    assert!(matches!(SystemFile::from_json(&incompatible), Err(SystemError::UnsupportedVersion(_))));
+}
@@ -0,0 +1,72 @@
+// 3D view of a transfer, showing the inclinations of the orbits that the top-down plot of the app flattens.
+// The geometry comes from the library, this crate only drawing its polylines.
+
+use bevy::prelude::*;
+
+use planetary_transfer::geometry::Diagram3d;
+use planetary_transfer::Transfer;
+
+const ORIGIN_COLOR: Color = Color::rgb(0.2, 0.6, 1.0);
+const TARGET_COLOR: Color = Color::rgb(1.0, 0.4, 0.2);
+const TRANSFER_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+// Radii of the markers, the largest orbit being of unit radius.
+const PARENT_RADIUS: f32 = 0.04;
+const MARKER_RADIUS: f32 = 0.02;
+// In radians per second.
+const CAMERA_SPEED: f32 = 1.0;
+
+#[derive(Resource)]
+pub struct TransferScene(pub Diagram3d);
+
+impl TransferScene {
+    pub fn new(transfer: &Transfer) -> Self {
+        Self(Diagram3d::new(transfer))
+    }
+}
+
+// Draws the transfer of the `TransferScene` resource, the arrow keys turning the camera around the parent.
+pub struct Transfer3dPlugin;
+
+impl Plugin for Transfer3dPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(Update, (draw_transfer, orbit_camera));
+    }
+}
+
+fn point(position: planetary_transfer::Vec3) -> Vec3 {
+    Vec3::new(position.x as f32, position.y as f32, position.z as f32)
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, -2.5, 1.5).looking_at(Vec3::ZERO, Vec3::Z),
+        ..default()
+    });
+}
+
+fn draw_transfer(mut gizmos: Gizmos, scene: Res<TransferScene>) {
+    let diagram = &scene.0;
+    gizmos.linestrip(diagram.origin.iter().copied().map(point), ORIGIN_COLOR);
+    gizmos.linestrip(diagram.target.iter().copied().map(point), TARGET_COLOR);
+    gizmos.linestrip(diagram.transfer.iter().copied().map(point), TRANSFER_COLOR);
+
+    gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, PARENT_RADIUS, Color::YELLOW);
+    gizmos.sphere(point(diagram.departure()), Quat::IDENTITY, MARKER_RADIUS, ORIGIN_COLOR);
+    gizmos.sphere(point(diagram.arrival()), Quat::IDENTITY, MARKER_RADIUS, TARGET_COLOR);
+}
+
+fn orbit_camera(keys: Res<Input<KeyCode>>, time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera>>) {
+    let angle = CAMERA_SPEED * time.delta_seconds();
+    let (mut yaw, mut pitch) = (0.0, 0.0);
+    if keys.pressed(KeyCode::Left) {yaw -= angle}
+    if keys.pressed(KeyCode::Right) {yaw += angle}
+    if keys.pressed(KeyCode::Up) {pitch += angle}
+    if keys.pressed(KeyCode::Down) {pitch -= angle}
+
+    for mut transform in &mut cameras {
+        let right = transform.right();
+        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(yaw) * Quat::from_axis_angle(right, pitch));
+        transform.look_at(Vec3::ZERO, Vec3::Z);
+    }
+}
@@ -0,0 +1,44 @@
+// Usage: transfer3d <origin sma in AU> <target sma in AU> [target inclination in degrees]
+// Shows the Hohmann transfer between both orbits around a star of one solar mass.
+
+use std::env;
+use std::process;
+
+use bevy::prelude::*;
+
+use planetary_transfer::{Distance, Mass, Orbit, Parent, Planet, Transfer};
+use transfer3d::{Transfer3dPlugin, TransferScene};
+
+fn main() {
+    let args: Vec<f64> = env::args().skip(1).map(|arg| arg.parse().unwrap_or(f64::NAN)).collect();
+    let (origin, target, inclination) = match args[..] {
+        [origin, target] => (origin, target, 0.0),
+        [origin, target, inclination] => (origin, target, inclination),
+        _ => {
+            eprintln!("Usage: transfer3d <origin sma in AU> <target sma in AU> [target inclination in degrees]");
+            process::exit(2);
+        }
+    };
+
+    let parent = Parent::new(Mass::from_solar(1.0));
+    let planet = |sma: f64, inclination: f64| Distance::try_from_astronomical_unit(sma)
+        .map(|sma| Planet::from_orbit(Orbit::circular(sma, parent).with_inclination(inclination.to_radians())));
+    let transfer = match (planet(origin, 0.0), planet(target, inclination)) {
+        (Ok(origin), Ok(target)) => Transfer::try_new(origin, target),
+        (Err(error), _) | (_, Err(error)) => Err(error.into()),
+    };
+    let mut transfer = match transfer {
+        Ok(transfer) => transfer,
+        Err(error) => {
+            eprintln!("Invalid orbits: {}", error);
+            process::exit(2);
+        }
+    };
+    transfer.set_delta_v(transfer.delta_v_hohmann());
+
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(TransferScene::new(&transfer))
+        .add_plugins(Transfer3dPlugin)
+        .run();
+}
@@ -0,0 +1,51 @@
+use std::{env, process};
+
+use serenity::all::{Client, Context, CreateAttachment, CreateMessage, EventHandler, GatewayIntents, Message};
+use serenity::async_trait;
+
+use planetary_transfer::catalog::{self, Body};
+use transfer_bot::answer;
+
+struct Handler {
+    bodies: Vec<Body>,
+}
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn message(&self, context: Context, message: Message) {
+        if message.author.bot {
+            return;
+        }
+        let Some(reply) = answer(&message.content, &self.bodies) else {return};
+
+        let mut response = CreateMessage::new().content(format!("```\n{}\n```", reply.text));
+        if let Some(diagram) = reply.diagram {
+            response = response.add_file(CreateAttachment::bytes(diagram.into_bytes(), "transfer.svg"));
+        }
+        if let Err(error) = message.channel_id.send_message(&context.http, response).await {
+            eprintln!("Error: {}", error);
+        }
+    }
+}
+
+// Usage: DISCORD_TOKEN=<token> transfer_discord
+// The bot needs the message content intent enabled in the developer portal to read commands.
+#[tokio::main]
+async fn main() {
+    let Ok(token) = env::var("DISCORD_TOKEN") else {
+        eprintln!("Usage: DISCORD_TOKEN=<token> transfer_discord");
+        process::exit(2);
+    };
+
+    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    let client = Client::builder(&token, intents)
+        .event_handler(Handler { bodies: catalog::bodies() })
+        .await;
+    if let Err(error) = match client {
+        Ok(mut client) => client.start().await,
+        Err(error) => Err(error),
+    } {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::{env, process};
+
+use planetary_transfer::catalog;
+use transfer_bot::answer;
+
+// Usage: transfer_irc <server:port> <nick> <channel>
+// Answers commands in the channel and in private messages. Diagrams can't be attached on IRC and are left out.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        eprintln!("Usage: {} <server:port> <nick> <channel>", args[0]);
+        process::exit(2);
+    }
+
+    if let Err(error) = run(&args[1], &args[2], &args[3]) {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(address: &str, nick: &str, channel: &str) -> io::Result<()> {
+    let bodies = catalog::bodies();
+    let stream = TcpStream::connect(address)?;
+    let mut writer = stream.try_clone()?;
+    write!(writer, "NICK {0}\r\nUSER {0} 0 * :Transfer calculator\r\n", nick)?;
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if let Some(token) = line.strip_prefix("PING ") {
+            write!(writer, "PONG {}\r\n", token)?;
+            continue;
+        }
+
+        // :<sender>!<user>@<host> <command> <recipient> :<text>
+        let mut parts = line.splitn(4, ' ');
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            // Welcome message, the server accepting commands from then on.
+            (_, Some("001"), _, _) => write!(writer, "JOIN {}\r\n", channel)?,
+            (Some(sender), Some("PRIVMSG"), Some(recipient), Some(text)) => {
+                let Some(reply) = answer(text.trim_start_matches(':'), &bodies) else {continue};
+                // Private messages are answered to their sender rather than to the bot itself.
+                let to = if recipient.starts_with('#') {recipient} else {sender.trim_start_matches(':').split('!').next().unwrap_or(recipient)};
+                for line in reply.text.lines() {
+                    write!(writer, "PRIVMSG {} :{}\r\n", to, line)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// Chat commands answering with the transfer between two bodies of the catalog, shared by the IRC and Discord bots:
+// !transfer <origin> <target> [diagram]
+
+use planetary_transfer::catalog::Body;
+use planetary_transfer::render::SvgRenderer;
+use planetary_transfer::{Planet, Transfer, TransferSummary};
+
+pub const COMMAND: &str = "!transfer";
+pub const USAGE: &str = "Usage: !transfer <origin> <target> [diagram], both bodies orbiting the same one.";
+const DIAGRAM_SIZE: u32 = 512;
+
+pub struct Reply {
+    pub text: String,
+    // SVG diagram of the transfer when asked for, left out by chats that can't attach files.
+    pub diagram: Option<String>,
+}
+
+impl Reply {
+    fn text(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            diagram: None,
+        }
+    }
+}
+
+// None when the message isn't a command for the bot, the reply explaining what is wrong when the command is.
+pub fn answer(message: &str, bodies: &[Body]) -> Option<Reply> {
+    let mut words = message.split_whitespace();
+    if words.next()? != COMMAND {
+        return None;
+    }
+
+    let (origin, target, diagram) = match words.collect::<Vec<_>>()[..] {
+        [origin, target] => (origin, target, false),
+        [origin, target, "diagram"] => (origin, target, true),
+        _ => return Some(Reply::text(USAGE)),
+    };
+    Some(match transfer(origin, target, bodies) {
+        Ok((transfer, text)) => Reply {
+            text,
+            diagram: diagram.then(|| SvgRenderer::new(DIAGRAM_SIZE).render(&transfer)),
+        },
+        Err(error) => Reply::text(&error),
+    })
+}
+
+// Hohmann transfer between two bodies named case-insensitively, with its summary.
+fn transfer(origin: &str, target: &str, bodies: &[Body]) -> Result<(Transfer, String), String> {
+    let find = |name: &str| bodies.iter()
+        .find(|body| body.name.eq_ignore_ascii_case(name))
+        .ok_or(format!("Unknown body {}.", name));
+    let (origin, target) = (find(origin)?, find(target)?);
+
+    let primary = origin.primary(bodies).ok_or(format!("{} doesn't orbit any body.", origin.name))?;
+    if target.primary(bodies) != Some(primary) {
+        return Err(format!("{} and {} don't orbit the same body.", origin.name, target.name));
+    }
+    if origin.name == target.name {
+        return Err("The origin and target are the same body.".to_string());
+    }
+
+    let (parent, sma) = (&bodies[primary], |body: &Body| body.orbit.map(|(_, sma)| sma).unwrap());
+    let planet = |body: &Body| Planet::new(sma(body), parent.parent()).with_retrograde(body.retrograde);
+    let mut transfer = Transfer::try_new(planet(origin), planet(target)).map_err(|error| error.to_string())?;
+    transfer.set_delta_v(transfer.delta_v_hohmann());
+
+    let summary = TransferSummary::new(&transfer).with_parent(parent.name);
+    Ok((transfer, format!("{} to {}\n{}", origin.name, target.name, summary.to_text())))
+}
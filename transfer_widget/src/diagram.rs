@@ -0,0 +1,118 @@
+use egui::{Color32, Response, Slider, Ui, Widget};
+use egui::plot::{Plot, Legend, Points, Values, Value, MarkerShape};
+
+use planetary_transfer::{Transfer, Velocity, DEFAULT_HEADROOM};
+
+use crate::plotting::{BodySizing, Protractor, TransferGeometry, TransferPlot, points_per_unit};
+
+// Diagram of a transfer along with a slider for its departure delta-v, editing the transfer it is given.
+// The geometry is recomputed every frame, apps showing large transfers may cache a `TransferGeometry` and build
+// the diagram from `TransferPlot` instead.
+pub struct TransferWidget<'a> {
+    transfer: &'a mut Transfer,
+    id_source: &'a str,
+    color: Color32,
+    controls: bool,
+    sizing: BodySizing,
+    headroom: f64,
+}
+
+impl<'a> TransferWidget<'a> {
+    // The id source distinguishes the plots of several widgets shown at once.
+    pub fn new(transfer: &'a mut Transfer, id_source: &'a str) -> Self {
+        Self {
+            transfer,
+            id_source,
+            color: Color32::WHITE,
+            controls: true,
+            sizing: BodySizing::default(),
+            headroom: DEFAULT_HEADROOM,
+        }
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Whether to show the delta-v slider and results above the diagram, or the diagram alone.
+    pub fn controls(mut self, controls: bool) -> Self {
+        self.controls = controls;
+        self
+    }
+
+    pub fn sizing(mut self, sizing: BodySizing) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    // Extra delta-v the slider offers beyond the Hohmann transfer, as in `Transfer::suggested_delta_v_range`.
+    pub fn headroom(mut self, headroom: f64) -> Self {
+        self.headroom = headroom;
+        self
+    }
+}
+
+impl<'a> Widget for TransferWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let transfer = self.transfer;
+
+        ui.vertical(|ui| {
+            let slider = self.controls.then(|| {
+                // From the Hohmann transfer to the headroom of the suggested range.
+                let suggested = transfer.suggested_delta_v_range(self.headroom);
+                let (min, max) = if transfer.origin().sma() < transfer.target().sma() {(suggested.start().mps(), suggested.end().mps())} else {(suggested.end().mps(), suggested.start().mps())};
+                let mut delta_v = (transfer.launch_velocity() - transfer.origin().orbital_velocity()).mps();
+                let slider = ui.add(Slider::new(&mut delta_v, min..=max).logarithmic(true).suffix(" m/s").text("Departure ΔV"));
+                // Delta-v too close to the ends of the range for the transfer to be solved fall back to the Hohmann transfer.
+                if slider.changed() && transfer.try_set_delta_v(Velocity::from_meters_per_second(delta_v)).is_err() {
+                    transfer.set_delta_v(transfer.delta_v_hohmann());
+                }
+                ui.label(format!("Time of flight: {}", transfer.time_of_flight().formatted()));
+                slider
+            });
+
+            let geometry = TransferGeometry::new(transfer);
+            let mut transfer_plot = TransferPlot::new(transfer, &geometry, self.color);
+            let protractor = Protractor::new(transfer.target_true_anomaly_departure(), 1.0)
+                .color(Color32::GRAY);
+            let parent_radius = transfer.origin().orbit().parent.radius();
+
+            let plot = Plot::new(self.id_source)
+                .legend(Legend::default())
+                .allow_zoom(false)
+                .allow_drag(false)
+                .show_background(false)
+                .show_axes([false; 2])
+                .show_x(false).show_y(false)
+                .data_aspect(1.0)
+                .show(ui, |plot_ui| {
+                    let points_per_unit = points_per_unit(plot_ui);
+                    transfer_plot.set_sizing(self.sizing, points_per_unit);
+                    for orbit in transfer_plot.orbit_all() {
+                        plot_ui.line(orbit);
+                    }
+                    for line in protractor.plot() {
+                        plot_ui.line(line);
+                    }
+                    for marker in transfer_plot.marker_all() {
+                        plot_ui.points(marker);
+                    }
+                    let (points, labels) = transfer_plot.apsides();
+                    plot_ui.points(points);
+                    for label in labels {
+                        plot_ui.text(label);
+                    }
+                    plot_ui.text(protractor.text());
+                    plot_ui.points(Points::new(Values::from_values(vec![Value::new(0.0, 0.0)]))
+                        .radius(self.sizing.marker_radius(parent_radius, transfer_plot.scale().m(), points_per_unit))
+                        .shape(MarkerShape::Diamond));
+                });
+
+            match slider {
+                Some(slider) => slider.union(plot.response),
+                None => plot.response,
+            }
+        }).inner
+    }
+}
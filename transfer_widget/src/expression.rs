@@ -0,0 +1,146 @@
+// Arithmetic expressions entered in the text inputs, such as "1.52*au", "2.2e6 km" or "6371+400 km".
+// Units are given with their value in the unit of the input. A number followed by a unit is in that unit,
+// a unit on its own is its value, and a number added to a value with a unit takes that unit.
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Unit(f64),
+    Operator(char),
+}
+
+// Value in the unit of the input, along with the unit it was given in if any.
+type Value = (f64, Option<f64>);
+
+pub fn evaluate(text: &str, units: &[(&str, f64)]) -> Result<f64, String> {
+    let tokens = tokenize(&text.replace(',', ""), units)?;
+    let mut parser = Parser { tokens, position: 0 };
+
+    let (value, _) = parser.expression()?;
+    match parser.tokens.get(parser.position) {
+        None if value.is_finite() => Ok(value),
+        None => Err("result is not a number".to_string()),
+        Some(_) => Err("unexpected input after the expression".to_string()),
+    }
+}
+
+fn tokenize(text: &str, units: &[(&str, f64)]) -> Result<Vec<Token>, String> {
+    // Longest names first, so that "km/s" is not read as "km" divided by "s".
+    let mut units = units.to_vec();
+    units.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() || c == '.' {
+            let mut end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+            // Exponent, only when followed by digits so that "2e" alone stays a unit.
+            let exponent = &rest[end..];
+            if let Some(after) = exponent.strip_prefix(|c| c == 'e' || c == 'E') {
+                let after = after.strip_prefix(|c| c == '+' || c == '-').unwrap_or(after);
+                let digits = after.find(|c: char| !c.is_ascii_digit()).unwrap_or(after.len());
+                if digits > 0 {
+                    end = rest.len() - after.len() + digits;
+                }
+            }
+            let number = &rest[..end];
+            tokens.push(Token::Number(number.parse().map_err(|_| format!("invalid number {}", number))?));
+            rest = &rest[end..];
+        } else if "+-*/()".contains(c) {
+            tokens.push(Token::Operator(c));
+            rest = &rest[c.len_utf8()..];
+        } else {
+            match units.iter().find(|(name, _)| !name.is_empty() && rest.starts_with(name)) {
+                Some((name, value)) => {
+                    tokens.push(Token::Unit(*value));
+                    rest = &rest[name.len()..];
+                }
+                None => {
+                    let word = rest.split(|c: char| c.is_whitespace() || "+-*/()".contains(c)).next().unwrap_or(rest);
+                    return Err(format!("unknown unit {}", word));
+                }
+            }
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_operator(&self, operators: &str) -> Option<char> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operator(c)) if operators.contains(*c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<Value, String> {
+        let mut left = self.term()?;
+        while let Some(operator) = self.peek_operator("+-") {
+            self.position += 1;
+            let right = self.term()?;
+            let sign = if operator == '-' {-1.0} else {1.0};
+            left = match (left, right) {
+                ((a, None), (b, Some(unit))) => (a * unit + sign * b, Some(unit)),
+                ((a, Some(unit)), (b, None)) => (a + sign * b * unit, Some(unit)),
+                ((a, unit), (b, _)) => (a + sign * b, unit),
+            };
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Value, String> {
+        let mut left = self.factor()?;
+        while let Some(operator) = self.peek_operator("*/") {
+            self.position += 1;
+            let right = self.factor()?;
+            left = match (operator, left, right) {
+                ('*', (a, unit_a), (b, unit_b)) => (a * b, unit_a.or(unit_b)),
+                (_, (a, Some(_)), (b, Some(_))) => (a / b, None),
+                (_, (a, unit), (b, _)) => (a / b, unit),
+            };
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Value, String> {
+        let value = match self.next() {
+            Some(Token::Operator('-')) => {
+                let (value, unit) = self.factor()?;
+                return Ok((-value, unit));
+            }
+            Some(Token::Operator('+')) => return self.factor(),
+            Some(Token::Operator('(')) => {
+                let value = self.expression()?;
+                match self.next() {
+                    Some(Token::Operator(')')) => value,
+                    _ => return Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(Token::Number(number)) => (number, None),
+            Some(Token::Unit(unit)) => return Ok((unit, Some(unit))),
+            Some(Token::Operator(c)) => return Err(format!("unexpected {}", c)),
+            None => return Err("incomplete expression".to_string()),
+        };
+
+        // A unit right after a number or parenthesis applies to it.
+        match (value, self.tokens.get(self.position)) {
+            ((number, None), Some(&Token::Unit(unit))) => {
+                self.position += 1;
+                Ok((number * unit, Some(unit)))
+            }
+            _ => Ok(value),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// Reusable egui interface of the transfer calculator, for other egui apps to embed.
+// `TransferWidget` shows a transfer with the controls of its departure burn, while the plots and widgets
+// the standalone app builds its interface from are available on their own.
+
+mod diagram;
+pub mod widgets;
+pub mod plotting;
+pub mod expression;
+
+pub use diagram::TransferWidget;
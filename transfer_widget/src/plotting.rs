@@ -0,0 +1,650 @@
+use std::f64::consts::TAU;
+
+use egui::{plot::{Line, Value, Values, Points, LineStyle, Text, HLine, MarkerShape, PlotUi}, Color32};
+
+use planetary_transfer::{Transfer, Planet, ResonantOrbit, MoonApproach, Mission, Distance, Duration, Velocity, Vec3, significant, SIGNIFICANT_FIGURES, geometry};
+use planetary_transfer::geometry::{Conic, Frame, RelativeMotion};
+use planetary_transfer::catalog::{Body, Marker};
+
+// Conversion of the library's renderer-agnostic polylines to plot items.
+pub fn values(points: Vec<(f64, f64)>) -> Values {
+    Values::from_values(points.into_iter().map(|(x, y)| Value::new(x, y)).collect())
+}
+
+// Coordinates of the cached polylines, narrowed to f32 with the `f32-geometry` feature to halve the memory
+// read every frame on low-end wasm targets. The physics behind them stays in f64.
+#[cfg(feature = "f32-geometry")]
+type Coordinate = f32;
+#[cfg(not(feature = "f32-geometry"))]
+type Coordinate = f64;
+
+fn narrow(points: Vec<(f64, f64)>) -> Vec<(Coordinate, Coordinate)> {
+    points.into_iter().map(|(x, y)| (x as Coordinate, y as Coordinate)).collect()
+}
+
+fn polyline_values(points: &[(Coordinate, Coordinate)]) -> Values {
+    Values::from_values(points.iter().map(|&(x, y)| Value::new(x, y)).collect())
+}
+
+fn marker(point: (f64, f64)) -> Points {
+    Points::new(values(vec![point]))
+        .radius(10.0)
+}
+
+// Sizes of the markers of the bodies, drawn at their actual radii scaled like their orbits, which would mostly leave
+// them invisible without the exaggeration and the minimum size on screen.
+#[derive(Copy, Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct BodySizing {
+    pub exaggeration: f64,
+    // Radius in points of the markers of bodies too small to show, or whose radius is unknown.
+    pub min_radius: f32,
+}
+
+impl Default for BodySizing {
+    fn default() -> Self {
+        Self {
+            exaggeration: 1.0,
+            min_radius: 4.0,
+        }
+    }
+}
+
+impl BodySizing {
+    // Radius in points of the marker of a body, given the scale of the plot in meters and its size on screen.
+    pub fn marker_radius(&self, radius: Option<Distance>, scale: f64, points_per_unit: f64) -> f32 {
+        radius.map_or(self.min_radius, |radius| ((radius.m() * self.exaggeration / scale * points_per_unit) as f32).max(self.min_radius))
+    }
+}
+
+// Points on screen per unit of the plot, as of the previous frame.
+pub fn points_per_unit(plot_ui: &PlotUi) -> f64 {
+    (plot_ui.screen_from_plot(Value::new(1.0, 0.0)).x - plot_ui.screen_from_plot(Value::new(0.0, 0.0)).x) as f64
+}
+
+pub fn body_color(body: &Body) -> Color32 {
+    let [r, g, b] = body.color;
+    Color32::from_rgb(r, g, b)
+}
+
+pub fn marker_shape(marker: Marker) -> MarkerShape {
+    match marker {
+        Marker::Circle => MarkerShape::Circle,
+        Marker::Diamond => MarkerShape::Diamond,
+        Marker::Square => MarkerShape::Square,
+    }
+}
+
+// Name, color and marker of a planet, those of a body of the catalog when it was picked from it.
+struct Style {
+    name: String,
+    color: Color32,
+    // Markers of planets not taken from the catalog keep the colors of the plot.
+    marker_color: Option<Color32>,
+    shape: MarkerShape,
+    radius: Option<Distance>,
+}
+
+impl Style {
+    fn new(name: &str, color: Color32) -> Self {
+        Self {
+            name: name.to_string(),
+            color,
+            marker_color: None,
+            shape: MarkerShape::Circle,
+            radius: None,
+        }
+    }
+
+    fn body(body: &Body) -> Self {
+        Self {
+            name: body.name.to_string(),
+            color: body_color(body),
+            marker_color: Some(body_color(body)),
+            shape: marker_shape(body.marker),
+            radius: Some(body.radius),
+        }
+    }
+
+    // Body of a custom system, colored like the bodies of the catalog but without a known radius.
+    fn named(name: &str, color: Color32) -> Self {
+        Self {
+            marker_color: Some(color),
+            ..Self::new(name, color)
+        }
+    }
+
+    fn marker(&self, point: (f64, f64)) -> Points {
+        let marker = marker(point).shape(self.shape).name(&self.name);
+        match self.marker_color {
+            Some(color) => marker.color(color),
+            None => marker,
+        }
+    }
+}
+
+// Polylines of a transfer diagram, only recomputed when the transfer changes rather than every frame.
+pub struct TransferGeometry {
+    scale: f64,
+    origin: Vec<(Coordinate, Coordinate)>,
+    target: Vec<(Coordinate, Coordinate)>,
+    transfer: Vec<(Coordinate, Coordinate)>,
+    time_ticks: Vec<((f64, f64), Duration)>,
+    apsides: Vec<((f64, f64), Distance)>,
+}
+
+impl TransferGeometry {
+    pub fn new(transfer: &Transfer) -> Self {
+        let diagram = geometry::Diagram::new(transfer);
+        let scale = diagram.scale;
+        Self {
+            scale,
+            origin: narrow(diagram.origin),
+            target: narrow(diagram.target),
+            transfer: narrow(diagram.transfer),
+            time_ticks: geometry::time_ticks(transfer, time_interval(transfer), scale),
+            apsides: geometry::apsides(transfer, scale),
+        }
+    }
+}
+
+// Interval between time markers, the smallest of a day, week, month or year giving at most a dozen markers.
+fn time_interval(transfer: &Transfer) -> Duration {
+    let time_of_flight = transfer.time_of_flight();
+    [Duration::from_days(1.0), Duration::from_days(7.0), Duration::from_months(1.0)]
+        .into_iter()
+        .find(|interval| time_of_flight.s() / interval.s() <= 12.0)
+        .unwrap_or(Duration::from_years(1.0))
+}
+
+pub struct TransferPlot<'a> {
+    transfer: &'a Transfer,
+    geometry: &'a TransferGeometry,
+    scale: f64,
+    origin: Style,
+    target: Style,
+    width_origin: f32,
+    width_target: f32,
+    width_transfer: f32,
+    sizing: BodySizing,
+    points_per_unit: f64,
+    reference: Option<(&'a Transfer, &'a TransferGeometry)>,
+}
+
+impl<'a> TransferPlot<'a> {
+    pub fn new(transfer: &'a Transfer, geometry: &'a TransferGeometry, color: Color32) -> Self {
+        Self {
+            transfer,
+            geometry,
+            scale: geometry.scale,
+            origin: Style::new("Origin", color),
+            target: Style::new("Target", color),
+            width_origin: 1.0,
+            width_target: 1.0,
+            width_transfer: 1.0,
+            sizing: BodySizing::default(),
+            points_per_unit: 0.0,
+            reference: None,
+        }
+    }
+
+    // Sizes the markers of the planets from their radii, markers otherwise keeping the minimum size.
+    pub fn set_sizing(&mut self, sizing: BodySizing, points_per_unit: f64) {
+        self.sizing = sizing;
+        self.points_per_unit = points_per_unit;
+    }
+
+    fn marker(&self, style: &Style, point: (f64, f64)) -> Points {
+        style.marker(point).radius(self.sizing.marker_radius(style.radius, self.scale, self.points_per_unit))
+    }
+
+    pub fn scale(&self) -> Distance {
+        Distance::from_meters(self.scale)
+    }
+
+    pub fn to_plot(&self, distance: Distance) -> f64 {
+        distance.m() / self.scale
+    }
+
+    pub fn orbit_all(&self) -> Vec<Line> {
+        let mut orbits = Vec::new();
+        orbits.push(self.orbit_origin());
+        orbits.push(self.orbit_target());
+        orbits.push(self.orbit_transfer());
+        orbits
+    }
+
+    pub fn marker_all(&self) ->  Vec<Points>  {
+        let mut markers = Vec::new();
+        markers.append(&mut self.marker_origin());
+        markers.append(&mut self.marker_target());
+        markers
+    }
+
+    pub fn orbit_origin(&self) -> Line {
+        Line::new(polyline_values(&self.geometry.origin))
+            .color(self.origin.color)
+            .width(self.width_origin)
+            .name(&self.origin.name)
+    }
+
+    pub fn orbit_target(&self) -> Line {
+        Line::new(polyline_values(&self.geometry.target))
+            .color(self.target.color)
+            .width(self.width_target)
+            .name(&self.target.name)
+    }
+
+    pub fn orbit_transfer(&self) -> Line {
+        Line::new(polyline_values(&self.geometry.transfer))
+            .color(Color32::from_rgb(255, 115, 0))
+            .width(self.width_transfer)
+            .name("Transfer")
+    }
+
+    // Transfer between the same orbits to compare this one with, as the Hohmann transfer, its geometry sharing the
+    // scale of this one.
+    pub fn set_reference(&mut self, transfer: &'a Transfer, geometry: &'a TransferGeometry) {
+        self.reference = Some((transfer, geometry));
+    }
+
+    // Faint arc of the reference transfer, labelled with its time of flight where it reaches the target.
+    pub fn reference(&self) -> Option<(Line, Text)> {
+        let (transfer, geometry) = self.reference?;
+        let color = Color32::from_rgba_unmultiplied(255, 115, 0, 90);
+        let line = Line::new(polyline_values(&geometry.transfer))
+            .color(color)
+            .style(LineStyle::dashed_dense())
+            .name("Hohmann transfer");
+        let (x, y) = transfer.target().position(transfer.target_true_anomaly_arrival(), self.scale);
+        let label = Text::new(Value::new(x, y), format!("  {}", transfer.time_of_flight().formatted()))
+            .anchor(egui::Align2::LEFT_TOP)
+            .color(color);
+        Some((line, label))
+    }
+
+    // Numerically integrated trajectory, overlaid on the analytic one to check it.
+    pub fn numerical_path(&self, path: &[Vec3]) -> Line {
+        Line::new(values(path.iter().map(|position| (position.x / self.scale, position.y / self.scale)).collect()))
+            .color(Color32::GRAY)
+            .style(LineStyle::dashed_loose())
+    }
+
+    pub fn marker_origin(&self) -> Vec<Points> {
+        vec![
+        self.marker(&self.origin, self.transfer.origin().position(self.transfer.origin_true_anomaly_departure(), self.scale)), 
+        self.marker(&self.origin, self.transfer.origin().position(self.transfer.origin_true_anomaly_arrival(), self.scale))
+        ]
+    }
+
+    pub fn marker_target(&self) -> Vec<Points> {
+        vec![
+        self.marker(&self.target, self.transfer.target().position(self.transfer.target_true_anomaly_departure(), self.scale)), 
+        self.marker(&self.target, self.transfer.target().position(self.transfer.target_true_anomaly_arrival(), self.scale))
+        ]
+    }
+
+    pub fn time_interval(&self) -> Duration {
+        time_interval(self.transfer)
+    }
+
+    pub fn time_markers(&self) -> (Points, Vec<Text>) {
+        let ticks = self.geometry.time_ticks.clone();
+
+        let labels = ticks.iter()
+            .map(|&((x, y), time)| Text::new(Value::new(x, y), format!("  {}", time.formatted()))
+                .anchor(egui::Align2::LEFT_CENTER)
+                .color(Color32::GRAY)
+            )
+            .collect();
+
+        let points = Points::new(values(ticks.into_iter().map(|(point, _)| point).collect()))
+            .radius(2.5)
+            .color(Color32::from_rgb(255, 115, 0));
+
+        (points, labels)
+    }
+
+    // Apsides reached during the transfer, labelled with their distance to the parent.
+    pub fn apsides(&self) -> (Points, Vec<Text>) {
+        let apsides = self.geometry.apsides.clone();
+
+        let labels = apsides.iter()
+            .map(|&((x, y), distance)| Text::new(Value::new(x, y), format!("  {}", distance.formatted()))
+                .anchor(egui::Align2::LEFT_BOTTOM)
+                .color(Color32::from_rgb(255, 115, 0))
+            )
+            .collect();
+
+        let points = Points::new(values(apsides.into_iter().map(|(point, _)| point).collect()))
+            .radius(4.0)
+            .shape(MarkerShape::Diamond)
+            .color(Color32::from_rgb(255, 115, 0));
+
+        (points, labels)
+    }
+
+    pub fn set_color_origin(&mut self, color: Color32) {
+        self.origin.color = color;
+    }
+
+    pub fn set_color_target(&mut self, color: Color32) {
+        self.target.color = color;
+    }
+
+    pub fn set_body_origin(&mut self, body: &Body) {
+        self.origin = Style::body(body);
+    }
+
+    pub fn set_body_target(&mut self, body: &Body) {
+        self.target = Style::body(body);
+    }
+
+    pub fn set_named_origin(&mut self, name: &str, color: Color32) {
+        self.origin = Style::named(name, color);
+    }
+
+    pub fn set_named_target(&mut self, name: &str, color: Color32) {
+        self.target = Style::named(name, color);
+    }
+
+    // Circular orbit of another body around the parent, fainter than the ones of the transfer.
+    pub fn orbit_other(&self, name: &str, sma: Distance, color: Color32) -> Line {
+        Line::new(values(geometry::orbit(sma.m() / self.scale, 0.0, 0.0..=TAU, geometry::ORBIT_SAMPLES)))
+            .color(color)
+            .style(LineStyle::dashed_loose())
+            .name(name)
+    }
+
+    pub fn highlight_origin(&mut self) {
+        self.width_origin = 2.0;
+    }
+
+    pub fn highlight_target(&mut self) {
+        self.width_target = 2.0;
+    }
+
+    pub fn highlight_transfer(&mut self) {
+        self.width_transfer = 2.0;
+    }
+}
+
+// Reference orbit with the resonant orbit, and the positions of consecutive passes along the reference orbit.
+pub struct ResonancePlot<'a> {
+    planet: &'a Planet,
+    resonant_orbit: &'a ResonantOrbit,
+    scale: f64,
+    color: Color32,
+}
+
+impl<'a> ResonancePlot<'a> {
+    pub fn new(planet: &'a Planet, resonant_orbit: &'a ResonantOrbit, color: Color32) -> Self {
+        Self {
+            planet,
+            resonant_orbit,
+            scale: resonant_orbit.apoapsis.m(),
+            color,
+        }
+    }
+
+    pub fn orbit_all(&self) -> Vec<Line> {
+        vec![
+            Line::new(values(self.planet.polyline(self.scale)))
+                .color(self.color),
+            Line::new(values(self.resonant_orbit.polyline(self.scale)))
+                .color(Color32::from_rgb(255, 115, 0)),
+        ]
+    }
+
+    pub fn passes(&self) -> Points {
+        let passes = (0..self.resonant_orbit.resonance)
+            .map(|i| self.planet.position(i as f64 * self.resonant_orbit.separation, self.scale))
+            .collect();
+
+        Points::new(values(passes))
+            .radius(6.0)
+            .color(self.color)
+    }
+}
+
+// Zoomed view of the system of the target around the orbit of its moon, inset in the transfer diagram.
+// The approach is cut where it is further than a few times the orbit of the moon from the target.
+pub struct MoonPlot<'a> {
+    moon_approach: &'a MoonApproach,
+    scale: f64,
+    color: Color32,
+}
+
+impl<'a> MoonPlot<'a> {
+    pub fn new(moon_approach: &'a MoonApproach, color: Color32) -> Self {
+        Self {
+            moon_approach,
+            scale: moon_approach.moon.sma().m(),
+            color,
+        }
+    }
+
+    pub fn orbit_all(&self) -> Vec<Line> {
+        let approach = self.moon_approach.polyline(self.scale)
+            .into_iter()
+            .filter(|(x, y)| x.hypot(*y) <= 3.0)
+            .collect();
+
+        vec![
+            Line::new(values(self.moon_approach.moon.polyline(self.scale)))
+                .color(self.color),
+            Line::new(values(approach))
+                .color(Color32::from_rgb(255, 115, 0)),
+        ]
+    }
+
+    // Target at the center and the moon where the spacecraft meets it.
+    pub fn markers(&self) -> Vec<Points> {
+        vec![
+            marker((0.0, 0.0)).color(self.color),
+            marker((1.0, 0.0)).radius(5.0).color(self.color),
+        ]
+    }
+}
+
+// Speed of the spacecraft in km/s against days since departure.
+pub struct VelocityPlot<'a> {
+    transfer: &'a Transfer,
+    color: Color32,
+}
+
+impl<'a> VelocityPlot<'a> {
+    pub fn new(transfer: &'a Transfer, color: Color32) -> Self {
+        Self {
+            transfer,
+            color,
+        }
+    }
+
+    pub fn profile(&self) -> Line {
+        let profile = geometry::velocity_profile(self.transfer, geometry::ORBIT_SAMPLES)
+            .into_iter()
+            .map(|(time, velocity)| (time.d(), velocity.kps()))
+            .collect();
+
+        Line::new(values(profile))
+            .color(Color32::from_rgb(255, 115, 0))
+            .name("Speed")
+    }
+
+    pub fn apsides(&self) -> Vec<HLine> {
+        let mut apsides = vec![
+            HLine::new(self.transfer.periapsis_velocity().kps())
+                .color(self.color)
+                .style(LineStyle::dashed_loose())
+                .name("Periapsis")
+        ];
+        if let Some(apoapsis) = self.transfer.apoapsis_velocity() {
+            apsides.push(HLine::new(apoapsis.kps())
+                .color(Color32::GRAY)
+                .style(LineStyle::dashed_loose())
+                .name("Apoapsis")
+            );
+        }
+        apsides
+    }
+}
+
+// Paths of the spacecraft and of the origin in a frame rotating with the target, where the transfer ends on the target.
+pub struct RelativePlot {
+    motion: RelativeMotion,
+    color: Color32,
+}
+
+impl RelativePlot {
+    pub fn new(transfer: &Transfer, frame: Frame, scale: f64, color: Color32) -> Self {
+        Self {
+            motion: RelativeMotion::new(transfer, frame, scale, geometry::ORBIT_SAMPLES),
+            color,
+        }
+    }
+
+    pub fn paths(&self) -> Vec<Line> {
+        vec![
+            Line::new(values(self.motion.origin.clone()))
+                .color(self.color)
+                .name("Origin"),
+            Line::new(values(self.motion.spacecraft.clone()))
+                .color(Color32::from_rgb(255, 115, 0))
+                .name("Transfer"),
+        ]
+    }
+
+    pub fn markers(&self) -> Vec<Points> {
+        vec![
+            marker(self.motion.target).color(self.color).name("Target"),
+            marker(self.motion.parent).shape(MarkerShape::Diamond).color(self.color).name("Parent"),
+        ]
+    }
+}
+
+// Time of flight in days against C3 in km²/s² of the transfers between the two orbits, faster than the Hohmann
+// transfer as the C3 grows, with the missions that flew between the same bodies.
+pub struct MissionPlot<'a> {
+    transfer: &'a Transfer,
+    missions: &'a [Mission],
+    color: Color32,
+}
+
+impl<'a> MissionPlot<'a> {
+    pub fn new(transfer: &'a Transfer, missions: &'a [Mission], color: Color32) -> Self {
+        Self {
+            transfer,
+            missions,
+            color,
+        }
+    }
+
+    // Transfers up to half again the largest C3 shown, sampled evenly in departure excess speed.
+    pub fn transfers(&self) -> Line {
+        let max_c3 = self.missions.iter().map(|mission| mission.c3).fold(self.transfer.c3(), f64::max) * 1.5;
+        let mut transfer = *self.transfer;
+        let hohmann = transfer.delta_v_hohmann().mps();
+        let (start, end) = (hohmann.abs(), max_c3.sqrt() * 1E3);
+        let (min, max) = (transfer.min_velocity().mps(), transfer.max_velocity().mps());
+
+        let points = (0..=geometry::ORBIT_SAMPLES)
+            .map(|i| hohmann.signum() * (start + (end - start) * i as f64 / geometry::ORBIT_SAMPLES as f64))
+            .filter(|delta_v| (min.min(max)..=min.max(max)).contains(delta_v))
+            .filter_map(|delta_v| {
+                transfer.try_set_delta_v(Velocity::from_meters_per_second(delta_v)).ok()?;
+                Some((transfer.c3(), transfer.time_of_flight().d()))
+            })
+            .collect();
+
+        Line::new(values(points))
+            .color(self.color)
+            .style(LineStyle::dashed_loose())
+            .name("Transfers")
+    }
+
+    pub fn transfer(&self) -> Points {
+        marker((self.transfer.c3(), self.transfer.time_of_flight().d()))
+            .color(Color32::from_rgb(255, 115, 0))
+            .name("This transfer")
+    }
+
+    pub fn missions(&self) -> (Points, Vec<Text>) {
+        let labels = self.missions.iter()
+            .map(|mission| Text::new(Value::new(mission.c3, mission.time_of_flight.d()), format!("  {}", mission.name))
+                .anchor(egui::Align2::LEFT_CENTER)
+                .color(Color32::GRAY)
+            )
+            .collect();
+
+        let points = Points::new(values(self.missions.iter().map(|mission| (mission.c3, mission.time_of_flight.d())).collect()))
+            .radius(4.0)
+            .shape(MarkerShape::Diamond)
+            .color(Color32::GRAY)
+            .name("Missions");
+
+        (points, labels)
+    }
+}
+
+pub struct Protractor {
+    geometry: geometry::Protractor,
+    color: Color32,
+    style: LineStyle,
+    width: f32,
+}
+
+impl Protractor {
+    pub fn new(angle: f64, length: f64) -> Self {
+        Self {
+            geometry: geometry::Protractor::new(angle, length),
+            color: Color32::WHITE,
+            style: LineStyle::dashed_loose(),
+            width: 3.0,
+        }
+    }
+
+    pub fn plot(&self) -> Vec<Line> {
+        vec![self.geometry.hypothenuse(), self.geometry.adjacent(), self.geometry.measure()]
+            .into_iter()
+            .map(|points| Line::new(values(points))
+                .color(self.color)
+                .style(self.style)
+                .width(self.width)
+            )
+            .collect()
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.geometry.angle()
+    }
+
+    pub fn text_string(&self) -> String {
+        format!("{} °", significant(self.geometry.angle().to_degrees(), SIGNIFICANT_FIGURES))
+    }
+
+    pub fn text(&self) -> Text {
+        let (x, y) = self.geometry.label_position();
+
+        Text::new(
+            Value::new(x, y),
+            self.text_string()
+        )
+        .style(egui::TextStyle::Heading)
+        .color(Color32::WHITE)
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
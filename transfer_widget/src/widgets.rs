@@ -0,0 +1,440 @@
+use egui::{Response, Widget, Ui, Slider, TextEdit, Sense, Vec2, Pos2, Color32, CollapsingHeader};
+use thousands::Separable;
+
+use crate::expression;
+
+use planetary_transfer::{round_to, Distance, DistanceUnit, Duration, Mass, MassUnit, Orbit, Parent, Planet, Quantity, Velocity, VelocityUnit, SECONDS_DAY};
+
+pub struct SliderWithText<'a> {
+    value: &'a mut f64,
+    text: &'a mut String,
+    range: std::ops::RangeInclusive<f64>,
+    suffix: &'a str,
+    units: &'a [(&'a str, f64)],
+    max_decimals: usize,
+    enabled_slider: bool,
+}
+
+impl<'a> SliderWithText<'a> {
+    pub fn new(value: &'a mut f64, text: &'a mut String, range: std::ops::RangeInclusive<f64>) -> Self {
+        Self {
+            value,
+            text,
+            range,
+            suffix: "",
+            units: &[],
+            max_decimals: 8,
+            enabled_slider: true,
+        }
+    }
+}
+
+impl<'a> Widget for SliderWithText<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let inner = ui.horizontal(|ui| self.add_widgets(ui));
+        
+        inner.inner
+    }
+}
+
+impl<'a> SliderWithText<'a> {
+    fn slider(&mut self) -> Slider {
+        Slider::new(self.value, self.range.clone())
+            .show_value(false)
+            .logarithmic(true)
+            .max_decimals(self.max_decimals)
+            .smallest_positive(0.5)
+    }
+    
+    fn value(&mut self) -> TextEdit {
+        TextEdit::singleline(self.text)
+            .text_style(egui::TextStyle::Monospace)
+    }
+
+    fn add_widgets(mut self, ui: &mut Ui) -> Response {
+        let slider_response = ui.add_enabled(self.enabled_slider, self.slider());
+        let value_response = ui.add(self.value());
+
+        if slider_response.dragged() {
+            ui.ctx().memory().stop_text_input();
+        }
+
+        if value_response.gained_focus() {
+            *self.text = self.value.separate_with_commas();
+
+        } else if !value_response.has_focus() {
+            if value_response.lost_focus() {
+                match expression::evaluate(self.text, self.units) {
+                    Ok(text_input) => {
+                        let start = *self.range.start();
+                        let end = *self.range.end();
+                        *self.value = text_input.clamp(start.min(end), start.max(end));
+                        ui.memory().data.insert_temp::<Option<String>>(value_response.id, None);
+                    }
+                    Err(error) => ui.memory().data.insert_temp(value_response.id, Some(error)),
+                }
+            }
+            // The text is drawn before being brought up to date, a value changed elsewhere needing another frame
+            let text = round_to(*self.value, 4).separate_with_commas() + self.suffix;
+            if *self.text != text {
+                *self.text = text;
+                ui.ctx().request_repaint();
+            }
+        }
+
+        let error = ui.memory().data.get_temp::<Option<String>>(value_response.id).flatten();
+        if let Some(error) = error {
+            ui.colored_label(Color32::RED, error);
+        }
+        
+        value_response | slider_response
+        
+    }
+}
+
+impl<'a> SliderWithText<'a> {
+    pub fn suffix(mut self, suffix: &'a str) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    // Units accepted in expressions typed in the text input, with their value in the unit of the slider.
+    pub fn units(mut self, units: &'a [(&'a str, f64)]) -> Self {
+        self.units = units;
+        self
+    }
+
+    pub fn max_decimals(mut self, max_decimals: usize) -> Self {
+        self.max_decimals = max_decimals;
+        self
+    }
+
+    pub fn enabled_slider(mut self, enabled_slider: bool) -> Self {
+        self.enabled_slider = enabled_slider;
+        self
+    }
+}
+// Horizontal timeline of launch windows, clicking a window selecting it.
+pub struct Timeline<'a> {
+    windows: &'a [Duration],
+    selected: &'a mut Option<usize>,
+    dates: &'a [String],
+    height: f32,
+}
+
+impl<'a> Timeline<'a> {
+    pub fn new(windows: &'a [Duration], selected: &'a mut Option<usize>) -> Self {
+        Self {
+            windows,
+            selected,
+            dates: &[],
+            height: 30.0,
+        }
+    }
+
+    // Dates of the windows, shown along with the time until them.
+    pub fn dates(mut self, dates: &'a [String]) -> Self {
+        self.dates = dates;
+        self
+    }
+}
+
+impl<'a> Widget for Timeline<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, mut response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), self.height), Sense::click());
+        let span = self.windows.last().map(|window| window.s() * 1.05).unwrap_or(1.0);
+        let to_x = |time: &Duration| rect.left() + 10.0 + (rect.width() - 20.0) * (time.s() / span) as f32;
+
+        let painter = ui.painter();
+        let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+        painter.line_segment([Pos2::new(rect.left(), rect.center().y), Pos2::new(rect.right(), rect.center().y)], stroke);
+        painter.circle_filled(Pos2::new(to_x(&Duration::from_seconds(0.0)), rect.center().y), 3.0, stroke.color);
+
+        let hovered = response.hover_pos().and_then(|pos| {
+            self.windows.iter().position(|window| (to_x(window) - pos.x).abs() < 8.0)
+        });
+
+        for (i, window) in self.windows.iter().enumerate() {
+            let color = if Some(i) == *self.selected || Some(i) == hovered {Color32::RED} else {Color32::from_rgb(255, 115, 0)};
+            painter.circle_filled(Pos2::new(to_x(window), rect.center().y), 6.0, color);
+        }
+
+        if let Some(i) = hovered {
+            let date = self.dates.get(i).map(|date| format!(", on {}", date)).unwrap_or_default();
+            response = response.on_hover_text(format!("Window {} in {}{}", i + 1, self.windows[i].formatted(), date));
+            if response.clicked() {
+                *self.selected = Some(i);
+                response.mark_changed();
+            }
+        }
+
+        response
+    }
+}
+
+// Unit in which a quantity is displayed, used while its condition holds.
+pub struct Unit<Q: Quantity> {
+    suffix: &'static str,
+    unit: Q::Unit,
+    condition: fn(&Q) -> bool,
+}
+
+impl<Q: Quantity> Unit<Q> {
+    pub fn new(suffix: &'static str, unit: Q::Unit) -> Self {
+        Self {
+            suffix,
+            unit,
+            condition: |_| true,
+        }
+    }
+
+    pub fn when(mut self, condition: fn(&Q) -> bool) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    pub fn suffix(&self) -> &'static str {
+        self.suffix
+    }
+}
+
+// First of the units whose condition holds for a quantity, the last one otherwise.
+pub fn unit_for<'a, Q: Quantity>(units: &'a [Unit<Q>], quantity: &Q) -> &'a Unit<Q> {
+    units.iter()
+        .find(|unit| (unit.condition)(quantity))
+        .or_else(|| units.last())
+        .expect("quantity without units")
+}
+
+pub fn distance_units() -> Vec<Unit<Distance>> {
+    vec![
+        Unit::new(" au", DistanceUnit::AstronomicalUnits).when(|d| d.km() > 7_500_000.0),
+        Unit::new(" km", DistanceUnit::Kilometers).when(|d| d.m() > 100_000.0),
+        Unit::new(" m", DistanceUnit::Meters),
+    ]
+}
+
+pub fn mass_units() -> Vec<Unit<Mass>> {
+    vec![
+        Unit::new(" M☉", MassUnit::Solar).when(|m| m.jovian() > 97.0),
+        Unit::new(" Mj", MassUnit::Jovian).when(|m| m.earth() > 35.0),
+        Unit::new(" Me", MassUnit::Earth).when(|m| m.lunar() > 8.0),
+        Unit::new(" Ml", MassUnit::Lunar),
+    ]
+}
+
+pub fn velocity_units() -> Vec<Unit<Velocity>> {
+    vec![
+        Unit::new(" km/s", VelocityUnit::KilometersPerSecond).when(|v| v.mps().abs() >= 1000.0),
+        Unit::new(" m/s", VelocityUnit::MetersPerSecond).when(|v| v.mmps().abs() >= 1000.0),
+        Unit::new(" mm/s", VelocityUnit::MillimetersPerSecond),
+    ]
+}
+
+// Slider of a quantity displayed in the first of its units whose condition holds.
+pub struct QuantitySlider<'a, Q: Quantity> {
+    quantity: &'a mut Q,
+    text: &'a mut String,
+    range: std::ops::RangeInclusive<Q>,
+    units: &'a [Unit<Q>],
+    max_decimals: usize,
+    enabled_slider: bool,
+}
+
+impl<'a, Q: Quantity> QuantitySlider<'a, Q> {
+    pub fn new(quantity: &'a mut Q, text: &'a mut String, range: std::ops::RangeInclusive<Q>, units: &'a [Unit<Q>]) -> Self {
+        Self {
+            quantity,
+            text,
+            range,
+            units,
+            max_decimals: 8,
+            enabled_slider: true,
+        }
+    }
+
+    pub fn max_decimals(mut self, max_decimals: usize) -> Self {
+        self.max_decimals = max_decimals;
+        self
+    }
+
+    pub fn enabled_slider(mut self, enabled_slider: bool) -> Self {
+        self.enabled_slider = enabled_slider;
+        self
+    }
+}
+
+impl<'a, Q: Quantity> Widget for QuantitySlider<'a, Q> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let unit = unit_for(self.units, self.quantity);
+        let range = self.range.start().in_unit(unit.unit)..=self.range.end().in_unit(unit.unit);
+
+        // Value of one of each unit in the displayed unit.
+        let factors: Vec<(&str, f64)> = self.units.iter()
+            .map(|other| (other.suffix.trim(), Q::from_unit(1.0, other.unit).in_unit(unit.unit)))
+            .collect();
+
+        // The slider edits the value in the displayed unit, the quantity only being set again once it changes so
+        // that its value in its other units isn't rounded every frame.
+        let mut value = self.quantity.in_unit(unit.unit);
+        let response = ui.add(SliderWithText::new(&mut value, self.text, range)
+            .suffix(unit.suffix)
+            .units(&factors)
+            .max_decimals(self.max_decimals)
+            .enabled_slider(self.enabled_slider)
+        );
+        if value != self.quantity.in_unit(unit.unit) {
+            self.quantity.set_in_unit(value, unit.unit);
+        }
+        response
+    }
+}
+
+// Compact input of a quantity dragged in the first of its units whose condition holds, as in tables.
+pub struct QuantityDragValue<'a, Q: Quantity> {
+    quantity: &'a mut Q,
+    range: std::ops::RangeInclusive<Q>,
+    units: &'a [Unit<Q>],
+}
+
+impl<'a, Q: Quantity> QuantityDragValue<'a, Q> {
+    pub fn new(quantity: &'a mut Q, range: std::ops::RangeInclusive<Q>, units: &'a [Unit<Q>]) -> Self {
+        Self {
+            quantity,
+            range,
+            units,
+        }
+    }
+}
+
+impl<'a, Q: Quantity> Widget for QuantityDragValue<'a, Q> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let unit = unit_for(self.units, self.quantity);
+        let range = self.range.start().in_unit(unit.unit)..=self.range.end().in_unit(unit.unit);
+
+        // Dragging changes the value by a share of itself, whatever its unit.
+        let mut value = self.quantity.in_unit(unit.unit);
+        let speed = value.abs() * 0.005;
+        let response = ui.add(egui::DragValue::new(&mut value)
+            .speed(speed)
+            .clamp_range(range)
+            .suffix(unit.suffix)
+        );
+        if value != self.quantity.in_unit(unit.unit) {
+            self.quantity.set_in_unit(value, unit.unit);
+        }
+        response
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum InputMode {
+    Sma,
+    Altitude,
+    Period,
+    Velocity,
+}
+
+impl InputMode {
+    pub fn name(&self) -> &str {
+        match self {
+            InputMode::Sma => "Semi-major axis",
+            InputMode::Altitude => "Altitude",
+            InputMode::Period => "Orbital period",
+            InputMode::Velocity => "Orbital velocity",
+        }
+    }
+}
+
+// Altitude, orbital period or circular velocity input of a body, converted back to its semi-major axis.
+pub struct OrbitInput<'a> {
+    mode: InputMode,
+    sma: &'a mut Distance,
+    text: &'a mut String,
+    parent: Parent,
+    range: std::ops::RangeInclusive<Distance>,
+}
+
+impl<'a> OrbitInput<'a> {
+    pub fn new(mode: InputMode, sma: &'a mut Distance, text: &'a mut String, parent: Parent, range: std::ops::RangeInclusive<Distance>) -> Self {
+        Self {
+            mode,
+            sma,
+            text,
+            parent,
+            range,
+        }
+    }
+}
+
+impl<'a> Widget for OrbitInput<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let planet = |sma: Distance| Planet::new(sma, self.parent);
+        let (min, max) = (planet(*self.range.start()), planet(*self.range.end()));
+        let current = planet(*self.sma);
+
+        match (self.mode, self.parent.radius()) {
+            (InputMode::Altitude, Some(radius)) => {
+                let mut altitude = (*self.sma - radius).km();
+                let response = ui.add(SliderWithText::new(
+                    &mut altitude, self.text,
+                    0.0..=(*self.range.end() - radius).km()
+                )
+                    .suffix(" km")
+                );
+                *self.sma = radius + Distance::from_kilometers(altitude);
+                response
+            }
+            (InputMode::Period, _) => {
+                let mut period = Duration::from_seconds(current.period()).d();
+                let response = ui.add(SliderWithText::new(
+                    &mut period, self.text,
+                    min.period() / SECONDS_DAY..=max.period() / SECONDS_DAY
+                )
+                    .suffix(" d")
+                );
+                *self.sma = Planet::from_period(Duration::from_days(period), self.parent).sma();
+                response
+            }
+            _ => {
+                let mut velocity = current.orbital_velocity().kps();
+                let response = ui.add(SliderWithText::new(
+                    &mut velocity, self.text,
+                    max.orbital_velocity().kps()..=min.orbital_velocity().kps()
+                )
+                    .suffix(" km/s")
+                );
+                *self.sma = Planet::from_orbital_velocity(Velocity::from_kilometers_per_second(velocity), self.parent).sma();
+                response
+            }
+        }
+    }
+}
+
+// Shape and orientation of a body's orbit beyond its semi-major axis, angles in degrees.
+#[derive(Copy, Clone, PartialEq, Default, serde::Deserialize, serde::Serialize)]
+pub struct OrbitParameters {
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub argument_of_periapsis: f64,
+}
+
+impl OrbitParameters {
+    pub fn orbit(&self, sma: Distance, parent: Parent) -> Orbit {
+        Orbit::new(sma, self.eccentricity, parent)
+            .with_inclination(self.inclination.to_radians())
+            .with_argument_of_periapsis(self.argument_of_periapsis.to_radians())
+    }
+
+    // Collapsed by default, keeping the simple semi-major axis input.
+    pub fn ui(&mut self, ui: &mut Ui, id_source: &str) {
+        CollapsingHeader::new("Advanced orbit parameters")
+            .id_source(id_source)
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.add(Slider::new(&mut self.eccentricity, 0.0..=0.99).text("Eccentricity"));
+                ui.add(Slider::new(&mut self.inclination, 0.0..=180.0).text("Inclination").suffix(" °"));
+                ui.add(Slider::new(&mut self.argument_of_periapsis, 0.0..=360.0).text("Argument of periapsis").suffix(" °"));
+            });
+    }
+}